@@ -0,0 +1,338 @@
+use jni::JNIEnv;
+use jni::objects::{JClass, JString};
+use jni::sys::{jboolean, jfloat, jfloatArray, jint, jlong, jlongArray, JNI_TRUE};
+use skia_safe::{Color, Picture, Point3};
+
+use crate::common::context::compositing::composite_operation_type::CompositeOperationType;
+use crate::common::context::Context;
+use crate::common::context::fill_and_stroke_styles::paint::PaintStyle;
+use crate::common::context::filters::canvas_filter::{
+    CanvasFilter, ColorChannelSelector, LightSource, Lighting, MorphologyOperator,
+};
+use crate::common::context::image_asset::ImageAsset;
+use crate::common::utils::color::parse_color;
+
+// Filters are passed around as handles, 0 standing for the source graphic when used as an
+// input. Every function returns 0 if the filter could not be created.
+
+fn filter_ref<'a>(filter: jlong) -> Option<&'a CanvasFilter> {
+    if filter == 0 {
+        return None;
+    }
+    unsafe {
+        let filter: *const CanvasFilter = filter as _;
+        Some(&*filter)
+    }
+}
+
+fn to_handle(filter: Option<CanvasFilter>) -> jlong {
+    match filter {
+        Some(filter) => Box::into_raw(Box::new(filter)) as jlong,
+        None => 0,
+    }
+}
+
+fn to_lighting(specular: jboolean, constant: jfloat, exponent: jfloat) -> Lighting {
+    if specular == JNI_TRUE {
+        Lighting::Specular { constant, exponent }
+    } else {
+        Lighting::Diffuse { constant }
+    }
+}
+
+fn to_light_color(env: &JNIEnv, color: JString) -> Color {
+    match env.get_string(color) {
+        Ok(color) => {
+            let color = color.to_string_lossy();
+            parse_color(color.as_ref()).unwrap_or(Color::WHITE)
+        }
+        Err(_) => Color::WHITE,
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasFilter_nativeSource(
+    _: JNIEnv,
+    _: JClass,
+) -> jlong {
+    to_handle(CanvasFilter::source())
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasFilter_nativeBlur(
+    _: JNIEnv,
+    _: JClass,
+    sigma_x: jfloat,
+    sigma_y: jfloat,
+    input: jlong,
+) -> jlong {
+    to_handle(CanvasFilter::blur(sigma_x, sigma_y, filter_ref(input)))
+}
+
+/// `matrix` must hold 20 values, a row major 4x5 matrix.
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasFilter_nativeColorMatrix(
+    env: JNIEnv,
+    _: JClass,
+    matrix: jfloatArray,
+    input: jlong,
+) -> jlong {
+    match env.get_array_length(matrix) {
+        Ok(20) => {}
+        _ => return 0,
+    }
+    let mut values = [0f32; 20];
+    if env.get_float_array_region(matrix, 0, &mut values).is_err() {
+        return 0;
+    }
+    to_handle(CanvasFilter::color_matrix(&values, filter_ref(input)))
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasFilter_nativeOffset(
+    _: JNIEnv,
+    _: JClass,
+    dx: jfloat,
+    dy: jfloat,
+    input: jlong,
+) -> jlong {
+    to_handle(CanvasFilter::offset(dx, dy, filter_ref(input)))
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasFilter_nativeMorphology(
+    _: JNIEnv,
+    _: JClass,
+    operator: jint,
+    radius_x: jfloat,
+    radius_y: jfloat,
+    input: jlong,
+) -> jlong {
+    to_handle(CanvasFilter::morphology(
+        MorphologyOperator::from(operator),
+        radius_x,
+        radius_y,
+        filter_ref(input),
+    ))
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasFilter_nativeDisplacementMap(
+    _: JNIEnv,
+    _: JClass,
+    x_channel: jint,
+    y_channel: jint,
+    scale: jfloat,
+    displacement: jlong,
+    color: jlong,
+) -> jlong {
+    to_handle(CanvasFilter::displacement_map(
+        ColorChannelSelector::from(x_channel),
+        ColorChannelSelector::from(y_channel),
+        scale,
+        filter_ref(displacement),
+        filter_ref(color),
+    ))
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasFilter_nativeDistantLighting(
+    env: JNIEnv,
+    _: JClass,
+    azimuth: jfloat,
+    elevation: jfloat,
+    specular: jboolean,
+    constant: jfloat,
+    exponent: jfloat,
+    color: JString,
+    surface_scale: jfloat,
+    input: jlong,
+) -> jlong {
+    to_handle(CanvasFilter::lighting(
+        LightSource::Distant { azimuth, elevation },
+        to_lighting(specular, constant, exponent),
+        to_light_color(&env, color),
+        surface_scale,
+        filter_ref(input),
+    ))
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasFilter_nativePointLighting(
+    env: JNIEnv,
+    _: JClass,
+    x: jfloat,
+    y: jfloat,
+    z: jfloat,
+    specular: jboolean,
+    constant: jfloat,
+    exponent: jfloat,
+    color: JString,
+    surface_scale: jfloat,
+    input: jlong,
+) -> jlong {
+    to_handle(CanvasFilter::lighting(
+        LightSource::Point {
+            location: Point3::new(x, y, z),
+        },
+        to_lighting(specular, constant, exponent),
+        to_light_color(&env, color),
+        surface_scale,
+        filter_ref(input),
+    ))
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasFilter_nativeSpotLighting(
+    env: JNIEnv,
+    _: JClass,
+    x: jfloat,
+    y: jfloat,
+    z: jfloat,
+    target_x: jfloat,
+    target_y: jfloat,
+    target_z: jfloat,
+    specular_exponent: jfloat,
+    limiting_cone_angle: jfloat,
+    specular: jboolean,
+    constant: jfloat,
+    exponent: jfloat,
+    color: JString,
+    surface_scale: jfloat,
+    input: jlong,
+) -> jlong {
+    to_handle(CanvasFilter::lighting(
+        LightSource::Spot {
+            location: Point3::new(x, y, z),
+            target: Point3::new(target_x, target_y, target_z),
+            specular_exponent,
+            limiting_cone_angle,
+        },
+        to_lighting(specular, constant, exponent),
+        to_light_color(&env, color),
+        surface_scale,
+        filter_ref(input),
+    ))
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasFilter_nativeBlend(
+    _: JNIEnv,
+    _: JClass,
+    mode: jint,
+    background: jlong,
+    foreground: jlong,
+) -> jlong {
+    to_handle(CanvasFilter::blend(
+        CompositeOperationType::from(mode),
+        filter_ref(background),
+        filter_ref(foreground),
+    ))
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasFilter_nativeMerge(
+    env: JNIEnv,
+    _: JClass,
+    inputs: jlongArray,
+) -> jlong {
+    let length = match env.get_array_length(inputs) {
+        Ok(length) => length as usize,
+        Err(_) => return 0,
+    };
+    let mut handles = vec![0 as jlong; length];
+    if env.get_long_array_region(inputs, 0, handles.as_mut_slice()).is_err() {
+        return 0;
+    }
+    let inputs: Vec<Option<&CanvasFilter>> =
+        handles.iter().map(|input| filter_ref(*input)).collect();
+    to_handle(CanvasFilter::merge(inputs.as_slice()))
+}
+
+/// Patterns in `style` are sampled with the image smoothing settings of `context`.
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasFilter_nativeShader(
+    _: JNIEnv,
+    _: JClass,
+    context: jlong,
+    style: jlong,
+) -> jlong {
+    if context == 0 || style == 0 {
+        return 0;
+    }
+    unsafe {
+        let context: *const Context = context as _;
+        let context = &*context;
+        let style: *const PaintStyle = style as _;
+        let style = &*style;
+        to_handle(CanvasFilter::shader(style, context.state.image_filter_quality()))
+    }
+}
+
+/// `picture` is a `TNSPicture` handle.
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasFilter_nativePicture(
+    _: JNIEnv,
+    _: JClass,
+    picture: jlong,
+) -> jlong {
+    if picture == 0 {
+        return 0;
+    }
+    let picture: *const Picture = picture as _;
+    let picture = unsafe { &*picture };
+    to_handle(CanvasFilter::picture(picture.clone()))
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasFilter_nativeCompose(
+    _: JNIEnv,
+    _: JClass,
+    outer: jlong,
+    inner: jlong,
+) -> jlong {
+    match (filter_ref(outer), filter_ref(inner)) {
+        (Some(outer), Some(inner)) => to_handle(CanvasFilter::compose(outer, inner)),
+        _ => 0,
+    }
+}
+
+/// Returns a new image asset holding the filtered pixels, or 0 on failure.
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasFilter_nativeApplyToAsset(
+    _: JNIEnv,
+    _: JClass,
+    filter: jlong,
+    asset: jlong,
+) -> jlong {
+    if asset == 0 {
+        return 0;
+    }
+    let filter = match filter_ref(filter) {
+        Some(filter) => filter,
+        None => return 0,
+    };
+    unsafe {
+        let asset: *const ImageAsset = asset as _;
+        let asset = &*asset;
+        match filter.apply_to_asset(asset) {
+            Some(output) => Box::into_raw(Box::new(output)) as jlong,
+            None => 0,
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasFilter_nativeDestroy(
+    _: JNIEnv,
+    _: JClass,
+    filter: jlong,
+) {
+    if filter == 0 {
+        return;
+    }
+    unsafe {
+        let filter: *mut CanvasFilter = filter as _;
+        let _ = Box::from_raw(filter);
+    }
+}
@@ -1,30 +1,35 @@
-use std::str::FromStr;
-
-
 use jni::JNIEnv;
 use jni::objects::{JClass, JObject, JString, ReleaseMode};
 use jni::sys::{
-    jboolean, jbyteArray, jfloat, jfloatArray, jint, jlong, JNI_FALSE, JNI_TRUE, jobject, jstring,
+    jboolean, jbyteArray, jfloat, jfloatArray, jint, jintArray, jlong, JNI_FALSE, JNI_TRUE, jobject,
+    jstring,
 };
-use skia_safe::Rect;
+use skia_safe::{Color, Picture, Point, Rect};
 
+use crate::common::context::box_shadows::BoxShadow;
+use crate::common::context::color_space::PredefinedColorSpace;
 use crate::common::context::compositing::composite_operation_type::CompositeOperationType;
 use crate::common::context::Context;
 use crate::common::context::drawing_paths::fill_rule::FillRule;
+use crate::common::context::fill_and_stroke_styles::gradient::Gradient;
 use crate::common::context::fill_and_stroke_styles::paint::PaintStyle;
 use crate::common::context::fill_and_stroke_styles::pattern::Repetition;
+use crate::common::context::filters::canvas_filter::CanvasFilter;
 use crate::common::context::image_asset::ImageAsset;
 use crate::common::context::image_smoothing::ImageSmoothingQuality;
+use crate::common::context::layers::LayerOptions;
 
 use crate::common::context::line_styles::line_join::LineJoin;
+use crate::common::context::masks::MaskType;
 use crate::common::context::matrix::Matrix;
 use crate::common::context::paths::path::Path;
-use crate::common::context::pixel_manipulation::image_data::ImageData;
+use crate::common::context::pixel_manipulation::image_data::{ImageData, ImageDataStorageFormat};
 use crate::common::context::text_styles::text_align::TextAlign;
 use crate::common::context::text_styles::text_baseline::TextBaseLine;
 use crate::common::context::text_styles::text_direction::TextDirection;
+use crate::common::context::text_styles::text_rendering::{TextEdging, TextHinting, TextRendering};
 use crate::common::ffi::paint_style_value::{PaintStyleValueType};
-use crate::common::utils::color::to_parsed_color;
+use crate::common::utils::color::{parse_color_4f, to_parsed_color};
 use crate::common::utils::image::{from_image_slice, from_image_slice_encoded};
 
 use ndk::{
@@ -97,7 +102,8 @@ fn get_style(env: JNIEnv, context: jlong, is_fill: bool) -> jobject {
         let type_ = match style {
             PaintStyle::Color(_) => PaintStyleValueType::PaintStyleValueTypeColor,
             PaintStyle::Gradient(_) => PaintStyleValueType::PaintStyleValueTypeGradient,
-            PaintStyle::Pattern(_) => PaintStyleValueType::PaintStyleValueTypePattern
+            PaintStyle::Pattern(_) => PaintStyleValueType::PaintStyleValueTypePattern,
+            PaintStyle::RuntimeEffect(_) => PaintStyleValueType::PaintStyleValueTypeRuntimeEffect,
         };
 
         let style = Box::into_raw(Box::new(style)) as jlong;
@@ -149,6 +155,127 @@ pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_
     }
 }
 
+/// Sets the filter graph as the context filter, 0 clearing it.
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeSetCanvasFilter(
+    _: JNIEnv,
+    _: JClass,
+    context: jlong,
+    filter: jlong,
+) {
+    unsafe {
+        if context == 0 {
+            return;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        if filter == 0 {
+            context.set_canvas_filter(None)
+        } else {
+            let filter: *const CanvasFilter = filter as _;
+            context.set_canvas_filter(Some(&*filter))
+        }
+    }
+}
+
+/// Filters what is already drawn inside `path`, 0 using the current path. Returns false if
+/// `filter` does not parse.
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeApplyBackdropFilter(
+    env: JNIEnv,
+    _: JClass,
+    context: jlong,
+    filter: JString,
+    path: jlong,
+    rule: jint,
+) -> jboolean {
+    unsafe {
+        if context == 0 {
+            return JNI_FALSE;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        if let Ok(filter) = env.get_string(filter) {
+            let filter = filter.to_string_lossy();
+            let applied = if path == 0 {
+                context.apply_backdrop_filter(filter.as_ref(), None, FillRule::from(rule))
+            } else {
+                let path: *mut Path = path as _;
+                context.apply_backdrop_filter(filter.as_ref(), Some(&mut *path), FillRule::from(rule))
+            };
+            if applied {
+                return JNI_TRUE;
+            }
+        }
+        JNI_FALSE
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeApplyBackdropCanvasFilter(
+    _: JNIEnv,
+    _: JClass,
+    context: jlong,
+    filter: jlong,
+    path: jlong,
+    rule: jint,
+) {
+    unsafe {
+        if context == 0 || filter == 0 {
+            return;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        let filter: *const CanvasFilter = filter as _;
+        if path == 0 {
+            context.apply_backdrop_canvas_filter(&*filter, None, FillRule::from(rule))
+        } else {
+            let path: *mut Path = path as _;
+            context.apply_backdrop_canvas_filter(&*filter, Some(&mut *path), FillRule::from(rule))
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeRegisterSvgFilters(
+    env: JNIEnv,
+    _: JClass,
+    context: jlong,
+    svg: JString,
+) -> jint {
+    unsafe {
+        if context == 0 {
+            return -1;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        if let Ok(svg) = env.get_string(svg) {
+            let svg = svg.to_string_lossy();
+            match context.register_svg_filters(svg.as_ref()) {
+                Ok(count) => return count as jint,
+                Err(error) => log::debug!("svg filter parse error {}", error),
+            }
+        }
+        -1
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeClearSvgFilters(
+    _: JNIEnv,
+    _: JClass,
+    context: jlong,
+) {
+    unsafe {
+        if context == 0 {
+            return;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        context.clear_svg_filters()
+    }
+}
+
 #[no_mangle]
 pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeSetFont(
     env: JNIEnv,
@@ -183,6 +310,74 @@ pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_
     }
 }
 
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeSetFontVariationSettings(
+    env: JNIEnv,
+    _: JClass,
+    context: jlong,
+    settings: JString,
+) {
+    unsafe {
+        if context == 0 {
+            return;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        if let Ok(settings) = env.get_string(settings) {
+            let settings = settings.to_string_lossy();
+            context.set_font_variation_settings(settings.as_ref());
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeGetFontVariationSettings(
+    env: JNIEnv,
+    _: JClass,
+    context: jlong,
+) -> jstring {
+    unsafe {
+        let context: *const Context = context as _;
+        let context = &*context;
+        let settings = context.font_variation_settings();
+        env.new_string(settings).unwrap().into_raw()
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeSetFontFeatureSettings(
+    env: JNIEnv,
+    _: JClass,
+    context: jlong,
+    settings: JString,
+) {
+    unsafe {
+        if context == 0 {
+            return;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        if let Ok(settings) = env.get_string(settings) {
+            let settings = settings.to_string_lossy();
+            context.set_font_feature_settings(settings.as_ref());
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeGetFontFeatureSettings(
+    env: JNIEnv,
+    _: JClass,
+    context: jlong,
+) -> jstring {
+    unsafe {
+        let context: *const Context = context as _;
+        let context = &*context;
+        let settings = context.font_feature_settings();
+        env.new_string(settings).unwrap().into_raw()
+    }
+}
+
 #[no_mangle]
 pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeSetGlobalAlpha(
     _: JNIEnv,
@@ -243,6 +438,73 @@ pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_
     }
 }
 
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeSetGlobalCompositeOperationName(
+    env: JNIEnv,
+    _: JClass,
+    context: jlong,
+    operation: JString,
+) -> jboolean {
+    unsafe {
+        if context == 0 {
+            return JNI_FALSE;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        if let Ok(operation) = env.get_string(operation) {
+            let operation = operation.to_string_lossy();
+            if context.set_global_composite_operation_name(operation.as_ref()) {
+                return JNI_TRUE;
+            }
+        }
+        JNI_FALSE
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeGetGlobalCompositeOperationName(
+    env: JNIEnv,
+    _: JClass,
+    context: jlong,
+) -> jstring {
+    unsafe {
+        if context == 0 {
+            return env.new_string("").unwrap().into_raw();
+        }
+        let context: *const Context = context as _;
+        let context = &*context;
+        env.new_string(context.global_composite_operation_name())
+            .unwrap()
+            .into_raw()
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeRegisterBlender(
+    env: JNIEnv,
+    _: JClass,
+    context: jlong,
+    name: JString,
+    sksl: JString,
+) -> jboolean {
+    unsafe {
+        if context == 0 {
+            return JNI_FALSE;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        if let (Ok(name), Ok(sksl)) = (env.get_string(name), env.get_string(sksl)) {
+            let name = name.to_string_lossy();
+            let sksl = sksl.to_string_lossy();
+            match context.register_blender(name.as_ref(), sksl.as_ref()) {
+                Ok(_) => return JNI_TRUE,
+                Err(error) => log::debug!("blender compile error {}", error),
+            }
+        }
+        JNI_FALSE
+    }
+}
+
 #[no_mangle]
 pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeSetImageSmoothingEnabled(
     _: JNIEnv,
@@ -503,7 +765,7 @@ pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_
         }
         let context: *mut Context = context as _;
         let context = &mut *context;
-        context.set_shadow_color(skia_safe::Color::from_argb(a, r, g, b))
+        context.set_shadow_color(skia_safe::Color::from_argb(a, r, g, b).into())
     }
 }
 
@@ -522,13 +784,8 @@ pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_
         let context = &mut *context;
         if let Ok(color) = env.get_string(color) {
             let color = color.to_string_lossy();
-            if let Ok(color) = css_color_parser::Color::from_str(color.as_ref()) {
-                context.set_shadow_color(skia_safe::Color::from_argb(
-                    (color.a * 255.0) as u8,
-                    color.r,
-                    color.g,
-                    color.b,
-                ))
+            if let Some(color) = parse_color_4f(color.as_ref(), context.font_color) {
+                context.set_shadow_color(color)
             }
         }
     }
@@ -543,7 +800,7 @@ pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_
     unsafe {
         let context: *const Context = context as _;
         let context = &*context;
-        env.new_string(to_parsed_color(context.shadow_color()))
+        env.new_string(to_parsed_color(context.shadow_color().to_color()))
             .unwrap()
             .into_raw()
     }
@@ -695,16 +952,11 @@ pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_
 }
 
 #[no_mangle]
-pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeArc(
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeSetTextRendering(
     _: JNIEnv,
     _: JClass,
     context: jlong,
-    x: jfloat,
-    y: jfloat,
-    radius: jfloat,
-    start_angle: jfloat,
-    end_angle: jfloat,
-    anti_clockwise: jboolean,
+    value: jint,
 ) {
     unsafe {
         if context == 0 {
@@ -712,43 +964,29 @@ pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_
         }
         let context: *mut Context = context as _;
         let context = &mut *context;
-        context.arc(
-            x,
-            y,
-            radius,
-            start_angle,
-            end_angle,
-            anti_clockwise == JNI_TRUE,
-        )
+        context.set_text_rendering(TextRendering::from(value));
     }
 }
 
 #[no_mangle]
-pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeArcTo(
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeGetTextRendering(
     _: JNIEnv,
     _: JClass,
     context: jlong,
-    x1: jfloat,
-    y1: jfloat,
-    x2: jfloat,
-    y2: jfloat,
-    radius: jfloat,
-) {
+) -> jint {
     unsafe {
-        if context == 0 {
-            return;
-        }
-        let context: *mut Context = context as _;
-        let context = &mut *context;
-        context.arc_to(x1, y1, x2, y2, radius)
+        let context: *const Context = context as _;
+        let context = &*context;
+        context.text_rendering().into()
     }
 }
 
 #[no_mangle]
-pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeBeginPath(
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeSetTextEdging(
     _: JNIEnv,
     _: JClass,
     context: jlong,
+    value: jint,
 ) {
     unsafe {
         if context == 0 {
@@ -756,41 +994,29 @@ pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_
         }
         let context: *mut Context = context as _;
         let context = &mut *context;
-        context.begin_path()
+        context.set_text_edging(TextEdging::from(value));
     }
 }
 
 #[no_mangle]
-pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeBezierCurveTo(
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeGetTextEdging(
     _: JNIEnv,
     _: JClass,
     context: jlong,
-    cp1x: jfloat,
-    cp1y: jfloat,
-    cp2x: jfloat,
-    cp2y: jfloat,
-    x: jfloat,
-    y: jfloat,
-) {
+) -> jint {
     unsafe {
-        if context == 0 {
-            return;
-        }
-        let context: *mut Context = context as _;
-        let context = &mut *context;
-        context.bezier_curve_to(cp1x, cp1y, cp2x, cp2y, x, y)
+        let context: *const Context = context as _;
+        let context = &*context;
+        context.text_edging().into()
     }
 }
 
 #[no_mangle]
-pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeClearRect(
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeSetTextHinting(
     _: JNIEnv,
     _: JClass,
     context: jlong,
-    x: jfloat,
-    y: jfloat,
-    width: jfloat,
-    height: jfloat,
+    value: jint,
 ) {
     unsafe {
         if context == 0 {
@@ -798,35 +1024,29 @@ pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_
         }
         let context: *mut Context = context as _;
         let context = &mut *context;
-        context.clear_rect(x, y, width, height)
+        context.set_text_hinting(TextHinting::from(value));
     }
 }
 
 #[no_mangle]
-pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeClip(
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeGetTextHinting(
     _: JNIEnv,
     _: JClass,
     context: jlong,
-    path: jlong,
-    rule: jint,
-) {
+) -> jint {
     unsafe {
-        if context == 0 || path == 0 {
-            return;
-        }
-        let context: *mut Context = context as _;
-        let context = &mut *context;
-        let path: *mut Path = path as _;
-        context.clip(Some(&mut *path), Some(FillRule::from(rule)))
+        let context: *const Context = context as _;
+        let context = &*context;
+        context.text_hinting().into()
     }
 }
 
 #[no_mangle]
-pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeClipRule(
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeSetTextSubpixelPositioning(
     _: JNIEnv,
     _: JClass,
     context: jlong,
-    rule: jint,
+    value: jboolean,
 ) {
     unsafe {
         if context == 0 {
@@ -834,7 +1054,236 @@ pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_
         }
         let context: *mut Context = context as _;
         let context = &mut *context;
-        context.clip(None, Some(FillRule::from(rule)))
+        context.set_text_subpixel_positioning(value == JNI_TRUE);
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeGetTextSubpixelPositioning(
+    _: JNIEnv,
+    _: JClass,
+    context: jlong,
+) -> jboolean {
+    unsafe {
+        let context: *const Context = context as _;
+        let context = &*context;
+        if context.text_subpixel_positioning() {
+            JNI_TRUE
+        } else {
+            JNI_FALSE
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeSetTextEmbeddedBitmaps(
+    _: JNIEnv,
+    _: JClass,
+    context: jlong,
+    value: jboolean,
+) {
+    unsafe {
+        if context == 0 {
+            return;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        context.set_text_embedded_bitmaps(value == JNI_TRUE);
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeGetTextEmbeddedBitmaps(
+    _: JNIEnv,
+    _: JClass,
+    context: jlong,
+) -> jboolean {
+    unsafe {
+        let context: *const Context = context as _;
+        let context = &*context;
+        if context.text_embedded_bitmaps() {
+            JNI_TRUE
+        } else {
+            JNI_FALSE
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeSetTextLinearMetrics(
+    _: JNIEnv,
+    _: JClass,
+    context: jlong,
+    value: jboolean,
+) {
+    unsafe {
+        if context == 0 {
+            return;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        context.set_text_linear_metrics(value == JNI_TRUE);
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeGetTextLinearMetrics(
+    _: JNIEnv,
+    _: JClass,
+    context: jlong,
+) -> jboolean {
+    unsafe {
+        let context: *const Context = context as _;
+        let context = &*context;
+        if context.text_linear_metrics() {
+            JNI_TRUE
+        } else {
+            JNI_FALSE
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeArc(
+    _: JNIEnv,
+    _: JClass,
+    context: jlong,
+    x: jfloat,
+    y: jfloat,
+    radius: jfloat,
+    start_angle: jfloat,
+    end_angle: jfloat,
+    anti_clockwise: jboolean,
+) {
+    unsafe {
+        if context == 0 {
+            return;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        context.arc(
+            x,
+            y,
+            radius,
+            start_angle,
+            end_angle,
+            anti_clockwise == JNI_TRUE,
+        )
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeArcTo(
+    _: JNIEnv,
+    _: JClass,
+    context: jlong,
+    x1: jfloat,
+    y1: jfloat,
+    x2: jfloat,
+    y2: jfloat,
+    radius: jfloat,
+) {
+    unsafe {
+        if context == 0 {
+            return;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        context.arc_to(x1, y1, x2, y2, radius)
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeBeginPath(
+    _: JNIEnv,
+    _: JClass,
+    context: jlong,
+) {
+    unsafe {
+        if context == 0 {
+            return;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        context.begin_path()
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeBezierCurveTo(
+    _: JNIEnv,
+    _: JClass,
+    context: jlong,
+    cp1x: jfloat,
+    cp1y: jfloat,
+    cp2x: jfloat,
+    cp2y: jfloat,
+    x: jfloat,
+    y: jfloat,
+) {
+    unsafe {
+        if context == 0 {
+            return;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        context.bezier_curve_to(cp1x, cp1y, cp2x, cp2y, x, y)
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeClearRect(
+    _: JNIEnv,
+    _: JClass,
+    context: jlong,
+    x: jfloat,
+    y: jfloat,
+    width: jfloat,
+    height: jfloat,
+) {
+    unsafe {
+        if context == 0 {
+            return;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        context.clear_rect(x, y, width, height)
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeClip(
+    _: JNIEnv,
+    _: JClass,
+    context: jlong,
+    path: jlong,
+    rule: jint,
+) {
+    unsafe {
+        if context == 0 || path == 0 {
+            return;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        let path: *mut Path = path as _;
+        context.clip(Some(&mut *path), Some(FillRule::from(rule)))
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeClipRule(
+    _: JNIEnv,
+    _: JClass,
+    context: jlong,
+    rule: jint,
+) {
+    unsafe {
+        if context == 0 {
+            return;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        context.clip(None, Some(FillRule::from(rule)))
     }
 }
 
@@ -864,6 +1313,38 @@ pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_
     Box::into_raw(Box::new(Context::create_image_data(width, height))) as jlong
 }
 
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeCreateImageDataWithColorSpace(
+    _: JNIEnv,
+    _: JClass,
+    width: jint,
+    height: jint,
+    color_space: jint,
+) -> jlong {
+    Box::into_raw(Box::new(Context::create_image_data_with_color_space(
+        width,
+        height,
+        PredefinedColorSpace::from(color_space),
+    ))) as jlong
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeCreateImageDataWithSettings(
+    _: JNIEnv,
+    _: JClass,
+    width: jint,
+    height: jint,
+    color_space: jint,
+    storage_format: jint,
+) -> jlong {
+    Box::into_raw(Box::new(Context::create_image_data_with_settings(
+        width,
+        height,
+        PredefinedColorSpace::from(color_space),
+        ImageDataStorageFormat::from(storage_format),
+    ))) as jlong
+}
+
 #[no_mangle]
 pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeCreateLinearGradient(
     _: JNIEnv,
@@ -942,6 +1423,134 @@ pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_
     }
 }
 
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeBeginRecording(
+    _: JNIEnv,
+    _: JClass,
+    context: jlong,
+    width: jfloat,
+    height: jfloat,
+) -> jboolean {
+    unsafe {
+        if context == 0 {
+            return JNI_FALSE;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        if context.begin_recording(width, height) {
+            JNI_TRUE
+        } else {
+            JNI_FALSE
+        }
+    }
+}
+
+/// Returns a `TNSPicture` handle, or 0 if the recording could not be ended.
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeEndRecording(
+    _: JNIEnv,
+    _: JClass,
+    context: jlong,
+) -> jlong {
+    unsafe {
+        if context == 0 {
+            return 0;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        match context.end_recording() {
+            Some(picture) => Box::into_raw(Box::new(picture)) as jlong,
+            None => 0,
+        }
+    }
+}
+
+/// `picture` is a `TNSPicture` handle; its cull rect becomes the pattern tile.
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeCreatePatternFromPicture(
+    _: JNIEnv,
+    _: JClass,
+    context: jlong,
+    picture: jlong,
+    repetition: jint,
+) -> jlong {
+    unsafe {
+        if context == 0 || picture == 0 {
+            return 0;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        let picture: *const Picture = picture as _;
+        let picture = &*picture;
+        let pattern =
+            context.create_pattern_from_picture(picture.clone(), Repetition::from(repetition));
+        Box::into_raw(Box::new(PaintStyle::Pattern(pattern))) as jlong
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeCreatePatternFromSvg(
+    env: JNIEnv,
+    _: JClass,
+    context: jlong,
+    svg: JString,
+    width: jfloat,
+    height: jfloat,
+    repetition: jint,
+) -> jlong {
+    unsafe {
+        if context == 0 {
+            return 0;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        if let Ok(svg) = env.get_string(svg) {
+            let svg = svg.to_string_lossy();
+            if let Some(pattern) = context.create_pattern_from_svg(
+                svg.as_ref(),
+                width,
+                height,
+                Repetition::from(repetition),
+            ) {
+                return Box::into_raw(Box::new(PaintStyle::Pattern(pattern))) as jlong;
+            }
+        }
+        0
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeCreatePatternFromPath(
+    _: JNIEnv,
+    _: JClass,
+    context: jlong,
+    path: jlong,
+    is_fill: jboolean,
+    width: jfloat,
+    height: jfloat,
+    repetition: jint,
+) -> jlong {
+    unsafe {
+        if context == 0 || path == 0 {
+            return 0;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        let path: *const Path = path as _;
+        let path = &*path;
+        match context.create_pattern_from_path(
+            path,
+            is_fill == JNI_TRUE,
+            width,
+            height,
+            Repetition::from(repetition),
+        ) {
+            Some(pattern) => Box::into_raw(Box::new(PaintStyle::Pattern(pattern))) as jlong,
+            None => 0,
+        }
+    }
+}
+
 #[no_mangle]
 pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeCreatePattern(
     env: JNIEnv,
@@ -1047,6 +1656,33 @@ pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_
     }
 }
 
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeCreateRuntimeEffect(
+    env: JNIEnv,
+    _: JClass,
+    context: jlong,
+    sksl: JString,
+) -> jlong {
+    unsafe {
+        if context == 0 {
+            return 0;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        if let Ok(sksl) = env.get_string(sksl) {
+            let sksl = sksl.to_string_lossy();
+            return match context.create_runtime_effect(sksl.as_ref()) {
+                Ok(effect) => Box::into_raw(Box::new(PaintStyle::RuntimeEffect(effect))) as jlong,
+                Err(error) => {
+                    log::debug!("runtime effect compile error {}", error);
+                    0
+                }
+            };
+        }
+        0
+    }
+}
+
 #[no_mangle]
 pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeCreateRadialGradient(
     _: JNIEnv,
@@ -1092,6 +1728,88 @@ pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_
     }
 }
 
+/// Reads the twelve control points and four ARGB corner colors of a patch.
+fn patch_from_arrays(
+    env: &JNIEnv,
+    points: jfloatArray,
+    colors: jintArray,
+) -> Option<([Point; 12], [Color; 4])> {
+    let mut points_buf = vec![0f32; env.get_array_length(points).ok()? as usize];
+    env.get_float_array_region(points, 0, points_buf.as_mut_slice()).ok()?;
+    let mut colors_buf = vec![0i32; env.get_array_length(colors).ok()? as usize];
+    env.get_int_array_region(colors, 0, colors_buf.as_mut_slice()).ok()?;
+    let colors_buf: Vec<u32> = colors_buf.into_iter().map(|color| color as u32).collect();
+    Some((
+        Gradient::cubics_from_slice(points_buf.as_slice())?,
+        Gradient::corner_colors_from_slice(colors_buf.as_slice())?,
+    ))
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeCreateMeshGradient(
+    env: JNIEnv,
+    _: JClass,
+    context: jlong,
+    points: jfloatArray,
+    colors: jintArray,
+) -> jlong {
+    unsafe {
+        if context == 0 {
+            return 0;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        match patch_from_arrays(&env, points, colors) {
+            Some((cubics, colors)) => Box::into_raw(Box::new(PaintStyle::Gradient(
+                context.create_mesh_gradient(cubics, colors),
+            ))) as jlong,
+            None => 0,
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeCreateFourCornerGradient(
+    _: JNIEnv,
+    _: JClass,
+    context: jlong,
+    x: jfloat,
+    y: jfloat,
+    width: jfloat,
+    height: jfloat,
+) -> jlong {
+    unsafe {
+        if context == 0 {
+            return 0;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        Box::into_raw(Box::new(PaintStyle::Gradient(
+            context.create_four_corner_gradient(x, y, width, height),
+        ))) as jlong
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeDrawPatch(
+    env: JNIEnv,
+    _: JClass,
+    context: jlong,
+    points: jfloatArray,
+    colors: jintArray,
+) {
+    unsafe {
+        if context == 0 {
+            return;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        if let Some((cubics, colors)) = patch_from_arrays(&env, points, colors) {
+            context.draw_patch(&cubics, &colors)
+        }
+    }
+}
+
 fn draw_image(
     context: jlong,
     image_data: &[u8],
@@ -1588,7 +2306,80 @@ pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_
 }
 
 #[no_mangle]
-pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeGetImageData(
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeTextToPath(
+    env: JNIEnv,
+    _: JClass,
+    context: jlong,
+    text: JString,
+    x: jfloat,
+    y: jfloat,
+    width: jfloat,
+) -> jlong {
+    unsafe {
+        if context == 0 {
+            return Box::into_raw(Box::new(Path::new())) as jlong;
+        }
+        let context: *const Context = context as _;
+        let context = &*context;
+        match env.get_string(text) {
+            Ok(txt) => {
+                let txt = txt.to_string_lossy();
+                Box::into_raw(Box::new(context.text_to_path(txt.as_ref(), x, y, width))) as jlong
+            }
+            Err(_) => Box::into_raw(Box::new(Path::new())) as jlong,
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeGetImageData(
+    _: JNIEnv,
+    _: JClass,
+    context: jlong,
+    sx: jfloat,
+    sy: jfloat,
+    sw: jfloat,
+    sh: jfloat,
+) -> jlong {
+    unsafe {
+        if context == 0 {
+            return 0;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        Box::into_raw(Box::new(context.get_image_data(sx, sy, sw, sh))) as jlong
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeGetImageDataWithColorSpace(
+    _: JNIEnv,
+    _: JClass,
+    context: jlong,
+    sx: jfloat,
+    sy: jfloat,
+    sw: jfloat,
+    sh: jfloat,
+    color_space: jint,
+) -> jlong {
+    unsafe {
+        if context == 0 {
+            return 0;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        Box::into_raw(Box::new(context.get_image_data_with_color_space(
+            sx,
+            sy,
+            sw,
+            sh,
+            PredefinedColorSpace::from(color_space),
+        ))) as jlong
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeGetImageDataWithSettings(
     _: JNIEnv,
     _: JClass,
     context: jlong,
@@ -1596,6 +2387,8 @@ pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_
     sy: jfloat,
     sw: jfloat,
     sh: jfloat,
+    color_space: jint,
+    storage_format: jint,
 ) -> jlong {
     unsafe {
         if context == 0 {
@@ -1603,7 +2396,30 @@ pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_
         }
         let context: *mut Context = context as _;
         let context = &mut *context;
-        Box::into_raw(Box::new(context.get_image_data(sx, sy, sw, sh))) as jlong
+        Box::into_raw(Box::new(context.get_image_data_with_settings(
+            sx,
+            sy,
+            sw,
+            sh,
+            PredefinedColorSpace::from(color_space),
+            ImageDataStorageFormat::from(storage_format),
+        ))) as jlong
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeGetColorSpace(
+    _: JNIEnv,
+    _: JClass,
+    context: jlong,
+) -> jint {
+    unsafe {
+        if context == 0 {
+            return PredefinedColorSpace::default().into();
+        }
+        let context: *const Context = context as _;
+        let context = &*context;
+        context.color_space().into()
     }
 }
 
@@ -1636,9 +2452,7 @@ pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_
         }
         let context: *mut Context = context as _;
         let context = &mut *context;
-        let matrix = Matrix {
-            matrix: skia_safe::M44::from(context.get_transform().clone()),
-        };
+        let matrix = context.get_transform();
         Box::into_raw(Box::new(matrix)) as jlong
     }
 }
@@ -1842,6 +2656,85 @@ pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_
 }
 
 
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeReset(
+    _: JNIEnv,
+    _: JClass,
+    context: jlong,
+) {
+    unsafe {
+        if context == 0 {
+            return;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        context.reset()
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeResetClip(
+    _: JNIEnv,
+    _: JClass,
+    context: jlong,
+) {
+    unsafe {
+        if context == 0 {
+            return;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        context.reset_clip()
+    }
+}
+
+/// Returns the x, y, width and height of the clip in user space, or an empty array if the
+/// clip is empty.
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeGetClipBounds(
+    env: JNIEnv,
+    _: JClass,
+    context: jlong,
+) -> jfloatArray {
+    unsafe {
+        let bounds = if context == 0 {
+            None
+        } else {
+            let context: *mut Context = context as _;
+            let context = &mut *context;
+            context.clip_bounds()
+        };
+        let values = match bounds {
+            Some(bounds) => vec![bounds.x(), bounds.y(), bounds.width(), bounds.height()],
+            None => Vec::new(),
+        };
+        let array = env.new_float_array(values.len() as i32).unwrap();
+        env.set_float_array_region(array, 0, values.as_slice())
+            .unwrap_or(());
+        array
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeIsClipEmpty(
+    _: JNIEnv,
+    _: JClass,
+    context: jlong,
+) -> jboolean {
+    unsafe {
+        if context == 0 {
+            return JNI_TRUE;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        if context.is_clip_empty() {
+            JNI_TRUE
+        } else {
+            JNI_FALSE
+        }
+    }
+}
+
 #[no_mangle]
 pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeResetTransform(
     _: JNIEnv,
@@ -1874,6 +2767,244 @@ pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_
     }
 }
 
+/// Starts a layer composited with `alpha`, the CSS `filter`, null keeping the current filter,
+/// and `composite_operation`, a negative value keeping the current one.
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeBeginLayer(
+    env: JNIEnv,
+    _: JClass,
+    context: jlong,
+    alpha: jfloat,
+    filter: JString,
+    composite_operation: jint,
+) {
+    unsafe {
+        if context == 0 {
+            return;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        let mut options = LayerOptions::default();
+        options.alpha = alpha;
+        if !filter.is_null() {
+            if let Ok(filter) = env.get_string(filter) {
+                options.filter = Some(filter.to_string_lossy().to_string());
+            }
+        }
+        if composite_operation >= 0 {
+            options.composite_operation = Some(CompositeOperationType::from(composite_operation));
+        }
+        context.begin_layer(&options)
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeEndLayer(
+    _: JNIEnv,
+    _: JClass,
+    context: jlong,
+) -> jboolean {
+    unsafe {
+        if context == 0 {
+            return JNI_FALSE;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        if context.end_layer() {
+            JNI_TRUE
+        } else {
+            JNI_FALSE
+        }
+    }
+}
+
+/// Starts a layer masked by `asset` drawn into the rectangle, closed by `nativeEndLayer`.
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeBeginImageMask(
+    _: JNIEnv,
+    _: JClass,
+    context: jlong,
+    asset: jlong,
+    x: jfloat,
+    y: jfloat,
+    width: jfloat,
+    height: jfloat,
+    mask_type: jint,
+) -> jboolean {
+    unsafe {
+        if context == 0 || asset == 0 {
+            return JNI_FALSE;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        let asset: *const ImageAsset = asset as _;
+        if context.begin_image_mask(&*asset, x, y, width, height, MaskType::from(mask_type)) {
+            JNI_TRUE
+        } else {
+            JNI_FALSE
+        }
+    }
+}
+
+/// Starts a layer masked by a color, gradient or pattern, closed by `nativeEndLayer`.
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeBeginStyleMask(
+    _: JNIEnv,
+    _: JClass,
+    context: jlong,
+    style: jlong,
+    mask_type: jint,
+) -> jboolean {
+    unsafe {
+        if context == 0 || style == 0 {
+            return JNI_FALSE;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        let style: *const PaintStyle = style as _;
+        if context.begin_style_mask(&*style, MaskType::from(mask_type)) {
+            JNI_TRUE
+        } else {
+            JNI_FALSE
+        }
+    }
+}
+
+/// Starts a layer clipped to `path`, 0 using the current path, with edges faded over
+/// `feather` pixels, closed by `nativeEndLayer`.
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeBeginPathMask(
+    _: JNIEnv,
+    _: JClass,
+    context: jlong,
+    path: jlong,
+    rule: jint,
+    feather: jfloat,
+) {
+    unsafe {
+        if context == 0 {
+            return;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        if path == 0 {
+            context.begin_path_mask(None, FillRule::from(rule), feather)
+        } else {
+            let path: *mut Path = path as _;
+            context.begin_path_mask(Some(&mut *path), FillRule::from(rule), feather)
+        }
+    }
+}
+
+fn box_shadows_from_arrays(
+    env: &JNIEnv,
+    values: jfloatArray,
+    colors: jintArray,
+) -> Option<Vec<BoxShadow>> {
+    let mut values_buf = vec![0f32; env.get_array_length(values).ok()? as usize];
+    env.get_float_array_region(values, 0, values_buf.as_mut_slice()).ok()?;
+    let mut colors_buf = vec![0i32; env.get_array_length(colors).ok()? as usize];
+    env.get_int_array_region(colors, 0, colors_buf.as_mut_slice()).ok()?;
+    let colors_buf: Vec<u32> = colors_buf.into_iter().map(|color| color as u32).collect();
+    BoxShadow::list_from_slices(values_buf.as_slice(), colors_buf.as_slice())
+}
+
+/// Fills `path`, 0 using the current path, with box shadows given as five values each, offset
+/// x, offset y, blur, spread and inset, and one ARGB color per shadow.
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeFillWithBoxShadows(
+    env: JNIEnv,
+    _: JClass,
+    context: jlong,
+    path: jlong,
+    rule: jint,
+    values: jfloatArray,
+    colors: jintArray,
+) {
+    unsafe {
+        if context == 0 {
+            return;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        if let Some(shadows) = box_shadows_from_arrays(&env, values, colors) {
+            if path == 0 {
+                context.fill_with_box_shadows(None, FillRule::from(rule), shadows.as_slice())
+            } else {
+                let path: *mut Path = path as _;
+                context.fill_with_box_shadows(
+                    Some(&mut *path),
+                    FillRule::from(rule),
+                    shadows.as_slice(),
+                )
+            }
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeFillRectWithBoxShadows(
+    env: JNIEnv,
+    _: JClass,
+    context: jlong,
+    x: jfloat,
+    y: jfloat,
+    width: jfloat,
+    height: jfloat,
+    values: jfloatArray,
+    colors: jintArray,
+) {
+    unsafe {
+        if context == 0 {
+            return;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        if let Some(shadows) = box_shadows_from_arrays(&env, values, colors) {
+            let rect = Rect::from_xywh(x, y, width, height);
+            context.fill_rect_with_box_shadows(&rect, shadows.as_slice())
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeFillRoundRectWithBoxShadows(
+    env: JNIEnv,
+    _: JClass,
+    context: jlong,
+    x: jfloat,
+    y: jfloat,
+    width: jfloat,
+    height: jfloat,
+    top_left: jfloat,
+    top_right: jfloat,
+    bottom_right: jfloat,
+    bottom_left: jfloat,
+    values: jfloatArray,
+    colors: jintArray,
+) {
+    unsafe {
+        if context == 0 {
+            return;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        if let Some(shadows) = box_shadows_from_arrays(&env, values, colors) {
+            context.fill_round_rect_with_box_shadows(
+                x,
+                y,
+                width,
+                height,
+                [
+                    top_left, top_left, top_right, top_right, bottom_right, bottom_right,
+                    bottom_left, bottom_left,
+                ],
+                shadows.as_slice(),
+            )
+        }
+    }
+}
+
 #[no_mangle]
 pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeRotate(
     _: JNIEnv,
@@ -1984,7 +3115,26 @@ pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_
         let context = &mut *context;
         let matrix: *const Matrix = matrix as _;
         let matrix = &*matrix;
-        context.set_transform_matrix(&matrix.matrix.to_m33())
+        context.set_transform_matrix(matrix)
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasRenderingContext2D_nativeTransformMatrix(
+    _: JNIEnv,
+    _: JClass,
+    context: jlong,
+    matrix: jlong,
+) {
+    unsafe {
+        if context == 0 || matrix == 0 {
+            return;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        let matrix: *const Matrix = matrix as _;
+        let matrix = &*matrix;
+        context.transform_with_matrix(matrix)
     }
 }
 
@@ -3,8 +3,13 @@ use std::os::raw::{c_float, c_longlong};
 
 use jni::JNIEnv;
 use jni::objects::{JClass, JString};
+use jni::sys::{jboolean, jint, JNI_FALSE, JNI_TRUE};
 
+use crate::common::context::fill_and_stroke_styles::gradient_interpolation::{
+    HueInterpolationMethod, InterpolationColorSpace,
+};
 use crate::common::context::fill_and_stroke_styles::paint::PaintStyle;
+use crate::common::utils::color::parse_color;
 
 #[no_mangle]
 pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasGradient_nativeAddColorStop(
@@ -24,16 +29,8 @@ pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasGradient_nativeAddC
             PaintStyle::Gradient(gradient) => {
                 if let Ok(color) = env.get_string(color) {
                     let color = color.to_string_lossy();
-                    if let Ok(color) = color.as_ref().parse::<css_color_parser::Color>() {
-                        gradient.add_color_stop(
-                            stop,
-                            skia_safe::Color::from_argb(
-                                (color.a * 255.0) as u8,
-                                color.r,
-                                color.g,
-                                color.b,
-                            ),
-                        )
+                    if let Some(color) = parse_color(color.as_ref()) {
+                        gradient.add_color_stop(stop, color)
                     }
                 }
             }
@@ -41,3 +38,154 @@ pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasGradient_nativeAddC
         }
     }
 }
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasGradient_nativeSetCornerColor(
+    env: JNIEnv,
+    _: JClass,
+    style: c_longlong,
+    corner: jint,
+    color: JString,
+) {
+    if style == 0 || corner < 0 {
+        return;
+    }
+    unsafe {
+        let style: *mut PaintStyle = style as _;
+        let style = &mut *style;
+        match style {
+            PaintStyle::Gradient(gradient) => {
+                if let Ok(color) = env.get_string(color) {
+                    let color = color.to_string_lossy();
+                    if let Some(color) = parse_color(color.as_ref()) {
+                        gradient.set_corner_color(corner as usize, color)
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasGradient_nativeSetInterpolationColorSpace(
+    _: JNIEnv,
+    _: JClass,
+    style: c_longlong,
+    color_space: jint,
+) {
+    if style == 0 {
+        return;
+    }
+    unsafe {
+        let style: *mut PaintStyle = style as _;
+        let style = &mut *style;
+        match style {
+            PaintStyle::Gradient(gradient) => gradient.set_interpolation_color_space(InterpolationColorSpace::from(color_space)),
+            _ => {}
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasGradient_nativeGetInterpolationColorSpace(
+    _: JNIEnv,
+    _: JClass,
+    style: c_longlong,
+) -> jint {
+    if style == 0 {
+        return InterpolationColorSpace::default().into();
+    }
+    unsafe {
+        let style: *const PaintStyle = style as _;
+        let style = &*style;
+        match style {
+            PaintStyle::Gradient(gradient) => gradient.interpolation().color_space.into(),
+            _ => InterpolationColorSpace::default().into(),
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasGradient_nativeSetHueInterpolationMethod(
+    _: JNIEnv,
+    _: JClass,
+    style: c_longlong,
+    method: jint,
+) {
+    if style == 0 {
+        return;
+    }
+    unsafe {
+        let style: *mut PaintStyle = style as _;
+        let style = &mut *style;
+        match style {
+            PaintStyle::Gradient(gradient) => gradient.set_hue_interpolation_method(HueInterpolationMethod::from(method)),
+            _ => {}
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasGradient_nativeGetHueInterpolationMethod(
+    _: JNIEnv,
+    _: JClass,
+    style: c_longlong,
+) -> jint {
+    if style == 0 {
+        return HueInterpolationMethod::default().into();
+    }
+    unsafe {
+        let style: *const PaintStyle = style as _;
+        let style = &*style;
+        match style {
+            PaintStyle::Gradient(gradient) => gradient.interpolation().hue_method.into(),
+            _ => HueInterpolationMethod::default().into(),
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasGradient_nativeSetDither(
+    _: JNIEnv,
+    _: JClass,
+    style: c_longlong,
+    dither: jboolean,
+) {
+    if style == 0 {
+        return;
+    }
+    unsafe {
+        let style: *mut PaintStyle = style as _;
+        let style = &mut *style;
+        match style {
+            PaintStyle::Gradient(gradient) => gradient.set_dither(dither == JNI_TRUE),
+            _ => {}
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvasGradient_nativeGetDither(
+    _: JNIEnv,
+    _: JClass,
+    style: c_longlong,
+) -> jboolean {
+    if style == 0 {
+        return JNI_FALSE;
+    }
+    unsafe {
+        let style: *const PaintStyle = style as _;
+        let style = &*style;
+        match style {
+            PaintStyle::Gradient(gradient) => {
+                if gradient.interpolation().dither {
+                    JNI_TRUE
+                } else {
+                    JNI_FALSE
+                }
+            }
+            _ => JNI_FALSE,
+        }
+    }
+}
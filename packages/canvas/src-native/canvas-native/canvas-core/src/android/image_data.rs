@@ -2,7 +2,8 @@ use jni::JNIEnv;
 use jni::objects::{JClass};
 use jni::sys::{jint, jlong, jobject};
 
-use crate::common::context::pixel_manipulation::image_data::ImageData;
+use crate::common::context::color_space::PredefinedColorSpace;
+use crate::common::context::pixel_manipulation::image_data::{ImageData, ImageDataStorageFormat};
 
 #[no_mangle]
 pub extern "system" fn Java_org_nativescript_canvas_TNSImageData_nativeInit(
@@ -14,6 +15,38 @@ pub extern "system" fn Java_org_nativescript_canvas_TNSImageData_nativeInit(
     Box::into_raw(Box::new(ImageData::new(width, height))) as jlong
 }
 
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSImageData_nativeInitWithColorSpace(
+    _: JNIEnv,
+    _: JClass,
+    width: jint,
+    height: jint,
+    color_space: jint,
+) -> jlong {
+    Box::into_raw(Box::new(ImageData::new_with_color_space(
+        width,
+        height,
+        PredefinedColorSpace::from(color_space),
+    ))) as jlong
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSImageData_nativeInitWithSettings(
+    _: JNIEnv,
+    _: JClass,
+    width: jint,
+    height: jint,
+    color_space: jint,
+    storage_format: jint,
+) -> jlong {
+    Box::into_raw(Box::new(ImageData::new_with_settings(
+        width,
+        height,
+        PredefinedColorSpace::from(color_space),
+        ImageDataStorageFormat::from(storage_format),
+    ))) as jlong
+}
+
 #[no_mangle]
 pub extern "system" fn Java_org_nativescript_canvas_TNSImageData_nativeWidth(
     _: JNIEnv,
@@ -46,6 +79,38 @@ pub extern "system" fn Java_org_nativescript_canvas_TNSImageData_nativeHeight(
     }
 }
 
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSImageData_nativeColorSpace(
+    _: JNIEnv,
+    _: JClass,
+    image_data: jlong,
+) -> jint {
+    if image_data == 0 {
+        return PredefinedColorSpace::default().into();
+    }
+    unsafe {
+        let image_data: *mut ImageData = image_data as _;
+        let image_data = &mut *image_data;
+        image_data.color_space().into()
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSImageData_nativeStorageFormat(
+    _: JNIEnv,
+    _: JClass,
+    image_data: jlong,
+) -> jint {
+    if image_data == 0 {
+        return ImageDataStorageFormat::default().into();
+    }
+    unsafe {
+        let image_data: *mut ImageData = image_data as _;
+        let image_data = &mut *image_data;
+        image_data.storage_format().into()
+    }
+}
+
 #[no_mangle]
 pub extern "system" fn Java_org_nativescript_canvas_TNSImageData_nativeData(
     env: JNIEnv,
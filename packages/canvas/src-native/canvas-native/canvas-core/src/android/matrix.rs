@@ -1,8 +1,53 @@
 use jni::JNIEnv;
-use jni::objects::JClass;
-use jni::sys::{jfloat, jfloatArray, jlong};
+use jni::objects::{JClass, JObject, JString};
+use jni::sys::{jboolean, jdoubleArray, jfloat, jfloatArray, jlong, jstring, JNI_FALSE, JNI_TRUE};
 
-use crate::common::context::matrix::Matrix;
+use crate::common::context::Device;
+use crate::common::context::matrix::{DecomposedMatrix, Matrix};
+
+fn matrix_ref<'a>(matrix: jlong) -> Option<&'a Matrix> {
+    if matrix == 0 {
+        return None;
+    }
+    unsafe {
+        let matrix: *const Matrix = matrix as _;
+        Some(&*matrix)
+    }
+}
+
+fn matrix_mut<'a>(matrix: jlong) -> Option<&'a mut Matrix> {
+    if matrix == 0 {
+        return None;
+    }
+    unsafe {
+        let matrix: *mut Matrix = matrix as _;
+        Some(&mut *matrix)
+    }
+}
+
+fn to_handle(matrix: Matrix) -> jlong {
+    Box::into_raw(Box::new(matrix)) as jlong
+}
+
+/// Runs `op` on a copy of `matrix` and returns the copy, or 0 if `matrix` is 0.
+fn with_copy<F: FnOnce(&mut Matrix)>(matrix: jlong, op: F) -> jlong {
+    match matrix_ref(matrix) {
+        Some(matrix) => {
+            let mut matrix = matrix.clone();
+            op(&mut matrix);
+            to_handle(matrix)
+        }
+        None => 0,
+    }
+}
+
+fn to_jboolean(value: bool) -> jboolean {
+    if value {
+        JNI_TRUE
+    } else {
+        JNI_FALSE
+    }
+}
 
 #[no_mangle]
 pub extern "system" fn Java_org_nativescript_canvas_TNSDOMMatrix_nativeInit(
@@ -758,6 +803,484 @@ pub extern "system" fn Java_org_nativescript_canvas_TNSDOMMatrix_nativeSetM44(
     }
 }
 
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSDOMMatrix_nativeInitWithValues(
+    env: JNIEnv,
+    _: JClass,
+    data: jfloatArray,
+) -> jlong {
+    let mut values = [0f32; 16];
+    match env.get_float_array_region(data, 0, &mut values) {
+        Ok(_) => to_handle(Matrix::from_float32_array(&values)),
+        Err(_) => 0,
+    }
+}
+
+/// Parses a CSS transform list, lengths resolving against a viewport of `width` by `height`
+/// with `ppi` pixels per inch. Returns 0 if the value is invalid.
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSDOMMatrix_nativeInitWithCss(
+    env: JNIEnv,
+    _: JClass,
+    value: JString,
+    width: jfloat,
+    height: jfloat,
+    density: jfloat,
+    ppi: jfloat,
+) -> jlong {
+    let value = match env.get_string(value) {
+        Ok(value) => value,
+        Err(_) => return 0,
+    };
+    let value = value.to_string_lossy();
+    let device = Device::new_non_gpu(width, height, density, ppi);
+    match Matrix::from_css(value.as_ref(), device) {
+        Some(matrix) => to_handle(matrix),
+        None => 0,
+    }
+}
+
+/// Returns null if `matrix` is 0 or holds values that are not finite.
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSDOMMatrix_nativeToString(
+    env: JNIEnv,
+    _: JClass,
+    matrix: jlong,
+) -> jstring {
+    match matrix_ref(matrix).and_then(|matrix| matrix.to_string()) {
+        Some(value) => env.new_string(value).unwrap().into_raw(),
+        None => JObject::null().into_raw(),
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSDOMMatrix_nativeIs2D(
+    _: JNIEnv,
+    _: JClass,
+    matrix: jlong,
+) -> jboolean {
+    to_jboolean(
+        matrix_ref(matrix)
+            .map(|matrix| matrix.is_2d())
+            .unwrap_or(false),
+    )
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSDOMMatrix_nativeIsIdentity(
+    _: JNIEnv,
+    _: JClass,
+    matrix: jlong,
+) -> jboolean {
+    to_jboolean(
+        matrix_ref(matrix)
+            .map(|matrix| matrix.is_identity())
+            .unwrap_or(false),
+    )
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSDOMMatrix_nativeMultiply(
+    _: JNIEnv,
+    _: JClass,
+    matrix: jlong,
+    other: jlong,
+) -> jlong {
+    match (matrix_ref(matrix), matrix_ref(other)) {
+        (Some(matrix), Some(other)) => to_handle(matrix.multiply(other)),
+        _ => 0,
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSDOMMatrix_nativeMultiplySelf(
+    _: JNIEnv,
+    _: JClass,
+    matrix: jlong,
+    other: jlong,
+) {
+    // `other` may be the same handle as `matrix`, so copy it before borrowing `matrix` mutably
+    if let Some(other) = matrix_ref(other).cloned() {
+        if let Some(matrix) = matrix_mut(matrix) {
+            matrix.multiply_self(&other)
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSDOMMatrix_nativePreMultiplySelf(
+    _: JNIEnv,
+    _: JClass,
+    matrix: jlong,
+    other: jlong,
+) {
+    // `other` may be the same handle as `matrix`, so copy it before borrowing `matrix` mutably
+    if let Some(other) = matrix_ref(other).cloned() {
+        if let Some(matrix) = matrix_mut(matrix) {
+            matrix.pre_multiply_self(&other)
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSDOMMatrix_nativeInverse(
+    _: JNIEnv,
+    _: JClass,
+    matrix: jlong,
+) -> jlong {
+    match matrix_ref(matrix) {
+        Some(matrix) => to_handle(matrix.inverse()),
+        None => 0,
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSDOMMatrix_nativeInvertSelf(
+    _: JNIEnv,
+    _: JClass,
+    matrix: jlong,
+) {
+    if let Some(matrix) = matrix_mut(matrix) {
+        matrix.invert_self()
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSDOMMatrix_nativeTranslate(
+    _: JNIEnv,
+    _: JClass,
+    matrix: jlong,
+    tx: jfloat,
+    ty: jfloat,
+    tz: jfloat,
+) -> jlong {
+    with_copy(matrix, |matrix| matrix.translate_self(tx, ty, tz))
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSDOMMatrix_nativeTranslateSelf(
+    _: JNIEnv,
+    _: JClass,
+    matrix: jlong,
+    tx: jfloat,
+    ty: jfloat,
+    tz: jfloat,
+) {
+    if let Some(matrix) = matrix_mut(matrix) {
+        matrix.translate_self(tx, ty, tz)
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSDOMMatrix_nativeScale(
+    _: JNIEnv,
+    _: JClass,
+    matrix: jlong,
+    sx: jfloat,
+    sy: jfloat,
+    sz: jfloat,
+    origin_x: jfloat,
+    origin_y: jfloat,
+    origin_z: jfloat,
+) -> jlong {
+    with_copy(matrix, |matrix| {
+        matrix.scale_self(sx, sy, sz, origin_x, origin_y, origin_z)
+    })
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSDOMMatrix_nativeScaleSelf(
+    _: JNIEnv,
+    _: JClass,
+    matrix: jlong,
+    sx: jfloat,
+    sy: jfloat,
+    sz: jfloat,
+    origin_x: jfloat,
+    origin_y: jfloat,
+    origin_z: jfloat,
+) {
+    if let Some(matrix) = matrix_mut(matrix) {
+        matrix.scale_self(sx, sy, sz, origin_x, origin_y, origin_z)
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSDOMMatrix_nativeScale3d(
+    _: JNIEnv,
+    _: JClass,
+    matrix: jlong,
+    scale: jfloat,
+    origin_x: jfloat,
+    origin_y: jfloat,
+    origin_z: jfloat,
+) -> jlong {
+    with_copy(matrix, |matrix| {
+        matrix.scale3d_self(scale, origin_x, origin_y, origin_z)
+    })
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSDOMMatrix_nativeScale3dSelf(
+    _: JNIEnv,
+    _: JClass,
+    matrix: jlong,
+    scale: jfloat,
+    origin_x: jfloat,
+    origin_y: jfloat,
+    origin_z: jfloat,
+) {
+    if let Some(matrix) = matrix_mut(matrix) {
+        matrix.scale3d_self(scale, origin_x, origin_y, origin_z)
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSDOMMatrix_nativeRotate(
+    _: JNIEnv,
+    _: JClass,
+    matrix: jlong,
+    rot_x: jfloat,
+    rot_y: jfloat,
+    rot_z: jfloat,
+) -> jlong {
+    with_copy(matrix, |matrix| matrix.rotate_self(rot_x, rot_y, rot_z))
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSDOMMatrix_nativeRotateSelf(
+    _: JNIEnv,
+    _: JClass,
+    matrix: jlong,
+    rot_x: jfloat,
+    rot_y: jfloat,
+    rot_z: jfloat,
+) {
+    if let Some(matrix) = matrix_mut(matrix) {
+        matrix.rotate_self(rot_x, rot_y, rot_z)
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSDOMMatrix_nativeRotateFromVector(
+    _: JNIEnv,
+    _: JClass,
+    matrix: jlong,
+    x: jfloat,
+    y: jfloat,
+) -> jlong {
+    with_copy(matrix, |matrix| matrix.rotate_from_vector_self(x, y))
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSDOMMatrix_nativeRotateFromVectorSelf(
+    _: JNIEnv,
+    _: JClass,
+    matrix: jlong,
+    x: jfloat,
+    y: jfloat,
+) {
+    if let Some(matrix) = matrix_mut(matrix) {
+        matrix.rotate_from_vector_self(x, y)
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSDOMMatrix_nativeRotateAxisAngle(
+    _: JNIEnv,
+    _: JClass,
+    matrix: jlong,
+    x: jfloat,
+    y: jfloat,
+    z: jfloat,
+    angle: jfloat,
+) -> jlong {
+    with_copy(matrix, |matrix| {
+        matrix.rotate_axis_angle_self(x, y, z, angle)
+    })
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSDOMMatrix_nativeRotateAxisAngleSelf(
+    _: JNIEnv,
+    _: JClass,
+    matrix: jlong,
+    x: jfloat,
+    y: jfloat,
+    z: jfloat,
+    angle: jfloat,
+) {
+    if let Some(matrix) = matrix_mut(matrix) {
+        matrix.rotate_axis_angle_self(x, y, z, angle)
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSDOMMatrix_nativeSkewX(
+    _: JNIEnv,
+    _: JClass,
+    matrix: jlong,
+    angle: jfloat,
+) -> jlong {
+    with_copy(matrix, |matrix| matrix.skew_x_self(angle))
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSDOMMatrix_nativeSkewXSelf(
+    _: JNIEnv,
+    _: JClass,
+    matrix: jlong,
+    angle: jfloat,
+) {
+    if let Some(matrix) = matrix_mut(matrix) {
+        matrix.skew_x_self(angle)
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSDOMMatrix_nativeSkewY(
+    _: JNIEnv,
+    _: JClass,
+    matrix: jlong,
+    angle: jfloat,
+) -> jlong {
+    with_copy(matrix, |matrix| matrix.skew_y_self(angle))
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSDOMMatrix_nativeSkewYSelf(
+    _: JNIEnv,
+    _: JClass,
+    matrix: jlong,
+    angle: jfloat,
+) {
+    if let Some(matrix) = matrix_mut(matrix) {
+        matrix.skew_y_self(angle)
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSDOMMatrix_nativeFlipX(
+    _: JNIEnv,
+    _: JClass,
+    matrix: jlong,
+) -> jlong {
+    match matrix_ref(matrix) {
+        Some(matrix) => to_handle(matrix.flip_x()),
+        None => 0,
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSDOMMatrix_nativeFlipY(
+    _: JNIEnv,
+    _: JClass,
+    matrix: jlong,
+) -> jlong {
+    match matrix_ref(matrix) {
+        Some(matrix) => to_handle(matrix.flip_y()),
+        None => 0,
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSDOMMatrix_nativeTransformPoint(
+    env: JNIEnv,
+    _: JClass,
+    matrix: jlong,
+    x: jfloat,
+    y: jfloat,
+    z: jfloat,
+    w: jfloat,
+) -> jfloatArray {
+    let values = match matrix_ref(matrix) {
+        Some(matrix) => matrix.transform_point(x, y, z, w).to_vec(),
+        None => Vec::new(),
+    };
+    let array = env.new_float_array(values.len() as i32).unwrap();
+    env.set_float_array_region(array, 0, values.as_slice())
+        .unwrap_or(());
+    array
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSDOMMatrix_nativeToFloat32Array(
+    env: JNIEnv,
+    _: JClass,
+    matrix: jlong,
+) -> jfloatArray {
+    let values = match matrix_ref(matrix) {
+        Some(matrix) => matrix.to_float32_array().to_vec(),
+        None => Vec::new(),
+    };
+    let array = env.new_float_array(values.len() as i32).unwrap();
+    env.set_float_array_region(array, 0, values.as_slice())
+        .unwrap_or(());
+    array
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSDOMMatrix_nativeToFloat64Array(
+    env: JNIEnv,
+    _: JClass,
+    matrix: jlong,
+) -> jdoubleArray {
+    let values = match matrix_ref(matrix) {
+        Some(matrix) => matrix.to_float64_array().to_vec(),
+        None => Vec::new(),
+    };
+    let array = env.new_double_array(values.len() as i32).unwrap();
+    env.set_double_array_region(array, 0, values.as_slice())
+        .unwrap_or(());
+    array
+}
+
+/// Returns translate, scale, skew, perspective and quaternion in order, 17 values, or an empty
+/// array if the matrix cannot be decomposed.
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSDOMMatrix_nativeDecompose(
+    env: JNIEnv,
+    _: JClass,
+    matrix: jlong,
+) -> jfloatArray {
+    let values = match matrix_ref(matrix).and_then(|matrix| matrix.decompose()) {
+        Some(decomposed) => decomposed.to_array().to_vec(),
+        None => Vec::new(),
+    };
+    let array = env.new_float_array(values.len() as i32).unwrap();
+    env.set_float_array_region(array, 0, values.as_slice())
+        .unwrap_or(());
+    array
+}
+
+/// `data` must hold the 17 values returned by `nativeDecompose`.
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSDOMMatrix_nativeRecompose(
+    env: JNIEnv,
+    _: JClass,
+    data: jfloatArray,
+) -> jlong {
+    let mut values = [0f32; DecomposedMatrix::LENGTH];
+    match env.get_float_array_region(data, 0, &mut values) {
+        Ok(_) => to_handle(DecomposedMatrix::from_array(&values).recompose()),
+        Err(_) => 0,
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSDOMMatrix_nativeInterpolate(
+    _: JNIEnv,
+    _: JClass,
+    from: jlong,
+    to: jlong,
+    t: jfloat,
+) -> jlong {
+    match (matrix_ref(from), matrix_ref(to)) {
+        (Some(from), Some(to)) => to_handle(from.interpolate(to, t)),
+        _ => 0,
+    }
+}
+
 #[no_mangle]
 pub extern "system" fn Java_org_nativescript_canvas_TNSDOMMatrix_nativeDestroy(
     _: JNIEnv,
@@ -18,10 +18,12 @@ use once_cell::sync::OnceCell;
 
 use crate::common::{image_to_data_url, to_data_url};
 use crate::common::context::{Context, Device, State};
+use crate::common::context::color_space::PredefinedColorSpace;
 use crate::common::context::filter_quality::FilterQuality::Low;
 use crate::common::context::paths::path::Path;
 use crate::common::context::text_styles::text_direction::TextDirection;
 
+pub mod canvas_filter;
 pub mod context;
 pub mod gl;
 pub mod gradient;
@@ -32,6 +34,8 @@ pub mod matrix;
 pub mod paint;
 pub mod path;
 pub mod pattern;
+pub mod picture;
+pub mod runtime_effect;
 pub mod svg;
 pub mod text_decoder;
 pub mod text_encoder;
@@ -389,10 +393,7 @@ pub extern "system" fn JNI_OnLoad(vm: JavaVM, _reserved: *const c_void) -> jint
     jni::sys::JNI_VERSION_1_6
 }
 
-#[no_mangle]
-pub extern "system" fn Java_org_nativescript_canvas_TNSCanvas_nativeInitContext(
-    _: JNIEnv,
-    _: JClass,
+pub(crate) fn init_context(
     width: jfloat,
     height: jfloat,
     density: jfloat,
@@ -402,6 +403,7 @@ pub extern "system" fn Java_org_nativescript_canvas_TNSCanvas_nativeInitContext(
     font_color: jint,
     ppi: jfloat,
     direction: jint,
+    color_space: PredefinedColorSpace,
 ) -> jlong {
     let device = Device {
         width,
@@ -411,7 +413,8 @@ pub extern "system" fn Java_org_nativescript_canvas_TNSCanvas_nativeInitContext(
         samples: samples as usize,
         alpha: alpha == JNI_TRUE,
         ppi,
-        matrix: skia_safe::Matrix::scale((density, density))
+        matrix: skia_safe::Matrix::scale((density, density)),
+        color_space,
     };
     let interface = Interface::new_native();
     let mut ctx = skia_safe::gpu::DirectContext::new_gl(interface, None).unwrap();
@@ -441,21 +444,80 @@ pub extern "system" fn Java_org_nativescript_canvas_TNSCanvas_nativeInitContext(
         &target,
         skia_safe::gpu::SurfaceOrigin::BottomLeft,
         color_type,
-        None,
+        Some(color_space.to_skia()),
         Some(&surface_props),
     );
 
     Box::into_raw(Box::new(Context {
-        surface: surface_holder.unwrap(),
+        surface: surface_holder.unwrap().into(),
         path: Path::default(),
         state: State::from_device(device, TextDirection::from(direction)),
         state_stack: vec![],
         font_color: Color::new(font_color as u32),
         device,
-        enable_scaling: false
+        enable_scaling: false,
+        blenders: HashMap::new(),
+        svg_filters: HashMap::new(),
     })) as jlong
 }
 
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvas_nativeInitContext(
+    _: JNIEnv,
+    _: JClass,
+    width: jfloat,
+    height: jfloat,
+    density: jfloat,
+    buffer_id: jint,
+    samples: jint,
+    alpha: jboolean,
+    font_color: jint,
+    ppi: jfloat,
+    direction: jint,
+) -> jlong {
+    init_context(
+        width,
+        height,
+        density,
+        buffer_id,
+        samples,
+        alpha,
+        font_color,
+        ppi,
+        direction,
+        PredefinedColorSpace::SRGB,
+    )
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvas_nativeInitContextWithColorSpace(
+    _: JNIEnv,
+    _: JClass,
+    width: jfloat,
+    height: jfloat,
+    density: jfloat,
+    buffer_id: jint,
+    samples: jint,
+    alpha: jboolean,
+    font_color: jint,
+    ppi: jfloat,
+    direction: jint,
+    color_space: jint,
+) -> jlong {
+    init_context(
+        width,
+        height,
+        density,
+        buffer_id,
+        samples,
+        alpha,
+        font_color,
+        ppi,
+        direction,
+        PredefinedColorSpace::from(color_space),
+    )
+}
+
 pub(crate) fn init_with_custom_surface(
     width: jfloat,
     height: jfloat,
@@ -464,6 +526,7 @@ pub(crate) fn init_with_custom_surface(
     font_color: jint,
     ppi: jfloat,
     direction: jint,
+    color_space: PredefinedColorSpace,
 ) -> jlong {
     let device = Device {
         width,
@@ -473,23 +536,26 @@ pub(crate) fn init_with_custom_surface(
         samples: 0,
         alpha: alpha == JNI_TRUE,
         ppi,
-        matrix: skia_safe::Matrix::scale((density, density))
+        matrix: skia_safe::Matrix::scale((density, density)),
+        color_space,
     };
     let info = ImageInfo::new(
         ISize::new(width as i32, height as i32),
         ColorType::RGBA8888,
         AlphaType::Premul,
-        None,
+        Some(color_space.to_skia()),
     );
 
     Box::into_raw(Box::new(Context {
-        surface: Surface::new_raster(&info, None, None).unwrap(),
+        surface: Surface::new_raster(&info, None, None).unwrap().into(),
         path: Path::default(),
         state: State::from_device(device, TextDirection::from(direction)),
         state_stack: vec![],
         font_color: Color::new(font_color as u32),
         device,
-        enable_scaling: false
+        enable_scaling: false,
+        blenders: HashMap::new(),
+        svg_filters: HashMap::new(),
     })) as jlong
 }
 
@@ -505,7 +571,41 @@ pub extern "system" fn Java_org_nativescript_canvas_TNSCanvas_nativeInitContextW
     ppi: jfloat,
     direction: jint,
 ) -> jlong {
-    init_with_custom_surface(width, height, density, alpha, font_color, ppi, direction)
+    init_with_custom_surface(
+        width,
+        height,
+        density,
+        alpha,
+        font_color,
+        ppi,
+        direction,
+        PredefinedColorSpace::SRGB,
+    )
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSCanvas_nativeInitContextWithCustomSurfaceAndColorSpace(
+    _: JNIEnv,
+    _: JClass,
+    width: jfloat,
+    height: jfloat,
+    density: jfloat,
+    alpha: jboolean,
+    font_color: jint,
+    ppi: jfloat,
+    direction: jint,
+    color_space: jint,
+) -> jlong {
+    init_with_custom_surface(
+        width,
+        height,
+        density,
+        alpha,
+        font_color,
+        ppi,
+        direction,
+        PredefinedColorSpace::from(color_space),
+    )
 }
 
 #[no_mangle]
@@ -574,7 +674,8 @@ pub extern "system" fn Java_org_nativescript_canvas_TNSCanvas_nativeResizeSurfac
             samples: samples as usize,
             alpha: alpha == JNI_TRUE,
             ppi,
-            matrix: skia_safe::Matrix::scale((density, density))
+            matrix: skia_safe::Matrix::scale((density, density)),
+            color_space: context.device.color_space,
         };
         let mut frame_buffer = skia_safe::gpu::gl::FramebufferInfo::from_fboid(buffer_id as u32);
 
@@ -604,10 +705,10 @@ pub extern "system" fn Java_org_nativescript_canvas_TNSCanvas_nativeResizeSurfac
             &target,
             skia_safe::gpu::SurfaceOrigin::BottomLeft,
             color_type,
-            None,
+            Some(device.color_space.to_skia()),
             Some(&surface_props),
         ) {
-            context.surface = surface;
+            context.surface = surface.into();
             context.device = device;
             context.path = Path::default();
             context.reset_state();
@@ -640,18 +741,19 @@ pub extern "system" fn Java_org_nativescript_canvas_TNSCanvas_nativeResizeCustom
             samples: 0,
             alpha: alpha == JNI_TRUE,
             ppi,
-            matrix: skia_safe::Matrix::scale((density, density))
+            matrix: skia_safe::Matrix::scale((density, density)),
+            color_space: context.device.color_space,
         };
 
         let info = ImageInfo::new(
             ISize::new(width as i32, height as i32),
             ColorType::RGBA8888,
             AlphaType::Premul,
-            None,
+            Some(device.color_space.to_skia()),
         );
 
         if let Some(surface) = Surface::new_raster(&info, None, None) {
-            context.surface = surface;
+            context.surface = surface.into();
             context.device = device;
             context.path = Path::default();
             context.reset_state();
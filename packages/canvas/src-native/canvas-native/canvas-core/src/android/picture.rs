@@ -0,0 +1,19 @@
+use jni::JNIEnv;
+use jni::objects::JClass;
+use jni::sys::jlong;
+use skia_safe::Picture;
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSPicture_nativeDestroy(
+    _: JNIEnv,
+    _: JClass,
+    picture: jlong,
+) {
+    if picture == 0 {
+        return;
+    }
+    unsafe {
+        let picture: *mut Picture = picture as _;
+        let _ = Box::from_raw(picture);
+    }
+}
@@ -0,0 +1,137 @@
+use jni::JNIEnv;
+use jni::objects::{JClass, JString};
+use jni::sys::{jboolean, jfloatArray, jintArray, jlong, JNI_FALSE, JNI_TRUE};
+
+use crate::common::context::fill_and_stroke_styles::paint::PaintStyle;
+use crate::common::context::image_asset::ImageAsset;
+use crate::common::context::matrix::Matrix;
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSRuntimeEffect_nativeSetUniform(
+    env: JNIEnv,
+    _: JClass,
+    style: jlong,
+    name: JString,
+    data: jfloatArray,
+) -> jboolean {
+    if style == 0 {
+        return JNI_FALSE;
+    }
+    unsafe {
+        let style: *mut PaintStyle = style as _;
+        let style = &mut *style;
+        if let (PaintStyle::RuntimeEffect(effect), Ok(name), Ok(len)) =
+            (style, env.get_string(name), env.get_array_length(data))
+        {
+            let mut values = vec![0f32; len as usize];
+            if env.get_float_array_region(data, 0, &mut values).is_ok()
+                && effect.set_uniform(name.to_string_lossy().as_ref(), &values)
+            {
+                return JNI_TRUE;
+            }
+        }
+        JNI_FALSE
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSRuntimeEffect_nativeSetUniformInt(
+    env: JNIEnv,
+    _: JClass,
+    style: jlong,
+    name: JString,
+    data: jintArray,
+) -> jboolean {
+    if style == 0 {
+        return JNI_FALSE;
+    }
+    unsafe {
+        let style: *mut PaintStyle = style as _;
+        let style = &mut *style;
+        if let (PaintStyle::RuntimeEffect(effect), Ok(name), Ok(len)) =
+            (style, env.get_string(name), env.get_array_length(data))
+        {
+            let mut values = vec![0i32; len as usize];
+            if env.get_int_array_region(data, 0, &mut values).is_ok()
+                && effect.set_uniform_int(name.to_string_lossy().as_ref(), &values)
+            {
+                return JNI_TRUE;
+            }
+        }
+        JNI_FALSE
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSRuntimeEffect_nativeSetChild(
+    env: JNIEnv,
+    _: JClass,
+    style: jlong,
+    name: JString,
+    child: jlong,
+) -> jboolean {
+    if style == 0 || child == 0 {
+        return JNI_FALSE;
+    }
+    unsafe {
+        let child: *const PaintStyle = child as _;
+        let child = (&*child).clone();
+        let style: *mut PaintStyle = style as _;
+        let style = &mut *style;
+        if let (PaintStyle::RuntimeEffect(effect), Ok(name)) = (style, env.get_string(name)) {
+            if effect.set_child(name.to_string_lossy().as_ref(), child) {
+                return JNI_TRUE;
+            }
+        }
+        JNI_FALSE
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSRuntimeEffect_nativeSetChildAsset(
+    env: JNIEnv,
+    _: JClass,
+    style: jlong,
+    name: JString,
+    asset: jlong,
+) -> jboolean {
+    if style == 0 || asset == 0 {
+        return JNI_FALSE;
+    }
+    unsafe {
+        let asset: *const ImageAsset = asset as _;
+        let asset = &*asset;
+        let style: *mut PaintStyle = style as _;
+        let style = &mut *style;
+        if let (PaintStyle::RuntimeEffect(effect), Ok(name), Some(image)) =
+            (style, env.get_string(name), asset.skia_image())
+        {
+            if effect.set_child_image(name.to_string_lossy().as_ref(), image) {
+                return JNI_TRUE;
+            }
+        }
+        JNI_FALSE
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_canvas_TNSRuntimeEffect_nativeSetTransform(
+    _: JNIEnv,
+    _: JClass,
+    style: jlong,
+    matrix: jlong,
+) {
+    if style == 0 || matrix == 0 {
+        return;
+    }
+    unsafe {
+        let style: *mut PaintStyle = style as _;
+        let style = &mut *style;
+        let matrix: *mut Matrix = matrix as _;
+        let matrix = &mut *matrix;
+        match style {
+            PaintStyle::RuntimeEffect(effect) => effect.set_transform(&matrix),
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,158 @@
+use std::borrow::BorrowMut;
+use std::os::raw::c_float;
+
+use skia_safe::{ClipOp, Color, PathOp, Rect, StrokeRec};
+use skia_safe::paint::{Cap, Join};
+
+use crate::common::context::Context;
+use crate::common::context::drawing_paths::fill_rule::FillRule;
+use crate::common::context::fill_and_stroke_styles::paint::Paint;
+use crate::common::context::paths::path::Path;
+
+/// A CSS `box-shadow` layer. Unlike the canvas shadow the offsets and blur are in user space, so
+/// they follow the current transform.
+#[derive(Copy, Clone, Debug)]
+pub struct BoxShadow {
+    pub offset_x: c_float,
+    pub offset_y: c_float,
+    pub blur: c_float,
+    pub spread: c_float,
+    pub color: Color,
+    pub inset: bool,
+}
+
+impl BoxShadow {
+    /// Reads shadows from five values each, offset x, offset y, blur, spread and 1 for inset or
+    /// 0 otherwise, with one ARGB color per shadow.
+    pub fn list_from_slices(values: &[f32], colors: &[u32]) -> Option<Vec<BoxShadow>> {
+        if values.len() != colors.len() * 5 {
+            return None;
+        }
+        Some(
+            values
+                .chunks(5)
+                .zip(colors.iter())
+                .map(|(value, color)| BoxShadow {
+                    offset_x: value[0],
+                    offset_y: value[1],
+                    blur: value[2].max(0.0),
+                    spread: value[3],
+                    color: Color::new(*color),
+                    inset: value[4] != 0.0,
+                })
+                .collect(),
+        )
+    }
+}
+
+/// Grows `path` by `spread`, or shrinks it for a negative spread, keeping sharp corners sharp.
+fn spread_path(path: &skia_safe::Path, spread: f32) -> Option<skia_safe::Path> {
+    if spread == 0.0 {
+        return Some(path.clone());
+    }
+    let mut stroke = StrokeRec::new_hairline();
+    stroke.set_stroke_style(spread.abs() * 2.0, false);
+    stroke.set_stroke_params(Cap::Butt, Join::Miter, 4.0);
+    let mut outline = skia_safe::Path::default();
+    if !stroke.apply_to_path(&mut outline, path) {
+        return None;
+    }
+    let op = if spread > 0.0 {
+        PathOp::Union
+    } else {
+        PathOp::Difference
+    };
+    path.op(&outline, op)
+}
+
+impl Context {
+    /// Fills `path`, or the current path, with the fill style and a list of box shadows drawn
+    /// in CSS order, the first shadow on top. Outer shadows are drawn around the shape and
+    /// never under it, inset shadows are drawn inside it over the fill. The canvas shadow is
+    /// not drawn.
+    pub fn fill_with_box_shadows(
+        &mut self,
+        path: Option<&mut Path>,
+        fill_rule: FillRule,
+        shadows: &[BoxShadow],
+    ) {
+        let mut path = path.unwrap_or(self.path.borrow_mut()).clone();
+        path.set_fill_type(fill_rule);
+        let path = path.path().clone();
+        self.draw_with_composite_layer(|context| context.fill_path_with_box_shadows(&path, shadows));
+    }
+
+    pub fn fill_rect_with_box_shadows(&mut self, rect: &Rect, shadows: &[BoxShadow]) {
+        let path = skia_safe::Path::rect(rect, None);
+        self.draw_with_composite_layer(|context| context.fill_path_with_box_shadows(&path, shadows));
+    }
+
+    /// `radii` holds the x and y radius of each corner, clockwise from the top left.
+    pub fn fill_round_rect_with_box_shadows(
+        &mut self,
+        x: c_float,
+        y: c_float,
+        width: c_float,
+        height: c_float,
+        radii: [c_float; 8],
+        shadows: &[BoxShadow],
+    ) {
+        let mut path = Path::new();
+        path.round_rect(x, y, width, height, radii);
+        let path = path.path().clone();
+        self.draw_with_composite_layer(|context| context.fill_path_with_box_shadows(&path, shadows));
+    }
+
+    fn fill_path_with_box_shadows(&mut self, path: &skia_safe::Path, shadows: &[BoxShadow]) {
+        self.set_scale_for_device();
+        let fill_paint = self.state.paint.fill_paint().clone();
+        // box shadows are cast by the shape, not by the alpha of the fill style
+        let mut shape_paint = fill_paint.clone();
+        shape_paint.set_shader(None);
+        shape_paint.set_color(Color::BLACK);
+        shape_paint.set_alpha_f(self.state.global_alpha);
+
+        let canvas = self.surface.canvas();
+        for shadow in shadows.iter().rev().filter(|shadow| !shadow.inset) {
+            let spread = match spread_path(path, shadow.spread) {
+                Some(spread) => spread,
+                None => continue,
+            };
+            let sigma = shadow.blur / 2.0;
+            let paint = Paint::shadow_paint(&shape_paint, shadow.color.into(), (sigma, sigma));
+            canvas.save();
+            canvas.clip_path(path, ClipOp::Difference, true);
+            canvas.translate((shadow.offset_x, shadow.offset_y));
+            canvas.draw_path(&spread, &paint);
+            canvas.restore();
+        }
+
+        canvas.draw_path(path, &fill_paint);
+
+        for shadow in shadows.iter().rev().filter(|shadow| shadow.inset) {
+            let inner = match spread_path(path, -shadow.spread) {
+                Some(inner) => inner.with_offset((shadow.offset_x, shadow.offset_y)),
+                None => continue,
+            };
+            // the shadow is cast by everything outside the shape, so a frame wide enough to
+            // cover the blur stands in for the rest of the plane
+            let margin = shadow.blur
+                + shadow.spread.abs()
+                + shadow.offset_x.abs()
+                + shadow.offset_y.abs()
+                + 1.0;
+            let frame = skia_safe::Path::rect(path.bounds().with_outset((margin, margin)), None);
+            let ring = match frame.op(&inner, PathOp::Difference) {
+                Some(ring) => ring,
+                None => continue,
+            };
+            let sigma = shadow.blur / 2.0;
+            let paint = Paint::shadow_paint(&shape_paint, shadow.color.into(), (sigma, sigma));
+            canvas.save();
+            canvas.clip_path(path, ClipOp::Intersect, true);
+            canvas.draw_path(&ring, &paint);
+            canvas.restore();
+        }
+        self.clear_scale_for_device();
+    }
+}
@@ -0,0 +1,108 @@
+use lazy_static::lazy_static;
+use skia_safe::{ColorSpace, Data};
+
+/// The header SkColorSpace serializes itself with: version 1 and three reserved bytes.
+const SERIALIZED_HEADER: [u8; 4] = [1, 0, 0, 0];
+
+/// Bytes in a serialized SkColorSpace: the header, 7 transfer function floats and the 9 floats
+/// of the gamut matrix.
+const SERIALIZED_LEN: usize = SERIALIZED_HEADER.len() + (7 + 9) * std::mem::size_of::<f32>();
+
+/// The sRGB to XYZ D50 matrix, row major.
+const SRGB_TO_XYZ_D50: [f32; 9] = [
+    0.4360657, 0.3851471, 0.1430664, 0.2224884, 0.7168732, 0.0606079, 0.013916, 0.0970764,
+    0.7140961,
+];
+
+/// The Display P3 to XYZ D50 matrix, row major.
+const DISPLAY_P3_TO_XYZ_D50: [f32; 9] = [
+    0.515102, 0.291965, 0.157153, 0.241182, 0.692236, 0.0665819, -0.00104941, 0.0418818, 0.784378,
+];
+
+lazy_static! {
+    static ref DISPLAY_P3: ColorSpace = display_p3();
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum PredefinedColorSpace {
+    SRGB = 0,
+    DisplayP3 = 1,
+}
+
+impl Default for PredefinedColorSpace {
+    fn default() -> Self {
+        Self::SRGB
+    }
+}
+
+impl From<i32> for PredefinedColorSpace {
+    fn from(value: i32) -> Self {
+        match value {
+            1 => Self::DisplayP3,
+            _ => Self::SRGB,
+        }
+    }
+}
+
+impl Into<i32> for PredefinedColorSpace {
+    fn into(self) -> i32 {
+        match self {
+            PredefinedColorSpace::SRGB => 0,
+            PredefinedColorSpace::DisplayP3 => 1,
+        }
+    }
+}
+
+impl PredefinedColorSpace {
+    pub fn from_str(value: &str) -> Option<Self> {
+        match value {
+            "srgb" => Some(Self::SRGB),
+            "display-p3" => Some(Self::DisplayP3),
+            _ => None,
+        }
+    }
+
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            PredefinedColorSpace::SRGB => "srgb",
+            PredefinedColorSpace::DisplayP3 => "display-p3",
+        }
+    }
+
+    pub fn to_skia(&self) -> ColorSpace {
+        match self {
+            PredefinedColorSpace::SRGB => ColorSpace::new_srgb(),
+            PredefinedColorSpace::DisplayP3 => DISPLAY_P3.clone(),
+        }
+    }
+}
+
+/// skia-safe only constructs the sRGB spaces, so the P3 space is sRGB as skia serializes it with
+/// the gamut swapped out. The serialization is private to skia, so it is only patched when it
+/// has the expected layout and holds the sRGB gamut, otherwise sRGB is used.
+fn display_p3() -> ColorSpace {
+    let srgb = ColorSpace::new_srgb();
+    let mut bytes = srgb.serialize().as_bytes().to_vec();
+    if bytes.len() != SERIALIZED_LEN || bytes[..SERIALIZED_HEADER.len()] != SERIALIZED_HEADER {
+        return srgb;
+    }
+    let gamut_start = bytes.len() - DISPLAY_P3_TO_XYZ_D50.len() * std::mem::size_of::<f32>();
+    let is_srgb_gamut = bytes[gamut_start..]
+        .chunks_exact(std::mem::size_of::<f32>())
+        .zip(SRGB_TO_XYZ_D50.iter())
+        .all(|(chunk, expected)| {
+            let value = f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+            (value - expected).abs() < 1e-4
+        });
+    if !is_srgb_gamut {
+        return srgb;
+    }
+    for (chunk, value) in bytes[gamut_start..]
+        .chunks_exact_mut(std::mem::size_of::<f32>())
+        .zip(DISPLAY_P3_TO_XYZ_D50.iter())
+    {
+        chunk.copy_from_slice(&value.to_le_bytes());
+    }
+    ColorSpace::deserialize(Data::new_copy(&bytes))
+}
@@ -105,6 +105,38 @@ impl From<i32> for CompositeOperationType {
 }
 
 impl CompositeOperationType {
+    pub fn from_str(value: &str) -> Option<Self> {
+        match value {
+            "source-over" => Some(CompositeOperationType::SourceOver),
+            "source-in" => Some(CompositeOperationType::SourceIn),
+            "source-out" => Some(CompositeOperationType::SourceOut),
+            "source-atop" => Some(CompositeOperationType::SourceAtop),
+            "destination-over" => Some(CompositeOperationType::DestinationOver),
+            "destination-in" => Some(CompositeOperationType::DestinationIn),
+            "destination-out" => Some(CompositeOperationType::DestinationOut),
+            "destination-atop" => Some(CompositeOperationType::DestinationAtop),
+            "lighter" => Some(CompositeOperationType::Lighter),
+            "copy" => Some(CompositeOperationType::Copy),
+            "xor" => Some(CompositeOperationType::Xor),
+            "multiply" => Some(CompositeOperationType::Multiply),
+            "screen" => Some(CompositeOperationType::Screen),
+            "overlay" => Some(CompositeOperationType::Overlay),
+            "darken" => Some(CompositeOperationType::Darken),
+            "lighten" => Some(CompositeOperationType::Lighten),
+            "color-dodge" => Some(CompositeOperationType::ColorDodge),
+            "color-burn" => Some(CompositeOperationType::ColorBurn),
+            "hard-light" => Some(CompositeOperationType::HardLight),
+            "soft-light" => Some(CompositeOperationType::SoftLight),
+            "difference" => Some(CompositeOperationType::Difference),
+            "exclusion" => Some(CompositeOperationType::Exclusion),
+            "hue" => Some(CompositeOperationType::Hue),
+            "saturation" => Some(CompositeOperationType::Saturation),
+            "color" => Some(CompositeOperationType::Color),
+            "luminosity" => Some(CompositeOperationType::Luminosity),
+            _ => None,
+        }
+    }
+
     pub fn to_str(self) -> &'static str {
         match self {
             CompositeOperationType::SourceOver => "source-over",
@@ -135,6 +167,18 @@ impl CompositeOperationType {
             CompositeOperationType::Luminosity => "luminosity",
         }
     }
+    /// Operations that also clear the destination outside of the drawn shape.
+    pub fn affects_whole_canvas(&self) -> bool {
+        match self {
+            CompositeOperationType::SourceIn
+            | CompositeOperationType::SourceOut
+            | CompositeOperationType::DestinationIn
+            | CompositeOperationType::DestinationAtop
+            | CompositeOperationType::Copy => true,
+            _ => false,
+        }
+    }
+
     pub fn get_blend_mode(&self) -> BlendMode {
         match self {
             CompositeOperationType::SourceIn => BlendMode::SrcIn,
@@ -0,0 +1,83 @@
+use skia_safe::{Blender, Data, RuntimeEffect};
+
+// Blenders receive premultiplied colors. Each one un-premultiplies, applies a separable blend
+// function `blend(source, backdrop)` and composites the result with source-over, following
+// the W3C compositing model used by the built in blend modes.
+const BLENDER_MAIN: &str = r#"
+vec4 main(vec4 src, vec4 dst) {
+    vec3 s = src.a > 0.0 ? src.rgb / src.a : vec3(0.0);
+    vec3 d = dst.a > 0.0 ? dst.rgb / dst.a : vec3(0.0);
+    vec3 b = clamp(vec3(blend(s.r, d.r), blend(s.g, d.g), blend(s.b, d.b)), 0.0, 1.0);
+    vec3 c = src.a * (1.0 - dst.a) * s + src.a * dst.a * b + (1.0 - src.a) * dst.a * d;
+    return vec4(c, src.a + dst.a * (1.0 - src.a));
+}
+"#;
+
+const COLOR_DODGE_BURN: &str = r#"
+float color_dodge(float s, float d) {
+    if (d == 0.0) { return 0.0; }
+    if (s >= 1.0) { return 1.0; }
+    return min(1.0, d / (1.0 - s));
+}
+
+float color_burn(float s, float d) {
+    if (d >= 1.0) { return 1.0; }
+    if (s <= 0.0) { return 0.0; }
+    return 1.0 - min(1.0, (1.0 - d) / s);
+}
+"#;
+
+const LINEAR_LIGHT: &str = r#"
+float blend(float s, float d) {
+    return d + 2.0 * s - 1.0;
+}
+"#;
+
+const PIN_LIGHT: &str = r#"
+float blend(float s, float d) {
+    return s < 0.5 ? min(d, 2.0 * s) : max(d, 2.0 * s - 1.0);
+}
+"#;
+
+const VIVID_LIGHT: &str = r#"
+float blend(float s, float d) {
+    return s <= 0.5 ? color_burn(2.0 * s, d) : color_dodge(2.0 * s - 1.0, d);
+}
+"#;
+
+const HARD_MIX: &str = r#"
+float blend(float s, float d) {
+    return s + d >= 1.0 ? 1.0 : 0.0;
+}
+"#;
+
+/// Names of the blend modes that ship with the crate but are not part of
+/// `CompositeOperationType`.
+pub const BUILTIN_BLENDERS: [&str; 4] = ["linear-light", "pin-light", "vivid-light", "hard-mix"];
+
+fn builtin_sksl(name: &str) -> Option<String> {
+    let blend = match name {
+        "linear-light" => LINEAR_LIGHT,
+        "pin-light" => PIN_LIGHT,
+        "vivid-light" => VIVID_LIGHT,
+        "hard-mix" => HARD_MIX,
+        _ => return None,
+    };
+    Some(format!("{}{}{}", COLOR_DODGE_BURN, blend, BLENDER_MAIN))
+}
+
+/// Compiles a blender from SkSL declaring `vec4 main(vec4 src, vec4 dst)`. The error holds the
+/// SkSL compiler output.
+pub fn make_blender(sksl: &str) -> Result<Blender, String> {
+    let effect = RuntimeEffect::make_for_blender(sksl, None)?;
+    if effect.uniform_size() > 0 || !effect.children().is_empty() {
+        return Err("custom blenders cannot declare uniforms or children".into());
+    }
+    effect
+        .make_blender(Data::new_empty(), None)
+        .ok_or_else(|| "failed to create blender".into())
+}
+
+pub(crate) fn builtin_blender(name: &str) -> Option<Blender> {
+    builtin_sksl(name).and_then(|sksl| make_blender(&sksl).ok())
+}
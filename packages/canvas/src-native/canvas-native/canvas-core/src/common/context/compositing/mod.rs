@@ -1,9 +1,13 @@
 use std::os::raw::c_float;
 
+use skia_safe::{BlendMode, Blender, canvas::SaveLayerRec, Paint};
+
 use crate::common::context::compositing::composite_operation_type::CompositeOperationType;
+use crate::common::context::compositing::custom_blender::{builtin_blender, make_blender};
 use crate::common::context::Context;
 
 pub mod composite_operation_type;
+pub mod custom_blender;
 
 impl Context {
     pub fn set_global_alpha(&mut self, alpha: c_float) {
@@ -19,23 +23,88 @@ impl Context {
         self.state.global_alpha
     }
 
-    pub fn set_global_composite_operation(&mut self, operation: CompositeOperationType) {
-        self.state.global_composite_operation = operation;
+    fn set_blender(&mut self, blender: Blender) {
         self.state
             .paint
             .fill_paint_mut()
-            .set_blender(skia_safe::Blender::mode(operation.get_blend_mode()));
+            .set_blender(blender.clone());
         self.state
             .paint
             .stroke_paint_mut()
-            .set_blender(skia_safe::Blender::mode(operation.get_blend_mode()));
-        self.state
-            .paint
-            .image_paint_mut()
-            .set_blender(skia_safe::Blender::mode(operation.get_blend_mode()));
+            .set_blender(blender.clone());
+        self.state.paint.image_paint_mut().set_blender(blender);
+    }
+
+    /// Skia only blends within the coverage of a primitive, while operations such as
+    /// `source-in` or `copy` also clear the canvas outside of it. For those `draw` renders into
+    /// a layer with source over, and the whole layer is then composited with the operation.
+    pub(crate) fn draw_with_composite_layer<F: FnOnce(&mut Context)>(&mut self, draw: F) {
+        let operation = self.state.global_composite_operation;
+        if self.state.custom_composite_operation.is_some() || !operation.affects_whole_canvas() {
+            draw(self);
+            return;
+        }
+        let mut layer_paint = Paint::default();
+        layer_paint.set_blend_mode(operation.get_blend_mode());
+        self.surface
+            .canvas()
+            .save_layer(&SaveLayerRec::default().paint(&layer_paint));
+        self.set_blender(Blender::mode(BlendMode::SrcOver));
+        draw(self);
+        self.set_blender(Blender::mode(operation.get_blend_mode()));
+        self.surface.canvas().restore();
+    }
+
+    pub fn set_global_composite_operation(&mut self, operation: CompositeOperationType) {
+        self.state.global_composite_operation = operation;
+        self.state.custom_composite_operation = None;
+        self.set_blender(Blender::mode(operation.get_blend_mode()));
     }
 
     pub fn global_composite_operation(&self) -> CompositeOperationType {
         self.state.global_composite_operation
     }
+
+    /// Compiles `sksl` into a blender that can be selected by `name` with
+    /// `set_global_composite_operation_name`. Names of built in composite operations are
+    /// rejected, registering an existing custom name replaces it.
+    pub fn register_blender(&mut self, name: &str, sksl: &str) -> Result<(), String> {
+        if CompositeOperationType::from_str(name).is_some() {
+            return Err(format!("{} is a built in composite operation", name));
+        }
+        let blender = make_blender(sksl)?;
+        self.blenders.insert(name.to_string(), blender);
+        Ok(())
+    }
+
+    /// Selects a composite operation by name, either one of the canvas operations, a registered
+    /// blender or one of `BUILTIN_BLENDERS`. Unknown names are ignored and return false.
+    pub fn set_global_composite_operation_name(&mut self, name: &str) -> bool {
+        if let Some(operation) = CompositeOperationType::from_str(name) {
+            self.set_global_composite_operation(operation);
+            return true;
+        }
+        let blender = match self.blenders.get(name) {
+            Some(blender) => blender.clone(),
+            None => match builtin_blender(name) {
+                Some(blender) => {
+                    self.blenders.insert(name.to_string(), blender.clone());
+                    blender
+                }
+                None => return false,
+            },
+        };
+        self.state.custom_composite_operation = Some(name.to_string());
+        self.set_blender(blender);
+        true
+    }
+
+    /// While a custom blender is active `global_composite_operation` keeps returning the last
+    /// built in operation that was set.
+    pub fn global_composite_operation_name(&self) -> String {
+        match self.state.custom_composite_operation.as_ref() {
+            Some(name) => name.clone(),
+            None => self.state.global_composite_operation.to_str().to_string(),
+        }
+    }
 }
@@ -10,36 +10,59 @@ impl Context {
         src_rect: impl Into<Rect>,
         dst_rect: impl Into<Rect>,
     ) {
-        self.set_scale_for_device();
         let src_rect = src_rect.into();
         let dst_rect = dst_rect.into();
+        self.draw_with_composite_layer(|context| {
+            context.draw_image_src_dst(image, &src_rect, &dst_rect)
+        });
+    }
 
+    fn draw_image_src_dst(&mut self, image: &Image, src_rect: &Rect, dst_rect: &Rect) {
+        self.set_scale_for_device();
         self.state
             .paint
             .image_smoothing_quality_set(self.state.image_filter_quality());
         let paint = self.state.paint.image_paint().clone();
+        let sampling = self.state.image_smoothing_quality;
+        self.draw_shadow(&paint, |canvas, paint| {
+            canvas.draw_image_rect_with_sampling_options(
+                image,
+                Some((src_rect, SrcRectConstraint::Strict)),
+                dst_rect,
+                sampling,
+                paint,
+            );
+        });
         self.surface.canvas().draw_image_rect_with_sampling_options(
             image,
-            Some((&src_rect, SrcRectConstraint::Strict)),
+            Some((src_rect, SrcRectConstraint::Strict)),
             dst_rect,
-            self.state.image_smoothing_quality,
+            sampling,
             &paint,
         );
         self.clear_scale_for_device();
     }
 
     pub fn draw_image_with_rect(&mut self, image: &Image, dst_rect: impl Into<Rect>) {
-        self.set_scale_for_device();
         let dst_rect = dst_rect.into();
+        self.draw_with_composite_layer(|context| context.draw_image_dst(image, &dst_rect));
+    }
+
+    fn draw_image_dst(&mut self, image: &Image, dst_rect: &Rect) {
+        self.set_scale_for_device();
         self.state
             .paint
             .image_smoothing_quality_set(self.state.image_filter_quality());
         let paint = self.state.paint.image_paint().clone();
+        let sampling = self.state.image_smoothing_quality;
+        self.draw_shadow(&paint, |canvas, paint| {
+            canvas.draw_image_rect_with_sampling_options(image, None, dst_rect, sampling, paint);
+        });
         self.surface.canvas().draw_image_rect_with_sampling_options(
             image,
             None,
             dst_rect,
-            self.state.image_smoothing_quality,
+            sampling,
             &paint,
         );
 
@@ -47,14 +70,22 @@ impl Context {
     }
 
     pub(crate) fn draw_image_with_points(&mut self, image: &Image, x: f32, y: f32) {
+        self.draw_with_composite_layer(|context| context.draw_image_at(image, x, y));
+    }
+
+    fn draw_image_at(&mut self, image: &Image, x: f32, y: f32) {
         self.state
             .paint
             .image_smoothing_quality_set(self.state.image_filter_quality());
         let paint = self.state.paint.image_paint().clone();
+        let sampling = self.state.image_smoothing_quality;
+        self.draw_shadow(&paint, |canvas, paint| {
+            canvas.draw_image_with_sampling_options(image, (x, y), sampling, Some(paint));
+        });
         self.surface.canvas().draw_image_with_sampling_options(
             image,
             (x, y),
-            self.state.image_smoothing_quality,
+            sampling,
             Some(&paint),
         );
     }
@@ -0,0 +1,23 @@
+use skia_safe::{Color, Point};
+
+use crate::common::context::Context;
+use crate::common::context::fill_and_stroke_styles::gradient::Gradient;
+
+impl Context {
+    /// Fills a Coons patch with the given corner colors. The fill style, color included, is
+    /// ignored; the global alpha, composite operation and shadow still apply.
+    pub fn draw_patch(&mut self, cubics: &[Point; 12], colors: &[Color; 4]) {
+        self.draw_with_composite_layer(|context| context.draw_patch_with_paint(cubics, colors));
+    }
+
+    fn draw_patch_with_paint(&mut self, cubics: &[Point; 12], colors: &[Color; 4]) {
+        self.set_scale_for_device();
+        let mut paint = self.state.paint.fill_paint().clone();
+        paint.set_shader(None);
+        self.draw_shadow(&paint, |canvas, paint| {
+            Gradient::draw_mesh(canvas, cubics, colors, paint);
+        });
+        Gradient::draw_mesh(self.surface.canvas(), cubics, colors, &paint);
+        self.clear_scale_for_device();
+    }
+}
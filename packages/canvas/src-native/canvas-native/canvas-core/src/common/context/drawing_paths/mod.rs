@@ -1,6 +1,6 @@
 use std::borrow::BorrowMut;
 
-use skia_safe::{ClipOp, Matrix, Point};
+use skia_safe::{ClipOp, IRect, Matrix, Point, Rect};
 
 use crate::common::context::Context;
 use crate::common::context::drawing_paths::fill_rule::FillRule;
@@ -17,9 +17,9 @@ impl Context {
     ) {
         let paint;
         if is_fill {
-            paint = self.state.paint.fill_paint();
+            paint = self.state.paint.fill_paint().clone();
         } else {
-            paint = self.state.paint.stroke_paint();
+            paint = self.state.paint.stroke_paint().clone();
         }
 
         let mut path = path.unwrap_or(self.path.borrow_mut()).clone();
@@ -31,34 +31,23 @@ impl Context {
 
         if let Some(rule) = fill_rule {
             path.path.set_fill_type(rule.to_fill_type());
-            if let Some(paint) = self.state.paint.fill_shadow_paint(
-                self.state.shadow_offset,
-                self.state.shadow_color,
-                self.state.shadow_blur,
-            ) {
-                self.surface.canvas().draw_path(&path.path, &paint);
-            }
-
-            self.surface.canvas().draw_path(&path.path, &paint);
         } else {
             path.path.set_fill_type(FillRule::NonZero.to_fill_type());
-            if let Some(paint) = self.state.paint.stroke_shadow_paint(
-                self.state.shadow_offset,
-                self.state.shadow_color,
-                self.state.shadow_blur,
-            ) {
-                self.surface.canvas().draw_path(&path.path, &paint);
-            }
-            self.surface.canvas().draw_path(&path.path, &paint);
         }
+        self.draw_shadow(&paint, |canvas, paint| {
+            canvas.draw_path(&path.path, paint);
+        });
+        self.surface.canvas().draw_path(&path.path, &paint);
     }
 
     pub fn fill(&mut self, path: Option<&mut Path>, fill_rule: FillRule) {
-        self.fill_or_stroke(true, path, Some(fill_rule));
+        self.draw_with_composite_layer(|context| {
+            context.fill_or_stroke(true, path, Some(fill_rule))
+        });
     }
 
     pub fn stroke(&mut self, path: Option<&mut Path>) {
-        self.fill_or_stroke(false, path, None);
+        self.draw_with_composite_layer(|context| context.fill_or_stroke(false, path, None));
     }
 
     pub fn clip(&mut self, path: Option<&mut Path>, fill_rule: Option<FillRule>) {
@@ -71,9 +60,68 @@ impl Context {
         }
 
         path.set_fill_type(rule);
-        self.surface
-            .canvas()
-            .clip_path(path.path(), Some(ClipOp::Intersect), Some(true));
+
+        // Skia cannot remove a clip made without a save, so the first clip outside of any save
+        // gets a save of its own that reset_clip and reset can restore past
+        let canvas = self.surface.canvas();
+        if self.state_stack.is_empty() && canvas.save_count() == 1 {
+            canvas.save();
+        }
+        canvas.clip_path(path.path(), Some(ClipOp::Intersect), Some(true));
+        let clip_path = path.path().with_transform(&canvas.local_to_device_as_3x3());
+        self.state.clip_paths.push(clip_path);
+    }
+
+    /// Removes the clip made since the last save, or within the current layer. The clips of
+    /// the saved states come back on `restore`.
+    pub fn reset_clip(&mut self) {
+        let canvas = self.surface.canvas();
+        let matrix = canvas.local_to_device();
+        let hidden_saves = canvas
+            .save_count()
+            .saturating_sub(self.state_stack.len() + 1);
+        let layer = self.state_stack.iter().rposition(|state| state.is_layer);
+        let first = match layer {
+            // the saves above the layer are rebuilt, the clip the layer was made with stays
+            Some(layer) => {
+                canvas.restore_to_count(layer + 2 + hidden_saves);
+                layer + 1
+            }
+            None => {
+                canvas.restore_to_count(1);
+                if hidden_saves > 0 {
+                    canvas.save();
+                }
+                0
+            }
+        };
+        for state in &self.state_stack[first..] {
+            canvas.reset_matrix();
+            for clip_path in &state.clip_paths {
+                canvas.clip_path(clip_path, Some(ClipOp::Intersect), Some(true));
+            }
+            canvas.set_matrix(&state.transform);
+            canvas.save();
+        }
+        canvas.set_matrix(&matrix);
+        self.state.clip_paths.clear();
+    }
+
+    /// The bounds of the current clip in user space, or `None` if nothing can be drawn.
+    pub fn clip_bounds(&mut self) -> Option<Rect> {
+        self.set_scale_for_device();
+        let bounds = self.surface.canvas().local_clip_bounds();
+        self.clear_scale_for_device();
+        bounds
+    }
+
+    /// The bounds of the current clip in canvas pixels, or `None` if nothing can be drawn.
+    pub fn device_clip_bounds(&mut self) -> Option<IRect> {
+        self.surface.canvas().device_clip_bounds()
+    }
+
+    pub fn is_clip_empty(&mut self) -> bool {
+        self.surface.canvas().is_clip_empty()
     }
 
     pub fn is_point_in_path(
@@ -20,41 +20,39 @@ impl Context {
     }
 
     pub fn fill_rect(&mut self, rect: &Rect) {
+        self.draw_with_composite_layer(|context| context.fill_rect_with_paint(rect));
+    }
+
+    fn fill_rect_with_paint(&mut self, rect: &Rect) {
         self.set_scale_for_device();
         //let path = skia_safe::Path::rect(rect, None);
 
-        if let Some(paint) = self.state.paint.fill_shadow_paint(
-            self.state.shadow_offset,
-            self.state.shadow_color,
-            self.state.shadow_blur,
-        ) {
-            self.surface.canvas()
-                .draw_rect(rect, &paint);
-            //.draw_path(&path, &paint);
-        }
+        let paint = self.state.paint.fill_paint().clone();
+        self.draw_shadow(&paint, |canvas, paint| {
+            canvas.draw_rect(rect, paint);
+        });
         self.surface
             .canvas()
-            .draw_rect(rect, self.state.paint.fill_paint());
+            .draw_rect(rect, &paint);
         // .draw_path(&path, self.state.paint.fill_paint());
 
         self.clear_scale_for_device();
     }
 
     pub fn stroke_rect(&mut self, rect: &Rect) {
+        self.draw_with_composite_layer(|context| context.stroke_rect_with_paint(rect));
+    }
+
+    fn stroke_rect_with_paint(&mut self, rect: &Rect) {
         self.set_scale_for_device();
         // let path = skia_safe::Path::rect(rect, None);
-        if let Some(paint) = &mut self.state.paint.stroke_shadow_paint(
-            self.state.shadow_offset,
-            self.state.shadow_color,
-            self.state.shadow_blur,
-        ) {
-            self.surface.canvas()
-                .draw_rect(rect, &paint);
-            //.draw_path(&path, &paint);
-        }
+        let paint = self.state.paint.stroke_paint().clone();
+        self.draw_shadow(&paint, |canvas, paint| {
+            canvas.draw_rect(rect, paint);
+        });
         self.surface
             .canvas()
-            .draw_rect(rect, self.state.paint.stroke_paint());
+            .draw_rect(rect, &paint);
         //.draw_path(&path, self.state.paint.stroke_paint());
         self.clear_scale_for_device();
     }
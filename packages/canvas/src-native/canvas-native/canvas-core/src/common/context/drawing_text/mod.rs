@@ -2,10 +2,12 @@ use std::os::raw::c_float;
 
 use skia_safe::{Point, Size};
 use skia_safe::paint::Style;
+use skia_safe::path::AddPathMode;
 
 use crate::common::context::Context;
 use crate::common::context::drawing_text::text_metrics::TextMetrics;
 use crate::common::context::drawing_text::typography::{get_font_baseline, to_real_text_align};
+use crate::common::context::paths::path::Path;
 use crate::common::context::text_styles::text_align::TextAlign;
 use crate::common::utils::geometry::inflate_stroke_rect;
 
@@ -14,11 +16,11 @@ pub(crate) mod typography;
 
 impl Context {
     pub fn fill_text(&mut self, text: &str, x: c_float, y: c_float, width: c_float) {
-        self.draw_text(true, text, x, y, width);
+        self.draw_with_composite_layer(|context| context.draw_text(true, text, x, y, width));
     }
 
     pub fn stroke_text(&mut self, text: &str, x: c_float, y: c_float, width: c_float) {
-        self.draw_text(false, text, x, y, width);
+        self.draw_with_composite_layer(|context| context.draw_text(false, text, x, y, width));
     }
 
     fn draw_text(&mut self, is_fill: bool, text: &str, x: c_float, y: c_float, width: c_float) {
@@ -29,52 +31,23 @@ impl Context {
         if width > 0.0 && width.is_infinite() {
             return;
         }
-        let shadow_paint;
         let paint;
 
         if is_fill {
             paint = self.state.paint.fill_paint().clone();
-            shadow_paint = self.state.paint.fill_shadow_paint(
-                self.state.shadow_offset,
-                self.state.shadow_color,
-                self.state.shadow_blur,
-            ).map(|p| p.clone());
         } else {
             paint = self.state.paint.stroke_paint().clone();
-            shadow_paint = self.state.paint.stroke_shadow_paint(
-                self.state.shadow_offset,
-                self.state.shadow_color,
-                self.state.shadow_blur,
-            ).map(|p| p.clone());
         }
-        let font = &self.state.font;
+        let font = self.state.font.to_skia();
+        let shaped = self.state.font.shape(&font, text, self.state.direction);
+        let font_width = match &shaped {
+            Some(shaped) => shaped.width(),
+            None => font.measure_str(text, Some(&paint)).0,
+        };
 
-        let measurement = font.to_skia().measure_str(text, Some(&paint));
-        let font_width = measurement.0;
-        let max_width = width;
-        let width: f32;
-        let use_max_width = max_width > 0.0 && max_width < font_width;
-        if use_max_width {
-            width = max_width
-        } else {
-            width = font_width;
-        }
-        let metrics = font.to_skia().metrics();
-        let baseline = get_font_baseline(metrics.1, self.state.text_baseline);
-        let mut location: Point = (x, y + baseline).into();
-
-        match to_real_text_align(self.state.text_align, self.state.direction) {
-            TextAlign::RIGHT => {
-                location.x = location.x - width;
-            }
-            TextAlign::CENTER => {
-                location.x = location.x - (width / 2.0);
-            }
-            _ => {
-                // NOOP
-            }
-        }
-        let (line_spacing, metrics) = font.to_skia().metrics();
+        let (location, width) = self.text_location(&font, font_width, x, y, width);
+        let use_max_width = width < font_width;
+        let (line_spacing, metrics) = font.metrics();
 
         let mut rect: (Point, Size) = (
             (
@@ -104,20 +77,28 @@ impl Context {
             self.surface.canvas().scale((scale_x, 1.0));
         }
 
-        let font = font.to_skia();
+        let blob = shaped.as_ref().and_then(|shaped| shaped.to_text_blob());
 
         self.set_scale_for_device();
 
-        if let Some(shadow_paint) = shadow_paint {
-            self.surface
-                .canvas()
-                .draw_str(text, (location.x, location.y), &font, &shadow_paint);
-        }
+        self.draw_shadow(&paint, |canvas, shadow_paint| match &blob {
+            Some(blob) => {
+                canvas.draw_text_blob(blob, location, shadow_paint);
+            }
+            None => {
+                canvas.draw_str(text, (location.x, location.y), &font, shadow_paint);
+            }
+        });
 
-        {
-            self.surface
-                .canvas()
-                .draw_str(text, (location.x, location.y), &font, &paint);
+        match &blob {
+            Some(blob) => {
+                self.surface.canvas().draw_text_blob(blob, location, &paint);
+            }
+            None => {
+                self.surface
+                    .canvas()
+                    .draw_str(text, (location.x, location.y), &font, &paint);
+            }
         }
 
         self.clear_scale_for_device();
@@ -127,13 +108,90 @@ impl Context {
         }
     }
 
+    /// Returns the origin of a run of text `font_width` wide once the current baseline and
+    /// alignment are applied, along with the width it is laid out at.
+    fn text_location(
+        &self,
+        font: &skia_safe::Font,
+        font_width: c_float,
+        x: c_float,
+        y: c_float,
+        max_width: c_float,
+    ) -> (Point, c_float) {
+        let width = if max_width > 0.0 && max_width < font_width {
+            max_width
+        } else {
+            font_width
+        };
+        let (_, metrics) = font.metrics();
+        let baseline = get_font_baseline(metrics, self.state.text_baseline);
+        let mut location: Point = (x, y + baseline).into();
+
+        match to_real_text_align(self.state.text_align, self.state.direction) {
+            TextAlign::RIGHT => {
+                location.x = location.x - width;
+            }
+            TextAlign::CENTER => {
+                location.x = location.x - (width / 2.0);
+            }
+            _ => {
+                // NOOP
+            }
+        }
+        (location, width)
+    }
+
+    pub fn text_to_path(&self, text: &str, x: c_float, y: c_float, width: c_float) -> Path {
+        let mut path = Path::new();
+        if x.is_infinite() || y.is_infinite() {
+            return path;
+        }
+
+        if width > 0.0 && width.is_infinite() {
+            return path;
+        }
+
+        let font = self.state.font.to_skia();
+        let (glyphs, positions, font_width) =
+            match self.state.font.shape(&font, text, self.state.direction) {
+                Some(shaped) => (
+                    shaped.glyphs().to_vec(),
+                    shaped.positions().to_vec(),
+                    shaped.width(),
+                ),
+                None => {
+                    let glyphs = font.str_to_glyphs_vec(text);
+                    let mut positions = vec![Point::default(); glyphs.len()];
+                    font.get_pos(&glyphs, &mut positions, None);
+                    let (font_width, _) =
+                        font.measure_str(text, Some(self.state.paint.fill_paint()));
+                    (glyphs, positions, font_width)
+                }
+            };
+        let (location, width) = self.text_location(&font, font_width, x, y, width);
+
+        for (glyph, position) in glyphs.iter().zip(positions) {
+            if let Some(outline) = font.get_path(*glyph) {
+                path.path.add_path(&outline, position, AddPathMode::Append);
+            }
+        }
+
+        let mut matrix = skia_safe::Matrix::translate(location);
+        if width < font_width && font_width > 0.0 {
+            matrix.pre_scale((width / font_width, 1.0), None);
+        }
+        path.path.transform(&matrix);
+        path
+    }
+
     pub fn measure_text(&self, text: &str) -> TextMetrics {
-        let (width, bounds) = self
-            .state
-            .font
-            .to_skia()
-            .measure_str(text, Some(self.state.paint.fill_paint()));
-        let (_, metrics) = self.state.font.to_skia().metrics();
+        let font = self.state.font.to_skia();
+        let paint = self.state.paint.fill_paint();
+        let (width, bounds) = match self.state.font.shape(&font, text, self.state.direction) {
+            Some(shaped) => (shaped.width(), shaped.bounds(paint)),
+            None => font.measure_str(text, Some(paint)),
+        };
+        let (_, metrics) = font.metrics();
         let ascent = metrics.ascent;
         let descent = metrics.descent;
         let baseline_y = get_font_baseline(metrics, self.state.text_baseline);
@@ -2,15 +2,22 @@ use std::collections::VecDeque;
 
 
 use skia_safe::{
+    font_arguments::{variation_position::Coordinate, VariationPosition},
     font_style::{Slant, Weight, Width},
+    shaper::{
+        run_handler::{Buffer, RunInfo},
+        Feature, RunHandler,
+    },
     typeface::Typeface,
-    FontMetrics, FontMgr, FontStyle,
+    FontArguments, FontMetrics, FontMgr, FontStyle, FourByteTag, GlyphId, Paint, Point, Rect,
+    Shaper, TextBlob, TextBlobBuilder,
 };
 
 use crate::{
     common::context::text_styles::text_align::TextAlign,
     common::context::text_styles::text_baseline::TextBaseLine,
-    common::context::text_styles::text_direction::TextDirection, common::context::Device,
+    common::context::text_styles::text_direction::TextDirection,
+    common::context::text_styles::text_rendering::TextRenderingOptions, common::context::Device,
     common::utils::dimensions::parse_size,
 };
 
@@ -22,6 +29,11 @@ const LARGE: &str = "18px";
 const X_LARGE: &str = "24px";
 const XX_LARGE: &str = "32px";
 
+thread_local! {
+    /// Shared by every shaping call on the thread, as creating a shaper loads HarfBuzz state.
+    static SHAPER: Shaper = Shaper::new(None);
+}
+
 pub(crate) fn get_font_baseline(metrics: FontMetrics, baseline: TextBaseLine) -> f32 {
     match baseline {
         TextBaseLine::TOP => metrics.ascent,
@@ -67,11 +79,28 @@ pub(crate) fn to_real_text_align(align: TextAlign, direction: TextDirection) ->
     }
 }
 
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct FontVariation {
+    pub axis: FourByteTag,
+    pub value: f32,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct FontFeature {
+    pub tag: FourByteTag,
+    pub value: u32,
+}
+
 #[derive(Debug, Clone)]
 pub struct Font {
     pub(crate) font_details: String,
     pub(crate) font: ParsedFont,
     pub(crate) device: Device,
+    pub(crate) variation_settings: String,
+    pub(crate) variations: Vec<FontVariation>,
+    pub(crate) feature_settings: String,
+    pub(crate) features: Vec<FontFeature>,
+    pub(crate) rendering: TextRenderingOptions,
 }
 
 impl Font {
@@ -80,6 +109,11 @@ impl Font {
             font_details: font_details.to_string(),
             font: parse_font(font_details),
             device,
+            variation_settings: NORMAL.to_string(),
+            variations: Vec::new(),
+            feature_settings: NORMAL.to_string(),
+            features: Vec::new(),
+            rendering: TextRenderingOptions::default(),
         }
     }
 
@@ -100,6 +134,49 @@ impl Font {
         self.font = parse_font(font_details);
     }
 
+    pub fn get_variation_settings(&self) -> &str {
+        self.variation_settings.as_ref()
+    }
+
+    pub fn variations(&self) -> &[FontVariation] {
+        self.variations.as_slice()
+    }
+
+    /// Accepts the `font-variation-settings` syntax, e.g. `"wght" 650, "wdth" 80`.
+    /// Invalid values are ignored and leave the current settings in place.
+    pub fn set_variation_settings(&mut self, settings: &str) {
+        if let Some(variations) = parse_font_variation_settings(settings) {
+            self.variation_settings = settings.trim().to_string();
+            self.variations = variations;
+        }
+    }
+
+    pub fn get_feature_settings(&self) -> &str {
+        self.feature_settings.as_ref()
+    }
+
+    /// OpenType features requested through `font-feature-settings`, applied by `shape`.
+    pub fn features(&self) -> &[FontFeature] {
+        self.features.as_slice()
+    }
+
+    /// Accepts the `font-feature-settings` syntax, e.g. `"liga" 0, "tnum", "ss01" on`.
+    /// Invalid values are ignored and leave the current settings in place.
+    pub fn set_feature_settings(&mut self, settings: &str) {
+        if let Some(features) = parse_font_feature_settings(settings) {
+            self.feature_settings = settings.trim().to_string();
+            self.features = features;
+        }
+    }
+
+    pub fn rendering(&self) -> &TextRenderingOptions {
+        &self.rendering
+    }
+
+    pub fn rendering_mut(&mut self) -> &mut TextRenderingOptions {
+        &mut self.rendering
+    }
+
     pub fn load_type_from_path(&mut self, path: &str) -> Option<Typeface> {
         let mgr = FontMgr::default();
         match std::fs::read(std::path::Path::new(path)) {
@@ -126,15 +203,157 @@ impl Font {
                 }
             }
         }
-        skia_safe::Font::from_typeface(
+        if !self.variations.is_empty() {
+            let coordinates: Vec<Coordinate> = self
+                .variations
+                .iter()
+                .map(|variation| Coordinate {
+                    axis: variation.axis,
+                    value: variation.value,
+                })
+                .collect();
+            let arguments = FontArguments::new().set_variation_design_position(VariationPosition {
+                coordinates: coordinates.as_slice(),
+            });
+            if let Some(typeface) = default_typeface.clone_with_arguments(&arguments) {
+                default_typeface = typeface;
+            }
+        }
+        let mut font = skia_safe::Font::from_typeface(
             default_typeface,
             Some(parse_size(self.font.font_size(), self.device)),
-        )
+        );
+        self.rendering.apply(&mut font);
+        font
     }
 
     pub fn to_skia(&self) -> skia_safe::Font {
         self.to_font()
     }
+
+    /// Shapes `text` in `font` with the `font-feature-settings` features. Returns `None` when
+    /// no features are set, in which case text is drawn straight from the character map. The
+    /// context has no `lang`, so text is always shaped as English, which matters for the
+    /// language specific forms of features such as `locl`.
+    pub(crate) fn shape(
+        &self,
+        font: &skia_safe::Font,
+        text: &str,
+        direction: TextDirection,
+    ) -> Option<ShapedText> {
+        if self.features.is_empty() || text.is_empty() {
+            return None;
+        }
+        let length = text.len();
+        let features: Vec<Feature> = self
+            .features
+            .iter()
+            .map(|feature| Feature {
+                tag: *feature.tag,
+                value: feature.value,
+                start: 0,
+                end: length,
+            })
+            .collect();
+        let bidi_level = if direction == TextDirection::RTL { 1 } else { 0 };
+        let mut font_runs = Shaper::new_trivial_font_run_iterator(font, length);
+        let mut bidi_runs = Shaper::new_trivial_bidi_run_iterator(bidi_level, length);
+        let mut script_runs = Shaper::new_hb_icu_script_run_iterator(text);
+        let mut language_runs = Shaper::new_trivial_language_run_iterator("en");
+        let mut collector = GlyphCollector::default();
+        SHAPER.with(|shaper| {
+            shaper.shape_with_iterators_and_features(
+                text,
+                &mut font_runs,
+                &mut bidi_runs,
+                &mut script_runs,
+                &mut language_runs,
+                features.as_slice(),
+                f32::MAX,
+                &mut collector,
+            )
+        });
+        Some(ShapedText {
+            font: font.clone(),
+            glyphs: collector.glyphs,
+            positions: collector.positions,
+            width: collector.origin.x,
+        })
+    }
+}
+
+/// Glyphs laid out by the shaper along a baseline starting at (0, 0).
+pub(crate) struct ShapedText {
+    font: skia_safe::Font,
+    glyphs: Vec<GlyphId>,
+    positions: Vec<Point>,
+    width: f32,
+}
+
+impl ShapedText {
+    pub(crate) fn width(&self) -> f32 {
+        self.width
+    }
+
+    pub(crate) fn glyphs(&self) -> &[GlyphId] {
+        self.glyphs.as_slice()
+    }
+
+    pub(crate) fn positions(&self) -> &[Point] {
+        self.positions.as_slice()
+    }
+
+    pub(crate) fn to_text_blob(&self) -> Option<TextBlob> {
+        let mut builder = TextBlobBuilder::new();
+        let (glyphs, positions) = builder.alloc_run_pos(&self.font, self.glyphs.len(), None);
+        glyphs.copy_from_slice(&self.glyphs);
+        positions.copy_from_slice(&self.positions);
+        builder.make()
+    }
+
+    /// The union of the glyph bounds as drawn with `paint`.
+    pub(crate) fn bounds(&self, paint: &Paint) -> Rect {
+        let mut glyph_bounds = vec![Rect::default(); self.glyphs.len()];
+        self.font
+            .get_bounds(&self.glyphs, &mut glyph_bounds, Some(paint));
+        let mut bounds = Rect::new_empty();
+        for (glyph, position) in glyph_bounds.iter().zip(self.positions.iter()) {
+            bounds.join(glyph.with_offset(*position));
+        }
+        bounds
+    }
+}
+
+/// Collects the shaped runs into one list of glyphs, laid out one run after another.
+#[derive(Default)]
+struct GlyphCollector {
+    glyphs: Vec<GlyphId>,
+    positions: Vec<Point>,
+    run_glyphs: Vec<GlyphId>,
+    run_positions: Vec<Point>,
+    origin: Point,
+}
+
+impl RunHandler for GlyphCollector {
+    fn begin_line(&mut self) {}
+
+    fn run_info(&mut self, _: &RunInfo) {}
+
+    fn commit_run_info(&mut self) {}
+
+    fn run_buffer(&mut self, info: &RunInfo) -> Buffer {
+        self.run_glyphs = vec![0; info.glyph_count];
+        self.run_positions = vec![Point::default(); info.glyph_count];
+        Buffer::new(&mut self.run_glyphs, &mut self.run_positions, self.origin)
+    }
+
+    fn commit_run_buffer(&mut self, info: &RunInfo) {
+        self.glyphs.extend_from_slice(&self.run_glyphs);
+        self.positions.extend_from_slice(&self.run_positions);
+        self.origin.x += info.advance.x;
+    }
+
+    fn commit_line(&mut self) {}
 }
 
 #[derive(Clone, Debug)]
@@ -339,6 +558,73 @@ fn parse_font_family(value: &str) -> Vec<String> {
     return result;
 }
 
+fn parse_tag(value: &str) -> Option<FourByteTag> {
+    let value = value.trim();
+    let tag = value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .or_else(|| value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))?;
+    let bytes = tag.as_bytes();
+    if bytes.len() != 4 || !bytes.iter().all(|b| (0x20..=0x7E).contains(b)) {
+        return None;
+    }
+    Some(FourByteTag::from_chars(
+        bytes[0] as char,
+        bytes[1] as char,
+        bytes[2] as char,
+        bytes[3] as char,
+    ))
+}
+
+fn split_tag(value: &str) -> Option<(&str, &str)> {
+    let value = value.trim();
+    let quote = value.chars().next()?;
+    if quote != '"' && quote != '\'' {
+        return None;
+    }
+    let end = value[1..].find(quote)? + 2;
+    Some((&value[..end], value[end..].trim()))
+}
+
+/// Parses `font-variation-settings`. Returns `None` when the value is invalid.
+pub(crate) fn parse_font_variation_settings(value: &str) -> Option<Vec<FontVariation>> {
+    let value = value.trim();
+    if value == NORMAL {
+        return Some(Vec::new());
+    }
+    let mut variations: Vec<FontVariation> = Vec::new();
+    for item in value.split(',') {
+        let (tag, number) = split_tag(item)?;
+        let axis = parse_tag(tag)?;
+        let value = number.parse::<f32>().ok().filter(|v| v.is_finite())?;
+        // later declarations of the same axis win
+        variations.retain(|v| v.axis != axis);
+        variations.push(FontVariation { axis, value });
+    }
+    Some(variations)
+}
+
+/// Parses `font-feature-settings`. Returns `None` when the value is invalid.
+pub(crate) fn parse_font_feature_settings(value: &str) -> Option<Vec<FontFeature>> {
+    let value = value.trim();
+    if value == NORMAL {
+        return Some(Vec::new());
+    }
+    let mut features: Vec<FontFeature> = Vec::new();
+    for item in value.split(',') {
+        let (tag, setting) = split_tag(item)?;
+        let tag = parse_tag(tag)?;
+        let value = match setting {
+            "" | "on" => 1,
+            "off" => 0,
+            _ => setting.parse::<u32>().ok()?,
+        };
+        features.retain(|f| f.tag != tag);
+        features.push(FontFeature { tag, value });
+    }
+    Some(features)
+}
+
 fn is_size_length(value: &str) -> bool {
     return value.contains("pc")
         || value.contains("pt")
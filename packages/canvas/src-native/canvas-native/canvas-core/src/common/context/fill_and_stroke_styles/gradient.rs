@@ -1,10 +1,15 @@
 use std::os::raw::c_float;
 
-use skia_safe::{Color, Point, Shader, TileMode};
+use skia_safe::{
+    BlendMode, Color, Color4f, FilterMode, Paint, PictureRecorder, Point, Rect, Shader, TileMode,
+};
 use skia_safe::{
     gradient_shader::GradientShaderColors,
 };
 
+use crate::common::context::fill_and_stroke_styles::gradient_interpolation::{
+    GradientInterpolation, HueInterpolationMethod, InterpolationColorSpace,
+};
 use crate::common::context::matrix::Matrix;
 
 #[derive(Clone)]
@@ -16,6 +21,7 @@ pub enum Gradient {
         colors: Vec<Color>,
         matrix: Option<Matrix>,
         tile_mode: TileMode,
+        interpolation: GradientInterpolation,
     },
     Radial {
         start: Point,
@@ -26,6 +32,7 @@ pub enum Gradient {
         colors: Vec<Color>,
         matrix: Option<Matrix>,
         tile_mode: TileMode,
+        interpolation: GradientInterpolation,
     },
     Conic {
         center: Point,
@@ -34,6 +41,16 @@ pub enum Gradient {
         colors: Vec<Color>,
         matrix: Option<Matrix>,
         tile_mode: TileMode,
+        interpolation: GradientInterpolation,
+    },
+    /// A Coons patch: four cubic edges given as twelve points clockwise from the top left corner,
+    /// with one color per corner blended across the patch. Color stops and interpolation
+    /// settings do not apply, the corner colors are blended in sRGB.
+    Mesh {
+        cubics: [Point; 12],
+        colors: [Color; 4],
+        matrix: Option<Matrix>,
+        tile_mode: TileMode,
     },
 }
 
@@ -43,6 +60,7 @@ impl Gradient {
             Gradient::Linear { .. } => {}
             Gradient::Radial { .. } => {}
             Gradient::Conic { .. } => {}
+            Gradient::Mesh { .. } => {}
         }
     }
 
@@ -57,6 +75,9 @@ impl Gradient {
             Gradient::Conic {
                 ref mut tile_mode, ..
             } => *tile_mode = mode,
+            Gradient::Mesh {
+                ref mut tile_mode, ..
+            } => *tile_mode = mode,
         }
     }
 
@@ -69,8 +90,9 @@ impl Gradient {
                 colors,
                 matrix,
                 tile_mode,
-                ..
+                interpolation,
             } => {
+                let (stops, colors) = interpolation.resolve(stops, colors);
                 if let Some(matrix) = matrix {
                     let matrix = matrix.matrix.to_m33();
                     Gradient::to_linear_gradient_shader(
@@ -101,8 +123,9 @@ impl Gradient {
                 colors,
                 matrix,
                 tile_mode,
-                ..
+                interpolation,
             } => {
+                let (stops, colors) = interpolation.resolve(stops, colors);
                 if let Some(matrix) = matrix {
                     let matrix = matrix.matrix.to_m33();
                     Gradient::to_radial_gradient_shader(
@@ -135,8 +158,9 @@ impl Gradient {
                 colors,
                 matrix,
                 tile_mode,
-                ..
+                interpolation,
             } => {
+                let (stops, colors) = interpolation.resolve(stops, colors);
                 if let Some(matrix) = matrix {
                     let matrix = matrix.matrix.to_m33();
                     Gradient::to_conic_gradient_shader(
@@ -158,7 +182,98 @@ impl Gradient {
                     )
                 }
             }
+            Gradient::Mesh {
+                cubics,
+                colors,
+                matrix,
+                tile_mode,
+            } => {
+                let matrix = matrix.as_ref().map(|matrix| matrix.matrix.to_m33());
+                Gradient::to_mesh_gradient_shader(cubics, colors, matrix.as_ref(), *tile_mode)
+            }
+        }
+    }
+
+    /// Reads twelve control points from 24 interleaved x, y values.
+    pub fn cubics_from_slice(points: &[f32]) -> Option<[Point; 12]> {
+        if points.len() != 24 {
+            return None;
+        }
+        let mut cubics = [Point::default(); 12];
+        for (i, point) in cubics.iter_mut().enumerate() {
+            *point = Point::new(points[i * 2], points[i * 2 + 1]);
+        }
+        Some(cubics)
+    }
+
+    /// Reads four ARGB corner colors.
+    pub fn corner_colors_from_slice(colors: &[u32]) -> Option<[Color; 4]> {
+        if colors.len() != 4 {
+            return None;
+        }
+        Some([
+            Color::new(colors[0]),
+            Color::new(colors[1]),
+            Color::new(colors[2]),
+            Color::new(colors[3]),
+        ])
+    }
+
+    /// The four cubics of a patch whose edges run straight along `rect`.
+    pub fn rect_cubics(rect: &Rect) -> [Point; 12] {
+        let lerp = |a: Point, b: Point, t: f32| {
+            Point::new(a.x + (b.x - a.x) * t, a.y + (b.y - a.y) * t)
+        };
+        let corners = [
+            Point::new(rect.left, rect.top),
+            Point::new(rect.right, rect.top),
+            Point::new(rect.right, rect.bottom),
+            Point::new(rect.left, rect.bottom),
+        ];
+        let mut cubics = [Point::default(); 12];
+        for i in 0..4 {
+            let (start, end) = (corners[i], corners[(i + 1) % 4]);
+            cubics[i * 3] = start;
+            cubics[i * 3 + 1] = lerp(start, end, 1.0 / 3.0);
+            cubics[i * 3 + 2] = lerp(start, end, 2.0 / 3.0);
+        }
+        cubics
+    }
+
+    /// Draws a patch with the given corner colors, modulated by the paint's alpha. The color of
+    /// the paint is not blended in.
+    pub(crate) fn draw_mesh(
+        canvas: &mut skia_safe::Canvas,
+        cubics: &[Point; 12],
+        colors: &[Color; 4],
+        paint: &Paint,
+    ) {
+        canvas.draw_patch(cubics, colors, None, BlendMode::Dst, paint);
+    }
+
+    fn to_mesh_gradient_shader(
+        cubics: &[Point; 12],
+        colors: &[Color; 4],
+        matrix: Option<&skia_safe::Matrix>,
+        tile_mode: TileMode,
+    ) -> Option<Shader> {
+        let mut bounds = Rect::default();
+        bounds.set_bounds(cubics);
+        if bounds.is_empty() {
+            return None;
         }
+        let mut recorder = PictureRecorder::new();
+        let canvas = recorder.begin_recording(bounds, None);
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true);
+        Gradient::draw_mesh(canvas, cubics, colors, &paint);
+        let picture = recorder.finish_recording_as_picture(Some(&bounds))?;
+        Some(picture.to_shader(
+            (tile_mode, tile_mode),
+            FilterMode::Linear,
+            matrix,
+            Some(&bounds),
+        ))
     }
 
     fn to_radial_gradient_shader(
@@ -167,11 +282,11 @@ impl Gradient {
         stop: Point,
         stop_radius: c_float,
         stops: &[f32],
-        colors: &[Color],
+        colors: &[Color4f],
         matrix: Option<&skia_safe::Matrix>,
         tile_mode: TileMode,
     ) -> Option<Shader> {
-        let color_array = GradientShaderColors::Colors(colors);
+        let color_array = GradientShaderColors::ColorsInSpace(colors, None);
         Shader::two_point_conical_gradient(
             start,
             start_radius,
@@ -189,11 +304,11 @@ impl Gradient {
         start: Point,
         stop: Point,
         stops: &[f32],
-        colors: &[Color],
+        colors: &[Color4f],
         matrix: Option<&skia_safe::Matrix>,
         tile_mode: TileMode,
     ) -> Option<Shader> {
-        let color_array = GradientShaderColors::Colors(colors);
+        let color_array = GradientShaderColors::ColorsInSpace(colors, None);
 
         Shader::linear_gradient(
             (start, stop),
@@ -209,11 +324,11 @@ impl Gradient {
         center: Point,
         angle: f32,
         stops: &[f32],
-        colors: &[Color],
+        colors: &[Color4f],
         matrix: Option<&skia_safe::Matrix>,
         tile_mode: TileMode,
     ) -> Option<Shader> {
-        let color_array = GradientShaderColors::Colors(colors);
+        let color_array = GradientShaderColors::ColorsInSpace(colors, None);
 
         let mut rotated = matrix.map(|v| v.clone()).unwrap_or(
             skia_safe::Matrix::new_identity()
@@ -234,11 +349,62 @@ impl Gradient {
         )
     }
 
+    /// Mesh gradients always report the default, their corner colors are blended in sRGB.
+    pub fn interpolation(&self) -> GradientInterpolation {
+        match self {
+            Gradient::Linear { interpolation, .. } => *interpolation,
+            Gradient::Radial { interpolation, .. } => *interpolation,
+            Gradient::Conic { interpolation, .. } => *interpolation,
+            Gradient::Mesh { .. } => GradientInterpolation::default(),
+        }
+    }
+
+    fn interpolation_mut(&mut self) -> Option<&mut GradientInterpolation> {
+        match self {
+            Gradient::Linear { interpolation, .. } => Some(interpolation),
+            Gradient::Radial { interpolation, .. } => Some(interpolation),
+            Gradient::Conic { interpolation, .. } => Some(interpolation),
+            Gradient::Mesh { .. } => None,
+        }
+    }
+
+    /// Ignored by mesh gradients.
+    pub fn set_interpolation_color_space(&mut self, color_space: InterpolationColorSpace) {
+        if let Some(interpolation) = self.interpolation_mut() {
+            interpolation.color_space = color_space;
+        }
+    }
+
+    /// Ignored by mesh gradients.
+    pub fn set_hue_interpolation_method(&mut self, method: HueInterpolationMethod) {
+        if let Some(interpolation) = self.interpolation_mut() {
+            interpolation.hue_method = method;
+        }
+    }
+
+    /// Ignored by mesh gradients.
+    pub fn set_dither(&mut self, dither: bool) {
+        if let Some(interpolation) = self.interpolation_mut() {
+            interpolation.dither = dither;
+        }
+    }
+
+    /// Sets one of the corner colors of a mesh gradient, clockwise from the top left.
+    pub fn set_corner_color(&mut self, corner: usize, color: Color) {
+        if let Gradient::Mesh { colors, .. } = self {
+            if corner < colors.len() {
+                colors[corner] = color;
+            }
+        }
+    }
+
+    /// Color stops only apply to linear, radial and conic gradients.
     pub fn add_color_stop(&mut self, offset: c_float, color: Color) {
         let stops = match self {
             Gradient::Linear { stops, .. } => stops,
             Gradient::Radial { stops, .. } => stops,
             Gradient::Conic { stops, .. } => stops,
+            Gradient::Mesh { .. } => return,
         };
 
         // insert the new entries at the right index to keep the vectors sorted
@@ -258,6 +424,7 @@ impl Gradient {
                 colors.insert(idx, color);
                 stops.insert(idx, offset);
             }
+            Gradient::Mesh { .. } => {}
         };
     }
 }
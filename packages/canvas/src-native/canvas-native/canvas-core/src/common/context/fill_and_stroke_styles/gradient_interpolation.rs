@@ -0,0 +1,309 @@
+use skia_safe::{Color, Color4f};
+
+use crate::common::utils::color::{
+    d50_to_d65, d65_to_d50, gamma_decode_srgb, gamma_encode_srgb, lab_to_lch, lab_to_xyz_d50,
+    lch_to_lab, linear_p3_to_linear_srgb, linear_srgb_to_linear_p3, linear_srgb_to_oklab,
+    linear_srgb_to_xyz_d65, oklab_to_linear_srgb, xyz_d50_to_lab, xyz_d65_to_linear_srgb,
+};
+
+/// Number of extra stops generated between two color stops when interpolating outside sRGB.
+const SEGMENT_STEPS: usize = 16;
+
+/// Chroma below which a hue is treated as powerless.
+const ACHROMATIC: f64 = 1e-4;
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum InterpolationColorSpace {
+    SRGB = 0,
+    SRGBLinear = 1,
+    Lab = 2,
+    Lch = 3,
+    Oklab = 4,
+    Oklch = 5,
+    DisplayP3 = 6,
+}
+
+impl Default for InterpolationColorSpace {
+    fn default() -> Self {
+        Self::SRGB
+    }
+}
+
+impl From<i32> for InterpolationColorSpace {
+    fn from(value: i32) -> Self {
+        match value {
+            1 => Self::SRGBLinear,
+            2 => Self::Lab,
+            3 => Self::Lch,
+            4 => Self::Oklab,
+            5 => Self::Oklch,
+            6 => Self::DisplayP3,
+            _ => Self::SRGB,
+        }
+    }
+}
+
+impl Into<i32> for InterpolationColorSpace {
+    fn into(self) -> i32 {
+        match self {
+            InterpolationColorSpace::SRGB => 0,
+            InterpolationColorSpace::SRGBLinear => 1,
+            InterpolationColorSpace::Lab => 2,
+            InterpolationColorSpace::Lch => 3,
+            InterpolationColorSpace::Oklab => 4,
+            InterpolationColorSpace::Oklch => 5,
+            InterpolationColorSpace::DisplayP3 => 6,
+        }
+    }
+}
+
+impl InterpolationColorSpace {
+    pub fn from_str(value: &str) -> Option<Self> {
+        match value {
+            "srgb" => Some(Self::SRGB),
+            "srgb-linear" => Some(Self::SRGBLinear),
+            "lab" => Some(Self::Lab),
+            "lch" => Some(Self::Lch),
+            "oklab" => Some(Self::Oklab),
+            "oklch" => Some(Self::Oklch),
+            "display-p3" => Some(Self::DisplayP3),
+            _ => None,
+        }
+    }
+
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            InterpolationColorSpace::SRGB => "srgb",
+            InterpolationColorSpace::SRGBLinear => "srgb-linear",
+            InterpolationColorSpace::Lab => "lab",
+            InterpolationColorSpace::Lch => "lch",
+            InterpolationColorSpace::Oklab => "oklab",
+            InterpolationColorSpace::Oklch => "oklch",
+            InterpolationColorSpace::DisplayP3 => "display-p3",
+        }
+    }
+
+    fn is_polar(&self) -> bool {
+        match self {
+            InterpolationColorSpace::Lch | InterpolationColorSpace::Oklch => true,
+            _ => false,
+        }
+    }
+
+    fn from_srgb(&self, rgb: (f64, f64, f64)) -> (f64, f64, f64) {
+        match self {
+            InterpolationColorSpace::SRGB => rgb,
+            InterpolationColorSpace::SRGBLinear => gamma_decode_srgb(rgb),
+            InterpolationColorSpace::Lab => {
+                xyz_d50_to_lab(d65_to_d50(linear_srgb_to_xyz_d65(gamma_decode_srgb(rgb))))
+            }
+            InterpolationColorSpace::Lch => lab_to_lch(InterpolationColorSpace::Lab.from_srgb(rgb)),
+            InterpolationColorSpace::Oklab => linear_srgb_to_oklab(gamma_decode_srgb(rgb)),
+            InterpolationColorSpace::Oklch => {
+                lab_to_lch(InterpolationColorSpace::Oklab.from_srgb(rgb))
+            }
+            InterpolationColorSpace::DisplayP3 => {
+                gamma_encode_srgb(linear_srgb_to_linear_p3(gamma_decode_srgb(rgb)))
+            }
+        }
+    }
+
+    fn to_srgb(&self, value: (f64, f64, f64)) -> (f64, f64, f64) {
+        match self {
+            InterpolationColorSpace::SRGB => value,
+            InterpolationColorSpace::SRGBLinear => gamma_encode_srgb(value),
+            InterpolationColorSpace::Lab => gamma_encode_srgb(xyz_d65_to_linear_srgb(d50_to_d65(
+                lab_to_xyz_d50(value.0, value.1, value.2),
+            ))),
+            InterpolationColorSpace::Lch => InterpolationColorSpace::Lab.to_srgb(lch_to_lab(value)),
+            InterpolationColorSpace::Oklab => gamma_encode_srgb(oklab_to_linear_srgb(value)),
+            InterpolationColorSpace::Oklch => {
+                InterpolationColorSpace::Oklab.to_srgb(lch_to_lab(value))
+            }
+            InterpolationColorSpace::DisplayP3 => {
+                gamma_encode_srgb(linear_p3_to_linear_srgb(gamma_decode_srgb(value)))
+            }
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum HueInterpolationMethod {
+    Shorter = 0,
+    Longer = 1,
+    Increasing = 2,
+    Decreasing = 3,
+}
+
+impl Default for HueInterpolationMethod {
+    fn default() -> Self {
+        Self::Shorter
+    }
+}
+
+impl From<i32> for HueInterpolationMethod {
+    fn from(value: i32) -> Self {
+        match value {
+            1 => Self::Longer,
+            2 => Self::Increasing,
+            3 => Self::Decreasing,
+            _ => Self::Shorter,
+        }
+    }
+}
+
+impl Into<i32> for HueInterpolationMethod {
+    fn into(self) -> i32 {
+        match self {
+            HueInterpolationMethod::Shorter => 0,
+            HueInterpolationMethod::Longer => 1,
+            HueInterpolationMethod::Increasing => 2,
+            HueInterpolationMethod::Decreasing => 3,
+        }
+    }
+}
+
+impl HueInterpolationMethod {
+    pub fn from_str(value: &str) -> Option<Self> {
+        match value {
+            "shorter" => Some(Self::Shorter),
+            "longer" => Some(Self::Longer),
+            "increasing" => Some(Self::Increasing),
+            "decreasing" => Some(Self::Decreasing),
+            _ => None,
+        }
+    }
+
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            HueInterpolationMethod::Shorter => "shorter",
+            HueInterpolationMethod::Longer => "longer",
+            HueInterpolationMethod::Increasing => "increasing",
+            HueInterpolationMethod::Decreasing => "decreasing",
+        }
+    }
+
+    /// Adjusts a pair of hues in degrees so that interpolating linearly between them follows
+    /// this method, as described in CSS Color 4.
+    fn fixup(&self, h1: f64, h2: f64) -> (f64, f64) {
+        let (mut h1, mut h2) = (h1.rem_euclid(360.0), h2.rem_euclid(360.0));
+        let delta = h2 - h1;
+        match self {
+            HueInterpolationMethod::Shorter => {
+                if delta > 180.0 {
+                    h1 += 360.0;
+                } else if delta < -180.0 {
+                    h2 += 360.0;
+                }
+            }
+            HueInterpolationMethod::Longer => {
+                if delta > 0.0 && delta < 180.0 {
+                    h1 += 360.0;
+                } else if delta > -180.0 && delta <= 0.0 {
+                    h2 += 360.0;
+                }
+            }
+            HueInterpolationMethod::Increasing => {
+                if h2 < h1 {
+                    h2 += 360.0;
+                }
+            }
+            HueInterpolationMethod::Decreasing => {
+                if h1 < h2 {
+                    h1 += 360.0;
+                }
+            }
+        }
+        (h1, h2)
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default)]
+pub struct GradientInterpolation {
+    pub color_space: InterpolationColorSpace,
+    pub hue_method: HueInterpolationMethod,
+    pub dither: bool,
+}
+
+impl GradientInterpolation {
+    /// Converts the color stops into the stops handed to Skia. Skia interpolates in sRGB, so for
+    /// any other space each segment is subdivided and the intermediate colors are computed in
+    /// that space with premultiplied alpha.
+    pub(crate) fn resolve(&self, stops: &[f32], colors: &[Color]) -> (Vec<f32>, Vec<Color4f>) {
+        let colors4f: Vec<Color4f> = colors.iter().map(|color| Color4f::from(*color)).collect();
+        if self.color_space == InterpolationColorSpace::SRGB || stops.len() < 2 {
+            return (stops.to_vec(), colors4f);
+        }
+
+        let mut out_stops = Vec::with_capacity(stops.len() * SEGMENT_STEPS);
+        let mut out_colors = Vec::with_capacity(stops.len() * SEGMENT_STEPS);
+        for i in 0..stops.len() - 1 {
+            out_stops.push(stops[i]);
+            out_colors.push(colors4f[i]);
+            let (start, end) = (stops[i], stops[i + 1]);
+            // hard stops have nothing to interpolate
+            if end - start <= f32::EPSILON {
+                continue;
+            }
+            for step in 1..SEGMENT_STEPS {
+                let t = step as f64 / SEGMENT_STEPS as f64;
+                out_stops.push(start + (end - start) * t as f32);
+                out_colors.push(self.mix(colors4f[i], colors4f[i + 1], t));
+            }
+        }
+        out_stops.push(stops[stops.len() - 1]);
+        out_colors.push(colors4f[colors4f.len() - 1]);
+        (out_stops, out_colors)
+    }
+
+    fn mix(&self, from: Color4f, to: Color4f, t: f64) -> Color4f {
+        let space = self.color_space;
+        let mut a = space.from_srgb((from.r as f64, from.g as f64, from.b as f64));
+        let mut b = space.from_srgb((to.r as f64, to.g as f64, to.b as f64));
+        let (alpha_a, alpha_b) = (from.a as f64, to.a as f64);
+        let alpha = alpha_a + (alpha_b - alpha_a) * t;
+
+        let lerp = |x: f64, y: f64| x + (y - x) * t;
+        let premul = |value: (f64, f64, f64), alpha: f64, polar: bool| {
+            if polar {
+                (value.0 * alpha, value.1 * alpha, value.2)
+            } else {
+                (value.0 * alpha, value.1 * alpha, value.2 * alpha)
+            }
+        };
+
+        let polar = space.is_polar();
+        if polar {
+            // a powerless hue takes the hue of the other color
+            if a.1 < ACHROMATIC {
+                a.2 = b.2;
+            }
+            if b.1 < ACHROMATIC {
+                b.2 = a.2;
+            }
+            let (h1, h2) = self.hue_method.fixup(a.2, b.2);
+            a.2 = h1;
+            b.2 = h2;
+        }
+
+        let a = premul(a, alpha_a, polar);
+        let b = premul(b, alpha_b, polar);
+        let mut value = (lerp(a.0, b.0), lerp(a.1, b.1), lerp(a.2, b.2));
+        if alpha > 0.0 {
+            value.0 /= alpha;
+            value.1 /= alpha;
+            if !polar {
+                value.2 /= alpha;
+            }
+        }
+        if polar {
+            value.2 = value.2.rem_euclid(360.0);
+        }
+
+        let rgb = space.to_srgb(value);
+        let clamp = |c: f64| c.max(0.0).min(1.0) as f32;
+        Color4f::new(clamp(rgb.0), clamp(rgb.1), clamp(rgb.2), clamp(alpha))
+    }
+}
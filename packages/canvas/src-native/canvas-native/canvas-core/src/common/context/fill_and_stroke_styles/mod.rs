@@ -1,8 +1,10 @@
 use crate::{common::context::Context, common::context::fill_and_stroke_styles::paint::PaintStyle};
 
 pub mod gradient;
+pub mod gradient_interpolation;
 pub mod paint;
 pub mod pattern;
+pub mod runtime_effect;
 
 impl Context {
     pub fn set_fill_style(&mut self, style: PaintStyle) {
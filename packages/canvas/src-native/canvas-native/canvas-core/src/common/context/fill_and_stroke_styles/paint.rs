@@ -1,36 +1,60 @@
 use std::os::raw::c_float;
 
-use skia_safe::{BlendMode, Color, Point};
+use skia_safe::{
+    BlendMode, Color, Color4f, color_filters, ColorSpace, image_filters, ImageFilter, Shader,
+    Vector,
+};
 use skia_safe::paint::{Cap, Style};
 
 use crate::common::context::fill_and_stroke_styles::gradient::Gradient;
 use crate::common::context::fill_and_stroke_styles::pattern::Pattern;
+use crate::common::context::fill_and_stroke_styles::runtime_effect::RuntimeEffect;
 use crate::common::context::filter_quality::FilterQuality;
 use crate::common::context::image_smoothing::ImageSmoothingQuality;
 use crate::common::utils::color::to_parsed_color;
 
+/// Solid colors are kept in extended sRGB so colors outside of sRGB survive on wide gamut
+/// surfaces.
 #[derive(Clone)]
 pub enum PaintStyle {
-    Color(Color),
+    Color(Color4f),
     Gradient(Gradient),
     Pattern(Pattern),
+    RuntimeEffect(RuntimeEffect),
 }
 
 impl PaintStyle {
     pub fn new_color(color: u32) -> Self {
-        Self::Color(Color::from(color))
+        Self::Color(Color4f::from(Color::from(color)))
     }
 
     pub fn new_color_rgba(red: u8, green: u8, blue: u8, alpha: u8) -> Self {
-        Self::Color(Color::from_argb(alpha, red, green, blue))
+        Self::Color(Color4f::from(Color::from_argb(alpha, red, green, blue)))
     }
 
     pub fn get_parsed_color(&self) -> Option<String> {
         match self {
-            PaintStyle::Color(color) => Some(to_parsed_color(*color)),
+            PaintStyle::Color(color) => Some(to_parsed_color(color.to_color())),
             _ => None,
         }
     }
+
+    /// The shader this style paints with, a solid color shader for colors.
+    pub fn to_shader(&self, image_smoothing_quality: FilterQuality) -> Option<Shader> {
+        match self {
+            PaintStyle::Color(color) => Some(skia_safe::shaders::color_in_space(
+                color,
+                ColorSpace::new_srgb(),
+            )),
+            PaintStyle::Gradient(gradient) => Gradient::to_shader(gradient),
+            PaintStyle::Pattern(pattern) => {
+                Pattern::to_pattern_shader(pattern, image_smoothing_quality)
+            }
+            PaintStyle::RuntimeEffect(effect) => {
+                RuntimeEffect::to_shader(effect, image_smoothing_quality)
+            }
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -55,13 +79,23 @@ impl Paint {
         } else {
             style = &mut self.stroke_style;
         }
+        let dither = match style {
+            PaintStyle::Gradient(gradient) => gradient.interpolation().dither,
+            _ => false,
+        };
+        if is_fill {
+            self.fill_paint.set_dither(dither);
+        } else {
+            self.stroke_paint.set_dither(dither);
+        }
         match style {
             PaintStyle::Color(color) => {
                 self.fill_paint.set_shader(None);
+                let color_space = ColorSpace::new_srgb();
                 if is_fill {
-                    self.fill_paint.set_color(*color);
+                    self.fill_paint.set_color4f(*color, &color_space);
                 } else {
-                    self.stroke_paint.set_color(*color);
+                    self.stroke_paint.set_color4f(*color, &color_space);
                 }
             }
             PaintStyle::Pattern(pattern) => {
@@ -84,6 +118,19 @@ impl Paint {
                     self.stroke_paint.set_shader(Gradient::to_shader(gradient));
                 }
             }
+            PaintStyle::RuntimeEffect(effect) => {
+                if is_fill {
+                    self.fill_paint.set_shader(RuntimeEffect::to_shader(
+                        effect,
+                        self.image_smoothing_quality,
+                    ));
+                } else {
+                    self.stroke_paint.set_shader(RuntimeEffect::to_shader(
+                        effect,
+                        self.image_smoothing_quality,
+                    ));
+                }
+            }
         }
     }
 
@@ -128,50 +175,35 @@ impl Paint {
         &mut self.image_paint
     }
 
-    fn shadow_paint(
-        mut paint: skia_safe::Paint,
-        offset: Point,
-        color: Color,
-        blur: c_float,
-    ) -> Option<skia_safe::Paint> {
-        let sigma = blur / 2.0;
-        let filter = skia_safe::image_filters::drop_shadow_only(
-            offset,
-            (sigma, sigma),
-            color,
-            None,
-            None,
-        );
+    /// A copy of `paint` that only draws the shadow of what it renders: the alpha of its output,
+    /// after any filter, blurred by `sigma` and tinted with `color`. The alpha and composite
+    /// operation of `paint` still apply.
+    pub(crate) fn shadow_paint(
+        paint: &skia_safe::Paint,
+        color: Color4f,
+        sigma: (c_float, c_float),
+    ) -> skia_safe::Paint {
+        let mut paint = paint.clone();
+        let filter = Self::shadow_filter((0.0, 0.0), sigma, color, paint.image_filter());
         paint.set_image_filter(filter);
-        return Some(paint);
-    }
-
-    pub fn fill_shadow_paint(
-        &self,
-        offset: Point,
-        color: Color,
-        blur: c_float,
-    ) -> Option<skia_safe::Paint> {
-        if !(color != Color::TRANSPARENT && blur > 0.0) {
-            return None;
-        }
-        let mut paint = self.fill_paint().clone();
-        paint.set_color(color);
-        Self::shadow_paint(paint, offset, color, blur)
-    }
-
-    pub fn stroke_shadow_paint(
-        &self,
-        offset: Point,
-        color: Color,
-        blur: c_float,
-    ) -> Option<skia_safe::Paint> {
-        if !(color != Color::TRANSPARENT && blur > 0.0) {
-            return None;
-        }
-        let mut paint = self.stroke_paint().clone();
-        paint.set_color(color);
-        Self::shadow_paint(paint, offset, color, blur)
+        paint
+    }
+
+    /// The alpha of `input` offset by `offset`, blurred by `sigma` and tinted with `color`. The
+    /// tint is blended in rather than handed to the drop shadow, which only takes 8-bit sRGB.
+    pub(crate) fn shadow_filter(
+        offset: impl Into<Vector>,
+        sigma: (c_float, c_float),
+        color: Color4f,
+        input: Option<ImageFilter>,
+    ) -> Option<ImageFilter> {
+        let shadow = image_filters::drop_shadow_only(offset, sigma, Color::BLACK, input, None);
+        let tint = color_filters::blend_with_color_space(
+            color,
+            ColorSpace::new_srgb(),
+            BlendMode::SrcIn,
+        )?;
+        image_filters::color_filter(tint, shadow, None)
     }
 }
 
@@ -203,8 +235,8 @@ impl Default for Paint {
             fill_paint,
             stroke_paint,
             image_paint,
-            fill_style: PaintStyle::Color(Color::BLACK),
-            stroke_style: PaintStyle::Color(Color::BLACK),
+            fill_style: PaintStyle::Color(Color4f::from(Color::BLACK)),
+            stroke_style: PaintStyle::Color(Color4f::from(Color::BLACK)),
             image_smoothing_quality: ImageSmoothingQuality::default().into(),
         }
     }
@@ -1,4 +1,4 @@
-use skia_safe::{Image, Shader, TileMode};
+use skia_safe::{FilterMode, Image, Picture, Shader, TileMode};
 
 use crate::common::context::filter_quality::FilterQuality;
 use crate::common::context::matrix::Matrix;
@@ -35,9 +35,17 @@ impl Into<i32> for Repetition {
     }
 }
 
+/// What a pattern tiles: a raster image, or a recorded picture that is replayed at the
+/// current scale so it stays sharp when the canvas is zoomed.
+#[derive(Clone)]
+pub enum PatternSource {
+    Image(Image),
+    Picture(Picture),
+}
+
 #[derive(Clone)]
 pub struct Pattern {
-    image: Image,
+    source: PatternSource,
     repetition: Repetition,
     matrix: skia_safe::Matrix,
 }
@@ -47,25 +55,54 @@ impl Pattern {
         pattern: &Pattern,
         image_smoothing_quality: FilterQuality,
     ) -> Option<Shader> {
-        let mode: (TileMode, TileMode) = match pattern.repetition {
-            Repetition::NoRepeat => (TileMode::Clamp, TileMode::Clamp),
-            Repetition::RepeatX => (TileMode::Repeat, TileMode::Clamp),
-            Repetition::RepeatY => (TileMode::Clamp, TileMode::Repeat),
-            _ => (TileMode::Repeat, TileMode::Repeat),
-        };
-        match pattern
-            .image()
-            .to_shader(Some(mode), image_smoothing_quality, None) {
-            Some(shader) => {
-              Some(shader.with_local_matrix(&pattern.matrix))
+        match &pattern.source {
+            PatternSource::Image(image) => {
+                let mode: (TileMode, TileMode) = match pattern.repetition {
+                    Repetition::NoRepeat => (TileMode::Clamp, TileMode::Clamp),
+                    Repetition::RepeatX => (TileMode::Repeat, TileMode::Clamp),
+                    Repetition::RepeatY => (TileMode::Clamp, TileMode::Repeat),
+                    _ => (TileMode::Repeat, TileMode::Repeat),
+                };
+                match image.to_shader(Some(mode), image_smoothing_quality, None) {
+                    Some(shader) => Some(shader.with_local_matrix(&pattern.matrix)),
+                    None => None,
+                }
+            }
+            PatternSource::Picture(picture) => {
+                // Clamping a picture would smear its edges across the canvas, so the
+                // unrepeated axes are left transparent instead.
+                let mode: (TileMode, TileMode) = match pattern.repetition {
+                    Repetition::NoRepeat => (TileMode::Decal, TileMode::Decal),
+                    Repetition::RepeatX => (TileMode::Repeat, TileMode::Decal),
+                    Repetition::RepeatY => (TileMode::Decal, TileMode::Repeat),
+                    _ => (TileMode::Repeat, TileMode::Repeat),
+                };
+                let bounds = picture.cull_rect();
+                if bounds.is_empty() {
+                    return None;
+                }
+                Some(picture.to_shader(
+                    Some(mode),
+                    FilterMode::Linear,
+                    Some(&pattern.matrix),
+                    Some(&bounds),
+                ))
             }
-            None => None
         }
     }
 
     pub fn new(image: Image, repetition: Repetition) -> Self {
         Self {
-            image,
+            source: PatternSource::Image(image),
+            repetition,
+            matrix: skia_safe::Matrix::default(),
+        }
+    }
+
+    /// Creates a pattern whose tile is the picture's cull rect.
+    pub fn from_picture(picture: Picture, repetition: Repetition) -> Self {
+        Self {
+            source: PatternSource::Picture(picture),
             repetition,
             matrix: skia_safe::Matrix::default(),
         }
@@ -84,8 +121,26 @@ impl Pattern {
         &mut self.matrix
     }
 
-    pub fn image(&self) -> &Image {
-        &self.image
+    pub fn source(&self) -> &PatternSource {
+        &self.source
+    }
+
+    pub fn image(&self) -> Option<&Image> {
+        match &self.source {
+            PatternSource::Image(image) => Some(image),
+            PatternSource::Picture(_) => None,
+        }
+    }
+
+    pub fn picture(&self) -> Option<&Picture> {
+        match &self.source {
+            PatternSource::Picture(picture) => Some(picture),
+            PatternSource::Image(_) => None,
+        }
+    }
+
+    pub fn repetition(&self) -> Repetition {
+        self.repetition
     }
 
     pub fn matrix(&self) -> &skia_safe::Matrix {
@@ -0,0 +1,117 @@
+use skia_safe::{Image, Shader};
+use skia_safe::runtime_effect::{ChildPtr, uniform};
+
+use crate::common::context::fill_and_stroke_styles::paint::PaintStyle;
+use crate::common::context::fill_and_stroke_styles::pattern::{Pattern, Repetition};
+use crate::common::context::filter_quality::FilterQuality;
+use crate::common::context::matrix::Matrix;
+
+/// A fill or stroke style backed by an SkSL shader. Uniforms are kept in the layout the
+/// effect reports and children are resolved to shaders each time the style is applied.
+#[derive(Clone)]
+pub struct RuntimeEffect {
+    effect: skia_safe::RuntimeEffect,
+    uniforms: Vec<u8>,
+    children: Vec<Option<PaintStyle>>,
+    matrix: skia_safe::Matrix,
+}
+
+impl RuntimeEffect {
+    pub fn new(sksl: &str) -> Result<Self, String> {
+        let effect = skia_safe::RuntimeEffect::make_for_shader(sksl, None)?;
+        let uniforms = vec![0u8; effect.uniform_size()];
+        let children = vec![None; effect.children().len()];
+        Ok(Self {
+            effect,
+            uniforms,
+            children,
+            matrix: skia_safe::Matrix::default(),
+        })
+    }
+
+    pub fn source(&self) -> &str {
+        self.effect.source()
+    }
+
+    fn write_uniform(&mut self, name: &str, is_float: bool, bytes: &[u8]) -> bool {
+        let uniform = match self.effect.find_uniform(name) {
+            Some(uniform) => uniform,
+            None => return false,
+        };
+        let float_type = match uniform.ty() {
+            uniform::Type::Float
+            | uniform::Type::Float2
+            | uniform::Type::Float3
+            | uniform::Type::Float4
+            | uniform::Type::Float2x2
+            | uniform::Type::Float3x3
+            | uniform::Type::Float4x4 => true,
+            _ => false,
+        };
+        if float_type != is_float || uniform.size_in_bytes() != bytes.len() {
+            return false;
+        }
+        let offset = uniform.offset();
+        self.uniforms[offset..offset + bytes.len()].copy_from_slice(bytes);
+        true
+    }
+
+    /// Sets a `float`, `vec` or `mat` uniform. Matrices are given in column-major order, the
+    /// same way SkSL lays them out. Returns false when the name or size does not match.
+    pub fn set_uniform(&mut self, name: &str, values: &[f32]) -> bool {
+        let bytes: Vec<u8> = values.iter().flat_map(|v| v.to_ne_bytes()).collect();
+        self.write_uniform(name, true, &bytes)
+    }
+
+    /// Sets an `int` or `ivec` uniform.
+    pub fn set_uniform_int(&mut self, name: &str, values: &[i32]) -> bool {
+        let bytes: Vec<u8> = values.iter().flat_map(|v| v.to_ne_bytes()).collect();
+        self.write_uniform(name, false, &bytes)
+    }
+
+    /// Binds a gradient, pattern, color or another runtime effect to a `shader` child.
+    pub fn set_child(&mut self, name: &str, style: PaintStyle) -> bool {
+        match self.effect.find_child(name) {
+            Some(child) => {
+                let index = child.index();
+                self.children[index] = Some(style);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Binds an image to a `shader` child, sampled without repeating.
+    pub fn set_child_image(&mut self, name: &str, image: Image) -> bool {
+        self.set_child(
+            name,
+            PaintStyle::Pattern(Pattern::new(image, Repetition::NoRepeat)),
+        )
+    }
+
+    pub fn set_transform(&mut self, matrix: &Matrix) {
+        let matrix = matrix.matrix.to_m33();
+        self.matrix.pre_concat(&matrix);
+    }
+
+    pub fn matrix(&self) -> &skia_safe::Matrix {
+        &self.matrix
+    }
+
+    /// Returns `None` if the effect has an unbound child or a child fails to build.
+    pub fn to_shader(
+        effect: &RuntimeEffect,
+        image_smoothing_quality: FilterQuality,
+    ) -> Option<Shader> {
+        let mut children: Vec<ChildPtr> = Vec::with_capacity(effect.children.len());
+        for child in effect.children.iter() {
+            let shader = child.as_ref()?.to_shader(image_smoothing_quality)?;
+            children.push(shader.into());
+        }
+        effect.effect.make_shader(
+            skia_safe::Data::new_copy(&effect.uniforms),
+            &children,
+            Some(&effect.matrix),
+        )
+    }
+}
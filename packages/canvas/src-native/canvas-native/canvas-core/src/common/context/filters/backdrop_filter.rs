@@ -0,0 +1,64 @@
+use std::borrow::BorrowMut;
+
+use skia_safe::{canvas::SaveLayerRec, ClipOp, ImageFilter};
+
+use crate::common::context::Context;
+use crate::common::context::drawing_paths::fill_rule::FillRule;
+use crate::common::context::filters::canvas_filter::CanvasFilter;
+use crate::common::context::filters::css_filter::parse_filter_list;
+use crate::common::context::paths::path::Path;
+
+impl Context {
+    /// Runs a CSS `<filter-value-list>` over what is already drawn inside `path`, or the current
+    /// path, leaving the result in place so that new content can be drawn on top of it. The
+    /// current clip also applies. Returns false if the value does not parse.
+    pub fn apply_backdrop_filter(
+        &mut self,
+        value: &str,
+        path: Option<&mut Path>,
+        fill_rule: FillRule,
+    ) -> bool {
+        let filters = match parse_filter_list(value, self.font_color) {
+            Some(filters) => filters,
+            None => return false,
+        };
+        if let Some(filter) = self.filter_list_to_image_filter(&filters) {
+            self.draw_backdrop(&filter, path, fill_rule);
+        }
+        true
+    }
+
+    /// Like `apply_backdrop_filter` with a filter graph, whose source graphic is the content
+    /// behind the region.
+    pub fn apply_backdrop_canvas_filter(
+        &mut self,
+        filter: &CanvasFilter,
+        path: Option<&mut Path>,
+        fill_rule: FillRule,
+    ) {
+        self.draw_backdrop(filter.image_filter(), path, fill_rule);
+    }
+
+    fn draw_backdrop(&mut self, filter: &ImageFilter, path: Option<&mut Path>, fill_rule: FillRule) {
+        let mut path = path.unwrap_or(self.path.borrow_mut()).clone();
+
+        if self.enable_scaling {
+            let scale = self.device.density;
+            path = path.make_scale((scale, scale));
+        }
+
+        path.set_fill_type(fill_rule);
+        let bounds = *path.path().bounds();
+        if bounds.is_empty() {
+            return;
+        }
+
+        // an empty layer restored straight away leaves only the filtered backdrop behind
+        let canvas = self.surface.canvas();
+        canvas.save();
+        canvas.clip_path(path.path(), Some(ClipOp::Intersect), Some(true));
+        canvas.save_layer(&SaveLayerRec::default().bounds(&bounds).backdrop(filter));
+        canvas.restore();
+        canvas.restore();
+    }
+}
@@ -0,0 +1,378 @@
+use skia_safe::{
+    AlphaType, Color, ColorChannel, ColorType, color_filters, Image, image_filters, ImageFilter,
+    ImageInfo, IPoint, Paint, Picture, Point3, Surface, TileMode,
+};
+use skia_safe::image::CachingHint;
+
+use crate::common::context::compositing::composite_operation_type::CompositeOperationType;
+use crate::common::context::fill_and_stroke_styles::paint::PaintStyle;
+use crate::common::context::filter_quality::FilterQuality;
+use crate::common::context::image_asset::ImageAsset;
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum MorphologyOperator {
+    Erode = 0,
+    Dilate = 1,
+}
+
+impl Default for MorphologyOperator {
+    fn default() -> Self {
+        Self::Erode
+    }
+}
+
+impl From<i32> for MorphologyOperator {
+    fn from(value: i32) -> Self {
+        match value {
+            1 => Self::Dilate,
+            _ => Self::Erode,
+        }
+    }
+}
+
+impl Into<i32> for MorphologyOperator {
+    fn into(self) -> i32 {
+        match self {
+            MorphologyOperator::Erode => 0,
+            MorphologyOperator::Dilate => 1,
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ColorChannelSelector {
+    R = 0,
+    G = 1,
+    B = 2,
+    A = 3,
+}
+
+impl Default for ColorChannelSelector {
+    fn default() -> Self {
+        Self::A
+    }
+}
+
+impl From<i32> for ColorChannelSelector {
+    fn from(value: i32) -> Self {
+        match value {
+            0 => Self::R,
+            1 => Self::G,
+            2 => Self::B,
+            _ => Self::A,
+        }
+    }
+}
+
+impl Into<i32> for ColorChannelSelector {
+    fn into(self) -> i32 {
+        match self {
+            ColorChannelSelector::R => 0,
+            ColorChannelSelector::G => 1,
+            ColorChannelSelector::B => 2,
+            ColorChannelSelector::A => 3,
+        }
+    }
+}
+
+impl ColorChannelSelector {
+    fn to_skia(&self) -> ColorChannel {
+        match self {
+            ColorChannelSelector::R => ColorChannel::R,
+            ColorChannelSelector::G => ColorChannel::G,
+            ColorChannelSelector::B => ColorChannel::B,
+            ColorChannelSelector::A => ColorChannel::A,
+        }
+    }
+}
+
+/// Light sources for the lighting filters, angles in degrees.
+#[derive(Copy, Clone, Debug)]
+pub enum LightSource {
+    Distant {
+        azimuth: f32,
+        elevation: f32,
+    },
+    Point {
+        location: Point3,
+    },
+    Spot {
+        location: Point3,
+        target: Point3,
+        specular_exponent: f32,
+        limiting_cone_angle: f32,
+    },
+}
+
+#[derive(Copy, Clone, Debug)]
+pub enum Lighting {
+    Diffuse { constant: f32 },
+    Specular { constant: f32, exponent: f32 },
+}
+
+/// A node in an image filter graph. Nodes are immutable and can feed any number of other
+/// nodes, so a graph is built bottom up by passing nodes as inputs. A missing input reads the
+/// source graphic that the filter is applied to.
+#[derive(Clone)]
+pub struct CanvasFilter {
+    filter: ImageFilter,
+}
+
+impl CanvasFilter {
+    fn from_filter(filter: Option<ImageFilter>) -> Option<Self> {
+        filter.map(|filter| Self { filter })
+    }
+
+    fn input(input: Option<&CanvasFilter>) -> Option<ImageFilter> {
+        input.map(|input| input.filter.clone())
+    }
+
+    pub fn image_filter(&self) -> &ImageFilter {
+        &self.filter
+    }
+
+    /// Passes the source graphic through unchanged.
+    pub fn source() -> Option<Self> {
+        Self::from_filter(image_filters::offset((0.0, 0.0), None, None))
+    }
+
+    pub fn blur(sigma_x: f32, sigma_y: f32, input: Option<&CanvasFilter>) -> Option<Self> {
+        if sigma_x < 0.0 || sigma_y < 0.0 {
+            return None;
+        }
+        Self::from_filter(image_filters::blur(
+            (sigma_x, sigma_y),
+            TileMode::Decal,
+            Self::input(input),
+            None,
+        ))
+    }
+
+    /// `matrix` is a row major 4x5 matrix with offsets in the 0 to 1 range.
+    pub fn color_matrix(matrix: &[f32; 20], input: Option<&CanvasFilter>) -> Option<Self> {
+        Self::from_filter(image_filters::color_filter(
+            color_filters::matrix_row_major(matrix),
+            Self::input(input),
+            None,
+        ))
+    }
+
+    pub fn offset(dx: f32, dy: f32, input: Option<&CanvasFilter>) -> Option<Self> {
+        Self::from_filter(image_filters::offset((dx, dy), Self::input(input), None))
+    }
+
+    pub fn morphology(
+        operator: MorphologyOperator,
+        radius_x: f32,
+        radius_y: f32,
+        input: Option<&CanvasFilter>,
+    ) -> Option<Self> {
+        let radius = (radius_x.max(0.0), radius_y.max(0.0));
+        Self::from_filter(match operator {
+            MorphologyOperator::Erode => image_filters::erode(radius, Self::input(input), None),
+            MorphologyOperator::Dilate => image_filters::dilate(radius, Self::input(input), None),
+        })
+    }
+
+    /// Moves the pixels of `color` by the channels of `displacement` scaled by `scale`.
+    pub fn displacement_map(
+        x_channel: ColorChannelSelector,
+        y_channel: ColorChannelSelector,
+        scale: f32,
+        displacement: Option<&CanvasFilter>,
+        color: Option<&CanvasFilter>,
+    ) -> Option<Self> {
+        let color = match color {
+            Some(color) => color.filter.clone(),
+            None => Self::source()?.filter,
+        };
+        Self::from_filter(image_filters::displacement_map(
+            (x_channel.to_skia(), y_channel.to_skia()),
+            scale,
+            Self::input(displacement),
+            color,
+            None,
+        ))
+    }
+
+    /// Lights the alpha channel of `input` as a height map.
+    pub fn lighting(
+        light: LightSource,
+        lighting: Lighting,
+        light_color: Color,
+        surface_scale: f32,
+        input: Option<&CanvasFilter>,
+    ) -> Option<Self> {
+        let input = Self::input(input);
+        let filter = match (light, lighting) {
+            (LightSource::Distant { azimuth, elevation }, lighting) => {
+                let (azimuth, elevation) = (azimuth.to_radians(), elevation.to_radians());
+                let direction = Point3::new(
+                    azimuth.cos() * elevation.cos(),
+                    azimuth.sin() * elevation.cos(),
+                    elevation.sin(),
+                );
+                match lighting {
+                    Lighting::Diffuse { constant } => image_filters::distant_lit_diffuse(
+                        direction,
+                        light_color,
+                        surface_scale,
+                        constant,
+                        input,
+                        None,
+                    ),
+                    Lighting::Specular { constant, exponent } => {
+                        image_filters::distant_lit_specular(
+                            direction,
+                            light_color,
+                            surface_scale,
+                            constant,
+                            exponent,
+                            input,
+                            None,
+                        )
+                    }
+                }
+            }
+            (LightSource::Point { location }, Lighting::Diffuse { constant }) => {
+                image_filters::point_lit_diffuse(
+                    location,
+                    light_color,
+                    surface_scale,
+                    constant,
+                    input,
+                    None,
+                )
+            }
+            (LightSource::Point { location }, Lighting::Specular { constant, exponent }) => {
+                image_filters::point_lit_specular(
+                    location,
+                    light_color,
+                    surface_scale,
+                    constant,
+                    exponent,
+                    input,
+                    None,
+                )
+            }
+            (
+                LightSource::Spot {
+                    location,
+                    target,
+                    specular_exponent,
+                    limiting_cone_angle,
+                },
+                lighting,
+            ) => match lighting {
+                Lighting::Diffuse { constant } => image_filters::spot_lit_diffuse(
+                    location,
+                    target,
+                    specular_exponent,
+                    limiting_cone_angle,
+                    light_color,
+                    surface_scale,
+                    constant,
+                    input,
+                    None,
+                ),
+                Lighting::Specular { constant, exponent } => image_filters::spot_lit_specular(
+                    location,
+                    target,
+                    specular_exponent,
+                    limiting_cone_angle,
+                    light_color,
+                    surface_scale,
+                    constant,
+                    exponent,
+                    input,
+                    None,
+                ),
+            },
+        };
+        Self::from_filter(filter)
+    }
+
+    /// Draws `foreground` over `background` with `mode`.
+    pub fn blend(
+        mode: CompositeOperationType,
+        background: Option<&CanvasFilter>,
+        foreground: Option<&CanvasFilter>,
+    ) -> Option<Self> {
+        Self::from_filter(image_filters::blend(
+            mode.get_blend_mode(),
+            Self::input(background),
+            Self::input(foreground),
+            None,
+        ))
+    }
+
+    /// Draws the inputs over each other in order.
+    pub fn merge(inputs: &[Option<&CanvasFilter>]) -> Option<Self> {
+        Self::from_filter(image_filters::merge(
+            inputs.iter().map(|input| Self::input(*input)),
+            None,
+        ))
+    }
+
+    /// Fills the filter region with a color, gradient, pattern or runtime effect.
+    pub fn shader(style: &PaintStyle, image_smoothing_quality: FilterQuality) -> Option<Self> {
+        Self::from_filter(image_filters::shader(
+            style.to_shader(image_smoothing_quality)?,
+            None,
+        ))
+    }
+
+    /// Draws a recorded picture, ignoring the source graphic.
+    pub fn picture(picture: Picture) -> Option<Self> {
+        Self::from_filter(image_filters::picture(picture, None))
+    }
+
+    /// Applies `outer` to the result of `inner`.
+    pub fn compose(outer: &CanvasFilter, inner: &CanvasFilter) -> Option<Self> {
+        Self::from_filter(image_filters::compose(
+            outer.filter.clone(),
+            inner.filter.clone(),
+        ))
+    }
+
+    /// Runs the filter over `image` and returns the result clipped to the image bounds.
+    pub fn apply_to_image(&self, image: &Image) -> Option<Image> {
+        let info = ImageInfo::new(image.dimensions(), ColorType::RGBA8888, AlphaType::Premul, None);
+        let mut surface = Surface::new_raster(&info, None, None)?;
+        let mut paint = Paint::default();
+        paint.set_image_filter(self.filter.clone());
+        surface.canvas().draw_image(image, (0.0, 0.0), Some(&paint));
+        Some(surface.image_snapshot())
+    }
+
+    /// Runs the filter over the pixels of `asset` and returns them in a new asset.
+    pub fn apply_to_asset(&self, asset: &ImageAsset) -> Option<ImageAsset> {
+        let image = self.apply_to_image(&asset.skia_image()?)?;
+        let mut info = ImageInfo::new(
+            image.dimensions(),
+            ColorType::RGBA8888,
+            AlphaType::Unpremul,
+            None,
+        );
+        let row_bytes = info.width() as usize * 4;
+        let mut pixels = vec![0u8; row_bytes * info.height() as usize];
+        if !image.read_pixels(
+            &mut info,
+            pixels.as_mut_slice(),
+            row_bytes,
+            IPoint::new(0, 0),
+            CachingHint::Allow,
+        ) {
+            return None;
+        }
+        let mut output = ImageAsset::new();
+        let (width, height) = (info.width(), info.height());
+        if output.load_from_bytes_graphics(pixels, width, height, 4) {
+            Some(output)
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,624 @@
+use skia_safe::Color;
+
+use crate::common::context::Device;
+use crate::common::utils::color::{parse_color_with_current_color, to_parsed_color};
+use crate::common::utils::dimensions::length_to_px;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub(crate) enum Token<'a> {
+    Ident(&'a str),
+    Function(&'a str),
+    Url(&'a str),
+    String(&'a str),
+    Number(f32),
+    Percentage(f32),
+    Dimension(f32, &'a str),
+    Hash(&'a str),
+    Comma,
+    CloseParen,
+    Whitespace,
+    Delim(char),
+}
+
+/// A token together with the source text it covers. For functions the span runs up to and
+/// including the matching close paren.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct Component<'a> {
+    pub(crate) token: Token<'a>,
+    pub(crate) source: &'a str,
+}
+
+pub(crate) struct Tokenizer<'a> {
+    input: &'a str,
+    position: usize,
+}
+
+impl<'a> Tokenizer<'a> {
+    pub(crate) fn new(input: &'a str) -> Self {
+        Self { input, position: 0 }
+    }
+
+    fn peek(&self, offset: usize) -> Option<u8> {
+        self.input.as_bytes().get(self.position + offset).copied()
+    }
+
+    fn is_name_start(c: u8) -> bool {
+        c.is_ascii_alphabetic() || c == b'_' || c >= 0x80
+    }
+
+    fn is_name(c: u8) -> bool {
+        Self::is_name_start(c) || c.is_ascii_digit() || c == b'-'
+    }
+
+    fn starts_identifier(&self, offset: usize) -> bool {
+        match self.peek(offset) {
+            Some(b'-') => match self.peek(offset + 1) {
+                Some(c) => Self::is_name_start(c) || c == b'-',
+                None => false,
+            },
+            Some(c) => Self::is_name_start(c),
+            None => false,
+        }
+    }
+
+    fn starts_number(&self) -> bool {
+        let digit = |offset: usize| self.peek(offset).map_or(false, |c| c.is_ascii_digit());
+        match self.peek(0) {
+            Some(b'+') | Some(b'-') => {
+                digit(1) || (self.peek(1) == Some(b'.') && digit(2))
+            }
+            Some(b'.') => digit(1),
+            Some(c) => c.is_ascii_digit(),
+            None => false,
+        }
+    }
+
+    fn consume_name(&mut self) -> &'a str {
+        let start = self.position;
+        while self.peek(0).map_or(false, Self::is_name) {
+            self.position += 1;
+        }
+        &self.input[start..self.position]
+    }
+
+    fn consume_digits(&mut self) {
+        while self.peek(0).map_or(false, |c| c.is_ascii_digit()) {
+            self.position += 1;
+        }
+    }
+
+    fn consume_number(&mut self) -> Option<f32> {
+        let start = self.position;
+        if let Some(b'+') | Some(b'-') = self.peek(0) {
+            self.position += 1;
+        }
+        self.consume_digits();
+        if self.peek(0) == Some(b'.') && self.peek(1).map_or(false, |c| c.is_ascii_digit()) {
+            self.position += 1;
+            self.consume_digits();
+        }
+        if let Some(b'e') | Some(b'E') = self.peek(0) {
+            let sign = match self.peek(1) {
+                Some(b'+') | Some(b'-') => 1,
+                _ => 0,
+            };
+            if self.peek(1 + sign).map_or(false, |c| c.is_ascii_digit()) {
+                self.position += 1 + sign;
+                self.consume_digits();
+            }
+        }
+        self.input[start..self.position].parse::<f32>().ok()
+    }
+
+    fn next(&mut self) -> Option<Result<Token<'a>, ()>> {
+        let c = self.peek(0)?;
+        if c.is_ascii_whitespace() {
+            while self.peek(0).map_or(false, |c| c.is_ascii_whitespace()) {
+                self.position += 1;
+            }
+            return Some(Ok(Token::Whitespace));
+        }
+        if self.starts_number() {
+            let value = match self.consume_number() {
+                Some(value) => value,
+                None => return Some(Err(())),
+            };
+            if self.peek(0) == Some(b'%') {
+                self.position += 1;
+                return Some(Ok(Token::Percentage(value)));
+            }
+            if self.starts_identifier(0) {
+                return Some(Ok(Token::Dimension(value, self.consume_name())));
+            }
+            return Some(Ok(Token::Number(value)));
+        }
+        if self.starts_identifier(0) {
+            let name = self.consume_name();
+            if self.peek(0) != Some(b'(') {
+                return Some(Ok(Token::Ident(name)));
+            }
+            self.position += 1;
+            if name.eq_ignore_ascii_case("url") {
+                return Some(self.consume_url());
+            }
+            return Some(Ok(Token::Function(name)));
+        }
+        self.position += 1;
+        let token = match c {
+            b'"' | b'\'' => return Some(self.consume_string(c)),
+            b'#' => {
+                if !self.peek(0).map_or(false, Self::is_name) {
+                    return Some(Err(()));
+                }
+                Token::Hash(self.consume_name())
+            }
+            b',' => Token::Comma,
+            b')' => Token::CloseParen,
+            // blocks other than function arguments have no meaning in a filter list
+            b'(' | b'[' | b']' | b'{' | b'}' | b';' => return Some(Err(())),
+            _ => match self.input[self.position - 1..].chars().next() {
+                Some(c) => {
+                    self.position += c.len_utf8() - 1;
+                    Token::Delim(c)
+                }
+                None => return Some(Err(())),
+            },
+        };
+        Some(Ok(token))
+    }
+
+    fn consume_string(&mut self, quote: u8) -> Result<Token<'a>, ()> {
+        let start = self.position;
+        loop {
+            match self.peek(0) {
+                Some(c) if c == quote => {
+                    let value = &self.input[start..self.position];
+                    self.position += 1;
+                    return Ok(Token::String(value));
+                }
+                Some(b'\n') | Some(b'\\') | None => return Err(()),
+                Some(_) => self.position += 1,
+            }
+        }
+    }
+
+    /// Consumes the rest of `url(`, either a quoted string or raw contents.
+    fn consume_url(&mut self) -> Result<Token<'a>, ()> {
+        let skip_whitespace = |tokenizer: &mut Self| {
+            while tokenizer.peek(0).map_or(false, |c| c.is_ascii_whitespace()) {
+                tokenizer.position += 1;
+            }
+        };
+        skip_whitespace(self);
+        let value = match self.peek(0) {
+            Some(quote @ b'"') | Some(quote @ b'\'') => {
+                self.position += 1;
+                match self.consume_string(quote)? {
+                    Token::String(value) => value,
+                    _ => return Err(()),
+                }
+            }
+            _ => {
+                let start = self.position;
+                while let Some(c) = self.peek(0) {
+                    if c == b')' || c.is_ascii_whitespace() {
+                        break;
+                    }
+                    if c == b'"' || c == b'\'' || c == b'(' || c == b'\\' {
+                        return Err(());
+                    }
+                    self.position += 1;
+                }
+                &self.input[start..self.position]
+            }
+        };
+        skip_whitespace(self);
+        if self.peek(0) != Some(b')') {
+            return Err(());
+        }
+        self.position += 1;
+        Ok(Token::Url(value))
+    }
+
+    /// Reads the next component value, folding a function and its arguments into one.
+    pub(crate) fn next_component(&mut self) -> Option<Result<Component<'a>, ()>> {
+        let start = self.position;
+        let token = match self.next()? {
+            Ok(token) => token,
+            Err(_) => return Some(Err(())),
+        };
+        if let Token::Function(_) = token {
+            let mut depth = 1;
+            while depth > 0 {
+                match self.next() {
+                    Some(Ok(Token::Function(_))) => depth += 1,
+                    Some(Ok(Token::CloseParen)) => depth -= 1,
+                    Some(Ok(_)) => {}
+                    Some(Err(_)) | None => return Some(Err(())),
+                }
+            }
+        }
+        Some(Ok(Component {
+            token,
+            source: &self.input[start..self.position],
+        }))
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum LengthUnit {
+    Px,
+    Em,
+    Rem,
+    Cm,
+    Mm,
+    In,
+    Pt,
+    Pc,
+    Vw,
+    Vh,
+}
+
+impl LengthUnit {
+    pub fn from_str(value: &str) -> Option<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "px" => Some(Self::Px),
+            "em" => Some(Self::Em),
+            "rem" => Some(Self::Rem),
+            "cm" => Some(Self::Cm),
+            "mm" => Some(Self::Mm),
+            "in" => Some(Self::In),
+            "pt" => Some(Self::Pt),
+            "pc" => Some(Self::Pc),
+            "vw" => Some(Self::Vw),
+            "vh" => Some(Self::Vh),
+            _ => None,
+        }
+    }
+
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            LengthUnit::Px => "px",
+            LengthUnit::Em => "em",
+            LengthUnit::Rem => "rem",
+            LengthUnit::Cm => "cm",
+            LengthUnit::Mm => "mm",
+            LengthUnit::In => "in",
+            LengthUnit::Pt => "pt",
+            LengthUnit::Pc => "pc",
+            LengthUnit::Vw => "vw",
+            LengthUnit::Vh => "vh",
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Length {
+    pub value: f32,
+    pub unit: LengthUnit,
+}
+
+impl Length {
+    pub fn px(value: f32) -> Self {
+        Self {
+            value,
+            unit: LengthUnit::Px,
+        }
+    }
+
+    /// Resolves to CSS pixels, `em` against `font_size` and physical and viewport units against
+    /// the device.
+    pub fn to_px(&self, font_size: f32, device: Device) -> f32 {
+        let value = self.value;
+        match self.unit {
+            LengthUnit::Em => value * font_size,
+            unit => length_to_px(value, unit, device),
+        }
+    }
+
+    fn serialize(&self) -> String {
+        format!("{}{}", self.value, self.unit.to_str())
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum AngleUnit {
+    Deg,
+    Rad,
+    Grad,
+    Turn,
+}
+
+impl AngleUnit {
+    pub fn from_str(value: &str) -> Option<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "deg" => Some(Self::Deg),
+            "rad" => Some(Self::Rad),
+            "grad" => Some(Self::Grad),
+            "turn" => Some(Self::Turn),
+            _ => None,
+        }
+    }
+
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            AngleUnit::Deg => "deg",
+            AngleUnit::Rad => "rad",
+            AngleUnit::Grad => "grad",
+            AngleUnit::Turn => "turn",
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Angle {
+    pub value: f32,
+    pub unit: AngleUnit,
+}
+
+impl Angle {
+    pub fn to_degrees(&self) -> f32 {
+        match self.unit {
+            AngleUnit::Deg => self.value,
+            AngleUnit::Rad => self.value.to_degrees(),
+            AngleUnit::Grad => self.value * 0.9,
+            AngleUnit::Turn => self.value * 360.0,
+        }
+    }
+
+    fn serialize(&self) -> String {
+        format!("{}{}", self.value, self.unit.to_str())
+    }
+}
+
+/// A `<number>` or `<percentage>` argument, kept as written so it serializes the same way.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Amount {
+    pub value: f32,
+    pub percentage: bool,
+}
+
+impl Amount {
+    fn one() -> Self {
+        Self {
+            value: 1.0,
+            percentage: false,
+        }
+    }
+
+    /// The amount as a fraction, 100% being 1.
+    pub fn fraction(&self) -> f32 {
+        if self.percentage {
+            self.value / 100.0
+        } else {
+            self.value
+        }
+    }
+
+    fn serialize(&self) -> String {
+        if self.percentage {
+            format!("{}%", self.value)
+        } else {
+            self.value.to_string()
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum FilterFunction {
+    Blur(Length),
+    Brightness(Amount),
+    Contrast(Amount),
+    Grayscale(Amount),
+    Invert(Amount),
+    Opacity(Amount),
+    Saturate(Amount),
+    Sepia(Amount),
+    HueRotate(Angle),
+    DropShadow {
+        offset_x: Length,
+        offset_y: Length,
+        blur: Length,
+        color: Color,
+    },
+    Url(String),
+}
+
+impl FilterFunction {
+    pub fn serialize(&self) -> String {
+        match self {
+            FilterFunction::Blur(length) => format!("blur({})", length.serialize()),
+            FilterFunction::Brightness(amount) => format!("brightness({})", amount.serialize()),
+            FilterFunction::Contrast(amount) => format!("contrast({})", amount.serialize()),
+            FilterFunction::Grayscale(amount) => format!("grayscale({})", amount.serialize()),
+            FilterFunction::Invert(amount) => format!("invert({})", amount.serialize()),
+            FilterFunction::Opacity(amount) => format!("opacity({})", amount.serialize()),
+            FilterFunction::Saturate(amount) => format!("saturate({})", amount.serialize()),
+            FilterFunction::Sepia(amount) => format!("sepia({})", amount.serialize()),
+            FilterFunction::HueRotate(angle) => format!("hue-rotate({})", angle.serialize()),
+            FilterFunction::DropShadow {
+                offset_x,
+                offset_y,
+                blur,
+                color,
+            } => format!(
+                "drop-shadow({} {} {} {})",
+                to_parsed_color(*color),
+                offset_x.serialize(),
+                offset_y.serialize(),
+                blur.serialize()
+            ),
+            FilterFunction::Url(url) => {
+                // strings cannot hold escapes, so pick the quote the url does not contain
+                let quote = if url.contains('"') { '\'' } else { '"' };
+                format!("url({}{}{})", quote, url, quote)
+            }
+        }
+    }
+}
+
+/// Serializes a parsed filter list, `none` when it is empty.
+pub fn serialize_filter_list(filters: &[FilterFunction]) -> String {
+    if filters.is_empty() {
+        return "none".into();
+    }
+    filters
+        .iter()
+        .map(|filter| filter.serialize())
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// Parses a CSS `<filter-value-list>` or `none`. Returns `None` if any part of the value is
+/// invalid, in which case the whole value must be ignored. `currentcolor` in a drop shadow
+/// resolves to `current_color`, which is also the default shadow color.
+pub fn parse_filter_list(value: &str, current_color: Color) -> Option<Vec<FilterFunction>> {
+    let mut tokenizer = Tokenizer::new(value);
+    let mut components = Vec::new();
+    while let Some(component) = tokenizer.next_component() {
+        let component = component.ok()?;
+        if component.token != Token::Whitespace {
+            components.push(component);
+        }
+    }
+    match components.as_slice() {
+        [] => return None,
+        [Component {
+            token: Token::Ident(ident),
+            ..
+        }] if ident.eq_ignore_ascii_case("none") => return Some(Vec::new()),
+        _ => {}
+    }
+    components
+        .iter()
+        .map(|component| parse_filter_function(component, current_color))
+        .collect()
+}
+
+fn parse_filter_function(component: &Component, current_color: Color) -> Option<FilterFunction> {
+    let name = match component.token {
+        Token::Url(url) => return Some(FilterFunction::Url(url.to_string())),
+        Token::Function(name) => name.to_ascii_lowercase(),
+        _ => return None,
+    };
+    let args = function_arguments(component)?;
+    match name.as_str() {
+        "blur" => match args.as_slice() {
+            [] => Some(FilterFunction::Blur(Length::px(0.0))),
+            [arg] => parse_length(arg)
+                .filter(|length| length.value >= 0.0)
+                .map(FilterFunction::Blur),
+            _ => None,
+        },
+        "brightness" | "contrast" | "grayscale" | "invert" | "opacity" | "saturate"
+        | "sepia" => {
+            let amount = match args.as_slice() {
+                [] => Amount::one(),
+                [arg] => parse_amount(arg).filter(|amount| amount.value >= 0.0)?,
+                _ => return None,
+            };
+            Some(match name.as_str() {
+                "brightness" => FilterFunction::Brightness(amount),
+                "contrast" => FilterFunction::Contrast(amount),
+                "grayscale" => FilterFunction::Grayscale(amount),
+                "invert" => FilterFunction::Invert(amount),
+                "opacity" => FilterFunction::Opacity(amount),
+                "saturate" => FilterFunction::Saturate(amount),
+                _ => FilterFunction::Sepia(amount),
+            })
+        }
+        "hue-rotate" => match args.as_slice() {
+            [] => Some(FilterFunction::HueRotate(Angle {
+                value: 0.0,
+                unit: AngleUnit::Deg,
+            })),
+            [arg] => parse_angle(arg).map(FilterFunction::HueRotate),
+            _ => None,
+        },
+        "drop-shadow" => parse_drop_shadow(&args, current_color),
+        _ => None,
+    }
+}
+
+/// The arguments of a function component with whitespace dropped.
+pub(crate) fn function_arguments<'a>(component: &Component<'a>) -> Option<Vec<Component<'a>>> {
+    let source = component.source;
+    let open = source.find('(')?;
+    // the span always ends with the close paren matching `open`
+    let inner = &source[open + 1..source.len() - 1];
+    let mut tokenizer = Tokenizer::new(inner);
+    let mut args = Vec::new();
+    while let Some(arg) = tokenizer.next_component() {
+        let arg = arg.ok()?;
+        match arg.token {
+            Token::Whitespace => {}
+            Token::CloseParen => return None,
+            _ => args.push(arg),
+        }
+    }
+    Some(args)
+}
+
+fn parse_length(component: &Component) -> Option<Length> {
+    match component.token {
+        Token::Dimension(value, unit) => LengthUnit::from_str(unit).map(|unit| Length { value, unit }),
+        Token::Number(value) if value == 0.0 => Some(Length::px(0.0)),
+        _ => None,
+    }
+}
+
+fn parse_amount(component: &Component) -> Option<Amount> {
+    match component.token {
+        Token::Number(value) => Some(Amount {
+            value,
+            percentage: false,
+        }),
+        Token::Percentage(value) => Some(Amount {
+            value,
+            percentage: true,
+        }),
+        _ => None,
+    }
+}
+
+pub(crate) fn parse_angle(component: &Component) -> Option<Angle> {
+    match component.token {
+        Token::Dimension(value, unit) => AngleUnit::from_str(unit).map(|unit| Angle { value, unit }),
+        Token::Number(value) if value == 0.0 => Some(Angle {
+            value: 0.0,
+            unit: AngleUnit::Deg,
+        }),
+        _ => None,
+    }
+}
+
+/// `drop-shadow( [ <color>? && <length>{2,3} ] )`, the color either before or after the
+/// lengths.
+fn parse_drop_shadow(args: &[Component], current_color: Color) -> Option<FilterFunction> {
+    let (color, lengths) = match args {
+        [first, rest @ ..] if parse_length(first).is_none() => (Some(first), rest),
+        [rest @ .., last] if parse_length(last).is_none() => (Some(last), rest),
+        _ => (None, args),
+    };
+    let lengths: Vec<Length> = lengths.iter().map(parse_length).collect::<Option<_>>()?;
+    let (offset_x, offset_y, blur) = match lengths.as_slice() {
+        [x, y] => (*x, *y, Length::px(0.0)),
+        [x, y, blur] if blur.value >= 0.0 => (*x, *y, *blur),
+        _ => return None,
+    };
+    let color = match color {
+        Some(color) => match color.token {
+            Token::Ident(_) | Token::Hash(_) | Token::Function(_) => {
+                parse_color_with_current_color(color.source, current_color)?
+            }
+            _ => return None,
+        },
+        None => current_color,
+    };
+    Some(FilterFunction::DropShadow {
+        offset_x,
+        offset_y,
+        blur,
+        color,
+    })
+}
@@ -0,0 +1,256 @@
+use std::f32::consts::PI;
+
+use skia_safe::{color_filters, image_filters, ImageFilter, table_color_filter, TileMode};
+
+use crate::common::context::Context;
+use crate::common::context::filters::canvas_filter::CanvasFilter;
+use crate::common::context::filters::css_filter::{
+    FilterFunction, parse_filter_list, serialize_filter_list,
+};
+use crate::common::context::filters::svg_filter::parse_svg_filters;
+
+pub mod backdrop_filter;
+pub mod canvas_filter;
+pub mod css_filter;
+pub mod svg_filter;
+
+pub fn to_radians(degrees: f32) -> f32 {
+    degrees / 180.0 * PI
+}
+
+fn color_matrix_filter(matrix: &[f32; 20], chain: Option<ImageFilter>) -> Option<ImageFilter> {
+    image_filters::color_filter(color_filters::matrix_row_major(matrix), chain, None)
+}
+
+fn table_filter<F: Fn(f32) -> f32>(map: F, chain: Option<ImageFilter>) -> Option<ImageFilter> {
+    let mut ramp = [0u8; 256];
+    for (i, val) in ramp.iter_mut().enumerate() {
+        *val = map(i as f32) as u8;
+    }
+    let table = Some(&ramp);
+    let color_table = table_color_filter::from_argb(None, table, table, table);
+    image_filters::color_filter(color_table, chain, None)
+}
+
+fn saturate_matrix(amt: f32) -> [f32; 20] {
+    [
+        (0.2126 + 0.7874 * amt),
+        (0.7152 - 0.7152 * amt),
+        (0.0722 - 0.0722 * amt),
+        0.0,
+        0.0,
+        (0.2126 - 0.2126 * amt),
+        (0.7152 + 0.2848 * amt),
+        (0.0722 - 0.0722 * amt),
+        0.0,
+        0.0,
+        (0.2126 - 0.2126 * amt),
+        (0.7152 - 0.7152 * amt),
+        (0.0722 + 0.9278 * amt),
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+        1.0,
+        0.0,
+    ]
+}
+
+impl Context {
+    /// Sets the filter from a CSS `<filter-value-list>` or `none`. Values that do not parse
+    /// are ignored and leave the current filter in place.
+    pub fn set_filter(&mut self, value: &str) {
+        let filters = match parse_filter_list(value, self.font_color) {
+            Some(filters) => filters,
+            None => return,
+        };
+        let filter = self.filter_list_to_image_filter(&filters);
+
+        self.state.filter = serialize_filter_list(&filters);
+        self.state.canvas_filter = None;
+        self.set_image_filter(filter);
+    }
+
+    /// Uses a filter graph as the filter, or clears the filter for `None`. A graph has no CSS
+    /// form, so `get_filter` reports `none` while one is set.
+    pub fn set_canvas_filter(&mut self, filter: Option<&CanvasFilter>) {
+        self.state.filter = "none".into();
+        self.state.canvas_filter = filter.cloned();
+        self.set_image_filter(filter.map(|filter| filter.image_filter().clone()));
+    }
+
+    pub fn canvas_filter(&self) -> Option<&CanvasFilter> {
+        self.state.canvas_filter.as_ref()
+    }
+
+    fn set_image_filter(&mut self, filter: Option<ImageFilter>) {
+        self.state
+            .paint
+            .fill_paint_mut()
+            .set_image_filter(filter.clone());
+        self.state
+            .paint
+            .stroke_paint_mut()
+            .set_image_filter(filter.clone());
+        self.state.paint.image_paint_mut().set_image_filter(filter);
+    }
+
+    pub(crate) fn filter_list_to_image_filter(&self, filters: &[FilterFunction]) -> Option<ImageFilter> {
+        let font_size = self.state.font.to_skia().size();
+        filters.iter().fold(None, |chain, next_filter| {
+            self.filter_function_to_image_filter(next_filter, font_size, chain)
+        })
+    }
+
+    fn filter_function_to_image_filter(
+        &self,
+        filter: &FilterFunction,
+        font_size: f32,
+        chain: Option<ImageFilter>,
+    ) -> Option<ImageFilter> {
+        match filter {
+            FilterFunction::Blur(length) => {
+                let value = length.to_px(font_size, self.device);
+                image_filters::blur((value, value), TileMode::Clamp, chain, None)
+            }
+            FilterFunction::Brightness(amount) => {
+                let amt = amount.fraction();
+                color_matrix_filter(
+                    &[
+                        amt, 0.0, 0.0, 0.0, 0.0, 0.0, amt, 0.0, 0.0, 0.0, 0.0, 0.0, amt, 0.0, 0.0,
+                        0.0, 0.0, 0.0, 1.0, 0.0,
+                    ],
+                    chain,
+                )
+            }
+            FilterFunction::Contrast(amount) => {
+                let amt = amount.fraction();
+                table_filter(|orig| 127.0 + amt * orig - (127.0 * amt), chain)
+            }
+            FilterFunction::Grayscale(amount) => {
+                let amt = 1.0 - amount.fraction().min(1.0);
+                color_matrix_filter(&saturate_matrix(amt), chain)
+            }
+            FilterFunction::Invert(amount) => {
+                let amt = amount.fraction().min(1.0);
+                table_filter(|orig| orig * (1.0 - amt) + (255.0 - orig) * amt, chain)
+            }
+            FilterFunction::Opacity(amount) => {
+                let amt = amount.fraction().min(1.0);
+                color_matrix_filter(
+                    &[
+                        1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0,
+                        0.0, 0.0, 0.0, amt, 0.0,
+                    ],
+                    chain,
+                )
+            }
+            FilterFunction::Saturate(amount) => {
+                color_matrix_filter(&saturate_matrix(amount.fraction()), chain)
+            }
+            FilterFunction::Sepia(amount) => {
+                let amt = 1.0 - amount.fraction().min(1.0);
+                color_matrix_filter(
+                    &[
+                        (0.393 + 0.607 * amt),
+                        (0.769 - 0.769 * amt),
+                        (0.189 - 0.189 * amt),
+                        0.0,
+                        0.0,
+                        (0.349 - 0.349 * amt),
+                        (0.686 + 0.314 * amt),
+                        (0.168 - 0.168 * amt),
+                        0.0,
+                        0.0,
+                        (0.272 - 0.272 * amt),
+                        (0.534 - 0.534 * amt),
+                        (0.131 + 0.869 * amt),
+                        0.0,
+                        0.0,
+                        0.0,
+                        0.0,
+                        0.0,
+                        1.0,
+                        0.0,
+                    ],
+                    chain,
+                )
+            }
+            FilterFunction::HueRotate(angle) => {
+                let radians = to_radians(angle.to_degrees());
+                let cos = radians.cos();
+                let sin = radians.sin();
+                color_matrix_filter(
+                    &[
+                        (0.213 + cos * 0.787 - sin * 0.213),
+                        (0.715 - cos * 0.715 - sin * 0.715),
+                        (0.072 - cos * 0.072 + sin * 0.928),
+                        0.0,
+                        0.0,
+                        (0.213 - cos * 0.213 + sin * 0.143),
+                        (0.715 + cos * 0.285 + sin * 0.140),
+                        (0.072 - cos * 0.072 - sin * 0.283),
+                        0.0,
+                        0.0,
+                        (0.213 - cos * 0.213 - sin * 0.787),
+                        (0.715 - cos * 0.715 + sin * 0.715),
+                        (0.072 + cos * 0.928 + sin * 0.072),
+                        0.0,
+                        0.0,
+                        0.0,
+                        0.0,
+                        0.0,
+                        1.0,
+                        0.0,
+                    ],
+                    chain,
+                )
+            }
+            FilterFunction::DropShadow {
+                offset_x,
+                offset_y,
+                blur,
+                color,
+            } => {
+                let offset = (
+                    offset_x.to_px(font_size, self.device),
+                    offset_y.to_px(font_size, self.device),
+                );
+                let sigma = blur.to_px(font_size, self.device) / 2.0;
+                image_filters::drop_shadow(offset, (sigma, sigma), *color, chain, None)
+            }
+            FilterFunction::Url(url) => {
+                let id = match url.rfind('#') {
+                    Some(index) => &url[index + 1..],
+                    None => url.as_str(),
+                };
+                match (self.svg_filters.get(id), chain) {
+                    (Some(filter), Some(chain)) => image_filters::compose(filter.clone(), chain),
+                    (Some(filter), None) => Some(filter.clone()),
+                    // references to unknown filters are ignored
+                    (None, chain) => chain,
+                }
+            }
+        }
+    }
+
+    /// Compiles every `<filter>` with an `id` in an SVG document so that `url(#id)` can refer
+    /// to it, replacing filters registered earlier under the same id. Filters that are already
+    /// set keep using the previous definition until the filter is set again. Returns the number
+    /// of filters registered.
+    pub fn register_svg_filters(&mut self, svg: &str) -> Result<usize, String> {
+        let filters = parse_svg_filters(svg)?;
+        let count = filters.len();
+        self.svg_filters.extend(filters);
+        Ok(count)
+    }
+
+    pub fn clear_svg_filters(&mut self) {
+        self.svg_filters.clear();
+    }
+
+    pub fn get_filter(&self) -> &str {
+        &self.state.filter
+    }
+}
@@ -1,11 +1,14 @@
 use std::os::raw::c_float;
 
-use skia_safe::{Image, Point, TileMode};
+use skia_safe::{Canvas, Color, Image, Picture, PictureRecorder, Point, Rect, Size, TileMode};
 
 use crate::common::context::Context;
 use crate::common::context::fill_and_stroke_styles::gradient::Gradient;
+use crate::common::context::fill_and_stroke_styles::gradient_interpolation::GradientInterpolation;
 use crate::common::context::fill_and_stroke_styles::pattern::{Pattern, Repetition};
+use crate::common::context::fill_and_stroke_styles::runtime_effect::RuntimeEffect;
 use crate::common::context::matrix::Matrix;
+use crate::common::context::paths::path::Path;
 
 impl Context {
     pub fn create_linear_gradient(
@@ -22,6 +25,7 @@ impl Context {
             stops: Vec::new(),
             matrix: None,
             tile_mode: TileMode::Clamp,
+            interpolation: GradientInterpolation::default(),
         }
     }
 
@@ -40,6 +44,7 @@ impl Context {
             stops: Vec::new(),
             matrix: Some(matrix),
             tile_mode: TileMode::Clamp,
+            interpolation: GradientInterpolation::default(),
         }
     }
 
@@ -57,6 +62,7 @@ impl Context {
             stops: Vec::new(),
             matrix: None,
             tile_mode: TileMode::Clamp,
+            interpolation: GradientInterpolation::default(),
         }
     }
 
@@ -75,13 +81,106 @@ impl Context {
             stops: Vec::new(),
             matrix: Some(matrix),
             tile_mode: TileMode::Clamp,
+            interpolation: GradientInterpolation::default(),
         }
     }
 
+    /// Creates a Coons patch gradient from twelve control points, clockwise from the top left
+    /// corner, and the colors of the top left, top right, bottom right and bottom left corners.
+    pub fn create_mesh_gradient(&self, cubics: [Point; 12], colors: [Color; 4]) -> Gradient {
+        Gradient::Mesh {
+            cubics,
+            colors,
+            matrix: None,
+            tile_mode: TileMode::Decal,
+        }
+    }
+
+    /// Creates a bilinear gradient over the rectangle, with transparent black corners until
+    /// they are set with `Gradient::set_corner_color`.
+    pub fn create_four_corner_gradient(
+        &self,
+        x: c_float,
+        y: c_float,
+        width: c_float,
+        height: c_float,
+    ) -> Gradient {
+        self.create_mesh_gradient(
+            Gradient::rect_cubics(&Rect::from_xywh(x, y, width, height)),
+            [Color::TRANSPARENT; 4],
+        )
+    }
+
     pub fn create_pattern(&self, image: Image, rep: Repetition) -> Pattern {
         Pattern::new(image, rep)
     }
 
+    /// Creates a pattern that replays `picture` instead of sampling pixels, so it stays sharp
+    /// under any transform. The picture's cull rect is the tile.
+    pub fn create_pattern_from_picture(&self, picture: Picture, rep: Repetition) -> Pattern {
+        Pattern::from_picture(picture, rep)
+    }
+
+    /// Creates a picture pattern from an SVG document laid out in a `width` by `height` tile.
+    pub fn create_pattern_from_svg(
+        &self,
+        svg: &str,
+        width: c_float,
+        height: c_float,
+        rep: Repetition,
+    ) -> Option<Pattern> {
+        let mut svg = match skia_safe::svg::Dom::from_bytes(svg.as_bytes()) {
+            Ok(svg) => svg,
+            Err(e) => {
+                log::debug!("svg parse error: {}", e);
+                return None;
+            }
+        };
+        svg.set_container_size(Size::new(width, height));
+        Context::record_pattern_tile(width, height, |canvas| svg.render(canvas))
+            .map(|picture| Pattern::from_picture(picture, rep))
+    }
+
+    /// Creates a picture pattern from `path` drawn with the current fill or stroke paint in a
+    /// `width` by `height` tile.
+    pub fn create_pattern_from_path(
+        &self,
+        path: &Path,
+        is_fill: bool,
+        width: c_float,
+        height: c_float,
+        rep: Repetition,
+    ) -> Option<Pattern> {
+        let paint = if is_fill {
+            self.state.paint.fill_paint()
+        } else {
+            self.state.paint.stroke_paint()
+        };
+        Context::record_pattern_tile(width, height, |canvas| {
+            canvas.draw_path(path.path(), paint);
+        })
+        .map(|picture| Pattern::from_picture(picture, rep))
+    }
+
+    fn record_pattern_tile<F: FnOnce(&mut Canvas)>(
+        width: c_float,
+        height: c_float,
+        draw: F,
+    ) -> Option<Picture> {
+        if !(width > 0.0 && height > 0.0) {
+            return None;
+        }
+        let bounds = Rect::from_wh(width, height);
+        let mut recorder = PictureRecorder::new();
+        draw(recorder.begin_recording(bounds, None));
+        recorder.finish_recording_as_picture(Some(&bounds))
+    }
+
+    /// Compiles `sksl` into a shader style. The error holds the SkSL compiler output.
+    pub fn create_runtime_effect(&self, sksl: &str) -> Result<RuntimeEffect, String> {
+        RuntimeEffect::new(sksl)
+    }
+
     pub fn create_radial_gradient(
         &self,
         x0: c_float,
@@ -100,6 +199,7 @@ impl Context {
             colors: Vec::new(),
             matrix: None,
             tile_mode: TileMode::Clamp,
+            interpolation: GradientInterpolation::default(),
         }
     }
 
@@ -122,6 +222,7 @@ impl Context {
             colors: Vec::new(),
             matrix: Some(matrix),
             tile_mode: TileMode::Clamp,
+            interpolation: GradientInterpolation::default(),
         }
     }
 }
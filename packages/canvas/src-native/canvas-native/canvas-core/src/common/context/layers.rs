@@ -0,0 +1,105 @@
+use skia_safe::{canvas::SaveLayerRec, Color4f, image_filters, Paint};
+
+use crate::common::context::compositing::composite_operation_type::CompositeOperationType;
+use crate::common::context::Context;
+use crate::common::context::fill_and_stroke_styles::paint;
+use crate::common::context::filters::css_filter::parse_filter_list;
+
+#[derive(Clone, Debug)]
+pub struct LayerOptions {
+    /// Multiplied with the global alpha when the layer is composited.
+    pub alpha: f32,
+    /// A CSS `<filter-value-list>` used instead of the current filter.
+    pub filter: Option<String>,
+    /// Used instead of the current composite operation.
+    pub composite_operation: Option<CompositeOperationType>,
+}
+
+impl Default for LayerOptions {
+    fn default() -> Self {
+        Self {
+            alpha: 1.0,
+            filter: None,
+            composite_operation: None,
+        }
+    }
+}
+
+impl Context {
+    /// Starts drawing into an offscreen layer that is composited as a single image by
+    /// `end_layer`, so overlapping shapes inside it do not show through each other. The global
+    /// alpha, composite operation, filter and shadow in effect are applied to the layer as a
+    /// whole and reset to their defaults inside it. Like `save`, the drawing state is restored by
+    /// `end_layer`.
+    pub fn begin_layer(&mut self, options: &LayerOptions) {
+        let filter = match &options.filter {
+            Some(value) => match parse_filter_list(value, self.font_color) {
+                Some(filters) => self.filter_list_to_image_filter(&filters),
+                None => self.state.paint.fill_paint().image_filter(),
+            },
+            None => self.state.paint.fill_paint().image_filter(),
+        };
+
+        let filter = if self.shadow_visible() {
+            let sigma = self.state.shadow_blur / 2.0;
+            let shadow = paint::Paint::shadow_filter(
+                self.state.shadow_offset,
+                (sigma, sigma),
+                self.state.shadow_color,
+                filter.clone(),
+            );
+            image_filters::merge([shadow, filter], None)
+        } else {
+            filter
+        };
+
+        let mut paint = Paint::default();
+        paint.set_alpha_f(self.state.global_alpha * options.alpha.max(0.0).min(1.0));
+        match options.composite_operation {
+            Some(operation) => {
+                paint.set_blend_mode(operation.get_blend_mode());
+            }
+            None => {
+                if let Some(blender) = self.state.paint.fill_paint().blender() {
+                    paint.set_blender(blender);
+                }
+            }
+        }
+        paint.set_image_filter(filter);
+
+        let mut saved = self.state.clone();
+        saved.is_layer = true;
+        saved.transform = self.surface.canvas().local_to_device();
+        self.surface
+            .canvas()
+            .save_layer(&SaveLayerRec::default().paint(&paint));
+        self.state_stack.push(saved);
+        self.state.clip_paths.clear();
+
+        self.set_global_alpha(1.0);
+        self.set_global_composite_operation(CompositeOperationType::SourceOver);
+        self.set_filter("none");
+        self.state.shadow_color = Color4f::TRANSPARENT;
+        self.state.shadow_blur = 0.0;
+        self.state.shadow_offset = (0.0, 0.0).into();
+    }
+
+    /// Composites the innermost layer, after applying its mask if it has one, and restores the
+    /// drawing state from before `begin_layer`. Returns false, doing nothing, if there is no open
+    /// layer or a `save` inside it has not been restored.
+    pub fn end_layer(&mut self) -> bool {
+        match self.state_stack.last() {
+            Some(state) if state.is_layer => {}
+            _ => return false,
+        }
+        if let Some(mut state) = self.state_stack.pop() {
+            if let Some(mask) = state.layer_mask.take() {
+                mask.apply(self.surface.canvas());
+            }
+            self.surface.canvas().restore();
+            state.is_layer = false;
+            self.state = state;
+        }
+        true
+    }
+}
@@ -0,0 +1,167 @@
+use std::borrow::BorrowMut;
+
+use skia_safe::{
+    BlendMode, BlurStyle, canvas::SaveLayerRec, luma_color_filter, M44, MaskFilter, Matrix,
+    Rect, Shader, TileMode,
+};
+
+use crate::common::context::Context;
+use crate::common::context::drawing_paths::fill_rule::FillRule;
+use crate::common::context::fill_and_stroke_styles::paint::PaintStyle;
+use crate::common::context::image_asset::ImageAsset;
+use crate::common::context::layers::LayerOptions;
+use crate::common::context::paths::path::Path;
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum MaskType {
+    Alpha = 0,
+    Luminance = 1,
+}
+
+impl Default for MaskType {
+    fn default() -> Self {
+        Self::Alpha
+    }
+}
+
+impl From<i32> for MaskType {
+    fn from(value: i32) -> Self {
+        match value {
+            1 => Self::Luminance,
+            _ => Self::Alpha,
+        }
+    }
+}
+
+impl Into<i32> for MaskType {
+    fn into(self) -> i32 {
+        match self {
+            MaskType::Alpha => 0,
+            MaskType::Luminance => 1,
+        }
+    }
+}
+
+#[derive(Clone)]
+enum MaskSource {
+    Shader(Shader),
+    Path { path: skia_safe::Path, sigma: f32 },
+}
+
+/// A mask waiting for its layer to end, kept with the transform it was created under.
+#[derive(Clone)]
+pub(crate) struct LayerMask {
+    source: MaskSource,
+    mask_type: MaskType,
+    matrix: M44,
+}
+
+impl LayerMask {
+    /// Keeps the layer content where the mask is opaque, clearing it everywhere the mask does
+    /// not cover.
+    pub(crate) fn apply(&self, canvas: &mut skia_safe::Canvas) {
+        let mut layer_paint = skia_safe::Paint::default();
+        layer_paint.set_blend_mode(BlendMode::DstIn);
+        if self.mask_type == MaskType::Luminance {
+            // the layer is premultiplied, so this is the luminance multiplied by the alpha
+            layer_paint.set_color_filter(luma_color_filter::new());
+        }
+        canvas.save_layer(&SaveLayerRec::default().paint(&layer_paint));
+        canvas.set_matrix(&self.matrix);
+        let mut paint = skia_safe::Paint::default();
+        paint.set_anti_alias(true);
+        match &self.source {
+            MaskSource::Shader(shader) => {
+                paint.set_shader(shader.clone());
+                canvas.draw_paint(&paint);
+            }
+            MaskSource::Path { path, sigma } => {
+                if *sigma > 0.0 {
+                    paint.set_mask_filter(MaskFilter::blur(BlurStyle::Normal, *sigma, true));
+                }
+                canvas.draw_path(path, &paint);
+            }
+        }
+        canvas.restore();
+    }
+}
+
+impl Context {
+    /// Starts a layer, closed by `end_layer`, whose content is masked by an image drawn into
+    /// the rectangle. The mask is transparent outside the rectangle.
+    pub fn begin_image_mask(
+        &mut self,
+        asset: &ImageAsset,
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+        mask_type: MaskType,
+    ) -> bool {
+        let image = match asset.skia_image() {
+            Some(image) => image,
+            None => return false,
+        };
+        if image.width() == 0 || image.height() == 0 {
+            return false;
+        }
+        let matrix = Matrix::rect_to_rect(
+            Rect::from_iwh(image.width(), image.height()),
+            Rect::from_xywh(x, y, width, height),
+            None,
+        );
+        let shader = match matrix.and_then(|matrix| {
+            image.to_shader(
+                Some((TileMode::Decal, TileMode::Decal)),
+                self.state.image_filter_quality(),
+                Some(&matrix),
+            )
+        }) {
+            Some(shader) => shader,
+            None => return false,
+        };
+        self.begin_mask(MaskSource::Shader(shader), mask_type);
+        true
+    }
+
+    /// Starts a layer, closed by `end_layer`, whose content is masked by a color, gradient or
+    /// pattern, for example a radial gradient to transparent for a vignette.
+    pub fn begin_style_mask(&mut self, style: &PaintStyle, mask_type: MaskType) -> bool {
+        match style.to_shader(self.state.image_filter_quality()) {
+            Some(shader) => {
+                self.begin_mask(MaskSource::Shader(shader), mask_type);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Starts a layer, closed by `end_layer`, whose content is clipped to `path`, or the current
+    /// path, with edges faded over `feather` pixels. A `feather` of 0 gives an antialiased clip.
+    pub fn begin_path_mask(&mut self, path: Option<&mut Path>, fill_rule: FillRule, feather: f32) {
+        let mut path = path.unwrap_or(self.path.borrow_mut()).clone();
+        path.set_fill_type(fill_rule);
+        self.begin_mask(
+            MaskSource::Path {
+                path: path.path().clone(),
+                sigma: feather.max(0.0) / 2.0,
+            },
+            MaskType::Alpha,
+        );
+    }
+
+    fn begin_mask(&mut self, source: MaskSource, mask_type: MaskType) {
+        self.set_scale_for_device();
+        let matrix = self.surface.canvas().local_to_device();
+        self.clear_scale_for_device();
+        self.begin_layer(&LayerOptions::default());
+        if let Some(state) = self.state_stack.last_mut() {
+            state.layer_mask = Some(LayerMask {
+                source,
+                mask_type,
+                matrix,
+            });
+        }
+    }
+}
@@ -1,11 +1,20 @@
 use std::ops::{Index, IndexMut};
 use std::os::raw::c_float;
 
-use skia_safe::M44;
+use skia_safe::{M44, V3};
+
+use crate::common::context::Device;
+use crate::common::context::filters::css_filter::{
+    Component, function_arguments, LengthUnit, parse_angle, Token, Tokenizer,
+};
+use crate::common::utils::dimensions::parse_size;
 
 #[derive(Clone)]
 pub struct Matrix {
     pub(crate) matrix: M44,
+    /// The DOMMatrix `is2D` flag. It only ever goes from true to false, when a 3d operation
+    /// or value is applied, even if the result happens to hold 2d values.
+    pub(crate) is_2d: bool,
 }
 
 impl Default for Matrix {
@@ -16,9 +25,7 @@ impl Default for Matrix {
 
 impl From<&skia_safe::Matrix> for Matrix {
     fn from(matrix: &skia_safe::Matrix) -> Self {
-        Self {
-            matrix: M44::from(matrix),
-        }
+        Self::from_m44(M44::from(matrix))
     }
 }
 
@@ -111,13 +118,454 @@ impl Matrix {
     pub fn new() -> Self {
         Self {
             matrix: M44::new_identity(),
+            is_2d: true,
         }
     }
 
     pub(crate) fn scale((sx, sy): (f32, f32)) -> Matrix {
         Self {
-            matrix: M44::from(skia_safe::Matrix::scale((sx, sy)))
+            matrix: M44::from(skia_safe::Matrix::scale((sx, sy))),
+            is_2d: true,
+        }
+    }
+
+    /// Wraps a matrix that does not come from a DOMMatrix, judging whether it is 2d from its
+    /// values.
+    pub(crate) fn from_m44(matrix: M44) -> Self {
+        let mut m = [0f32; 16];
+        matrix.get_col_major(&mut m);
+        Self {
+            matrix,
+            is_2d: holds_2d(&m),
+        }
+    }
+
+    /// `values` are m11 to m44 in order, the layout of `to_float32_array`. The matrix is 3d,
+    /// as DOMMatrix makes it for any 16 values.
+    pub fn from_float32_array(values: &[c_float; 16]) -> Self {
+        Self {
+            matrix: M44::col_major(values),
+            is_2d: false,
+        }
+    }
+
+    pub fn to_float32_array(&self) -> [c_float; 16] {
+        let mut m = [0f32; 16];
+        self.matrix.get_col_major(&mut m);
+        m
+    }
+
+    pub fn to_float64_array(&self) -> [f64; 16] {
+        let mut m = [0f64; 16];
+        for (value, member) in m.iter_mut().zip(self.to_float32_array().iter()) {
+            *value = *member as f64;
+        }
+        m
+    }
+
+    pub fn is_2d(&self) -> bool {
+        self.is_2d
+    }
+
+    pub fn is_identity(&self) -> bool {
+        self.to_float32_array() == Self::new().to_float32_array()
+    }
+
+    /// Returns `self * other`, `other` being applied to points first.
+    pub fn multiply(&self, other: &Matrix) -> Matrix {
+        Self {
+            matrix: M44::concat(&self.matrix, &other.matrix),
+            is_2d: self.is_2d && other.is_2d,
+        }
+    }
+
+    pub fn multiply_self(&mut self, other: &Matrix) {
+        self.matrix.pre_concat(&other.matrix);
+        self.is_2d &= other.is_2d;
+    }
+
+    /// Sets the matrix to `other * self`.
+    pub fn pre_multiply_self(&mut self, other: &Matrix) {
+        self.matrix = M44::concat(&other.matrix, &self.matrix);
+        self.is_2d &= other.is_2d;
+    }
+
+    /// Returns the inverse, or a 3d matrix of NaN values if there is none.
+    pub fn inverse(&self) -> Matrix {
+        match self.matrix.invert() {
+            Some(matrix) => Self {
+                matrix,
+                is_2d: self.is_2d,
+            },
+            None => Self::from_float32_array(&[f32::NAN; 16]),
+        }
+    }
+
+    pub fn invert_self(&mut self) {
+        *self = self.inverse();
+    }
+
+    pub fn translate_self(&mut self, tx: c_float, ty: c_float, tz: c_float) {
+        self.matrix.pre_concat(&M44::translate(tx, ty, tz));
+        if tz != 0.0 {
+            self.is_2d = false;
+        }
+    }
+
+    /// Scales around the origin point.
+    pub fn scale_self(
+        &mut self,
+        sx: c_float,
+        sy: c_float,
+        sz: c_float,
+        origin_x: c_float,
+        origin_y: c_float,
+        origin_z: c_float,
+    ) {
+        self.translate_self(origin_x, origin_y, origin_z);
+        self.matrix.pre_concat(&M44::scale(sx, sy, sz));
+        self.translate_self(-origin_x, -origin_y, -origin_z);
+        if sz != 1.0 {
+            self.is_2d = false;
+        }
+    }
+
+    pub fn scale3d_self(
+        &mut self,
+        scale: c_float,
+        origin_x: c_float,
+        origin_y: c_float,
+        origin_z: c_float,
+    ) {
+        self.scale_self(scale, scale, scale, origin_x, origin_y, origin_z);
+    }
+
+    /// Rotates around the z, then y, then x axis, angles in degrees. DOMMatrix callers passing
+    /// a single angle should pass it as `rot_z`.
+    pub fn rotate_self(&mut self, rot_x: c_float, rot_y: c_float, rot_z: c_float) {
+        self.rotate(0.0, 0.0, 1.0, rot_z);
+        self.rotate(0.0, 1.0, 0.0, rot_y);
+        self.rotate(1.0, 0.0, 0.0, rot_x);
+        if rot_x != 0.0 || rot_y != 0.0 {
+            self.is_2d = false;
+        }
+    }
+
+    /// Rotates around the z axis by the angle of the vector (x, y).
+    pub fn rotate_from_vector_self(&mut self, x: c_float, y: c_float) {
+        self.rotate(0.0, 0.0, 1.0, y.atan2(x).to_degrees());
+    }
+
+    /// Rotates around the axis (x, y, z) by `angle` degrees, a zero axis leaving the matrix
+    /// unchanged. Any axis off the z axis makes the matrix 3d, even with a zero angle.
+    pub fn rotate_axis_angle_self(&mut self, x: c_float, y: c_float, z: c_float, angle: c_float) {
+        self.rotate(x, y, z, angle);
+        if x != 0.0 || y != 0.0 {
+            self.is_2d = false;
+        }
+    }
+
+    fn rotate(&mut self, x: c_float, y: c_float, z: c_float, angle: c_float) {
+        if (x == 0.0 && y == 0.0 && z == 0.0) || angle == 0.0 {
+            return;
+        }
+        self.matrix
+            .pre_concat(&M44::rotate(V3::new(x, y, z), angle.to_radians()));
+    }
+
+    /// Skews along the x axis by `angle` degrees.
+    pub fn skew_x_self(&mut self, angle: c_float) {
+        self.skew_self(angle, 0.0);
+    }
+
+    /// Skews along the y axis by `angle` degrees.
+    pub fn skew_y_self(&mut self, angle: c_float) {
+        self.skew_self(0.0, angle);
+    }
+
+    /// Skews along both axes at once, as CSS `skew()` does.
+    fn skew_self(&mut self, angle_x: c_float, angle_y: c_float) {
+        let mut skew = M44::new_identity();
+        skew.set_rc(0, 1, angle_x.to_radians().tan());
+        skew.set_rc(1, 0, angle_y.to_radians().tan());
+        self.matrix.pre_concat(&skew);
+    }
+
+    /// Applies a perspective projection with the viewer `depth` away from the z = 0 plane.
+    fn perspective_self(&mut self, depth: c_float) {
+        let mut perspective = M44::new_identity();
+        // depths under 1px are clamped as in CSS Transforms 2
+        perspective.set_rc(3, 2, -1.0 / depth.max(1.0));
+        self.matrix.pre_concat(&perspective);
+        self.is_2d = false;
+    }
+
+    pub fn flip_x(&self) -> Matrix {
+        let mut matrix = self.clone();
+        matrix.matrix.pre_concat(&M44::scale(-1.0, 1.0, 1.0));
+        matrix
+    }
+
+    pub fn flip_y(&self) -> Matrix {
+        let mut matrix = self.clone();
+        matrix.matrix.pre_concat(&M44::scale(1.0, -1.0, 1.0));
+        matrix
+    }
+
+    /// Parses a CSS `<transform-list>` or `none` the way the DOMMatrix constructor does, an
+    /// empty string giving the identity. Lengths resolve through `parse_size` against `device`,
+    /// percentages and font relative lengths having nothing to resolve against. Returns `None`
+    /// if any part of the value is invalid.
+    pub fn from_css(value: &str, device: Device) -> Option<Matrix> {
+        let mut tokenizer = Tokenizer::new(value);
+        let mut components = Vec::new();
+        while let Some(component) = tokenizer.next_component() {
+            let component = component.ok()?;
+            if component.token != Token::Whitespace {
+                components.push(component);
+            }
+        }
+        let mut matrix = Matrix::new();
+        match components.as_slice() {
+            [] => return Some(matrix),
+            [Component {
+                token: Token::Ident(ident),
+                ..
+            }] if ident.eq_ignore_ascii_case("none") => return Some(matrix),
+            _ => {}
+        }
+        for component in components.iter() {
+            matrix.apply_transform_function(component, device)?;
+        }
+        Some(matrix)
+    }
+
+    /// Applies one transform function. The 3d functions make the matrix 3d whatever their
+    /// arguments, so `translateZ(0)` still gives a 3d matrix.
+    fn apply_transform_function(&mut self, component: &Component, device: Device) -> Option<()> {
+        let name = match component.token {
+            Token::Function(name) => name.to_ascii_lowercase(),
+            _ => return None,
+        };
+        let args = comma_separated(&function_arguments(component)?)?;
+        match (name.as_str(), args.as_slice()) {
+            ("matrix", args) if args.len() == 6 => {
+                let mut values = [0f32; 6];
+                for (value, arg) in values.iter_mut().zip(args.iter()) {
+                    *value = parse_number(arg)?;
+                }
+                let mut matrix = Matrix::new();
+                matrix.set_affine(&values);
+                self.multiply_self(&matrix);
+            }
+            ("matrix3d", args) if args.len() == 16 => {
+                let mut values = [0f32; 16];
+                for (value, arg) in values.iter_mut().zip(args.iter()) {
+                    *value = parse_number(arg)?;
+                }
+                self.multiply_self(&Matrix::from_float32_array(&values));
+            }
+            ("translate", [tx]) | ("translatex", [tx]) => {
+                self.translate_self(parse_length(tx, device)?, 0.0, 0.0)
+            }
+            ("translate", [tx, ty]) => {
+                self.translate_self(parse_length(tx, device)?, parse_length(ty, device)?, 0.0)
+            }
+            ("translatey", [ty]) => self.translate_self(0.0, parse_length(ty, device)?, 0.0),
+            ("translatez", [tz]) => self.translate_self(0.0, 0.0, parse_length(tz, device)?),
+            ("translate3d", [tx, ty, tz]) => self.translate_self(
+                parse_length(tx, device)?,
+                parse_length(ty, device)?,
+                parse_length(tz, device)?,
+            ),
+            ("scale", [scale]) => {
+                let scale = parse_scale(scale)?;
+                self.scale_self(scale, scale, 1.0, 0.0, 0.0, 0.0)
+            }
+            ("scale", [sx, sy]) => {
+                self.scale_self(parse_scale(sx)?, parse_scale(sy)?, 1.0, 0.0, 0.0, 0.0)
+            }
+            ("scalex", [sx]) => self.scale_self(parse_scale(sx)?, 1.0, 1.0, 0.0, 0.0, 0.0),
+            ("scaley", [sy]) => self.scale_self(1.0, parse_scale(sy)?, 1.0, 0.0, 0.0, 0.0),
+            ("scalez", [sz]) => self.scale_self(1.0, 1.0, parse_scale(sz)?, 0.0, 0.0, 0.0),
+            ("scale3d", [sx, sy, sz]) => self.scale_self(
+                parse_scale(sx)?,
+                parse_scale(sy)?,
+                parse_scale(sz)?,
+                0.0,
+                0.0,
+                0.0,
+            ),
+            ("rotate", [angle]) | ("rotatez", [angle]) => {
+                self.rotate_axis_angle_self(0.0, 0.0, 1.0, parse_degrees(angle)?)
+            }
+            ("rotatex", [angle]) => {
+                self.rotate_axis_angle_self(1.0, 0.0, 0.0, parse_degrees(angle)?)
+            }
+            ("rotatey", [angle]) => {
+                self.rotate_axis_angle_self(0.0, 1.0, 0.0, parse_degrees(angle)?)
+            }
+            ("rotate3d", [x, y, z, angle]) => self.rotate_axis_angle_self(
+                parse_number(x)?,
+                parse_number(y)?,
+                parse_number(z)?,
+                parse_degrees(angle)?,
+            ),
+            ("skew", [angle_x]) | ("skewx", [angle_x]) => {
+                self.skew_self(parse_degrees(angle_x)?, 0.0)
+            }
+            ("skew", [angle_x, angle_y]) => {
+                self.skew_self(parse_degrees(angle_x)?, parse_degrees(angle_y)?)
+            }
+            ("skewy", [angle_y]) => self.skew_self(0.0, parse_degrees(angle_y)?),
+            ("perspective", [depth]) => match depth.token {
+                Token::Ident(ident) if ident.eq_ignore_ascii_case("none") => {}
+                _ => {
+                    let depth = parse_length(depth, device)?;
+                    if depth < 0.0 {
+                        return None;
+                    }
+                    self.perspective_self(depth)
+                }
+            },
+            _ => return None,
+        }
+        if TRANSFORM_FUNCTIONS_3D.contains(&name.as_str()) {
+            self.is_2d = false;
         }
+        Some(())
+    }
+
+    /// Serializes as the DOMMatrix stringifier does, `matrix()` when the matrix is 2d and
+    /// `matrix3d()` otherwise. Returns `None` if any value is not finite.
+    pub fn to_string(&self) -> Option<String> {
+        let m = self.to_float32_array();
+        if m.iter().any(|value| !value.is_finite()) {
+            return None;
+        }
+        let (name, values) = if self.is_2d() {
+            ("matrix", self.affine())
+        } else {
+            ("matrix3d", m.to_vec())
+        };
+        let values: Vec<String> = values.iter().map(|value| js_number_to_string(*value)).collect();
+        Some(format!("{}({})", name, values.join(", ")))
+    }
+
+    /// Decomposes the matrix with the CSS Transforms unmatrix algorithm. Returns `None` if the
+    /// matrix cannot be decomposed.
+    pub fn decompose(&self) -> Option<DecomposedMatrix> {
+        // m[i * 4 + j] is matrix[i][j] in the algorithm, row i holding m(i + 1)1 to m(i + 1)4
+        let mut m = self.to_float32_array();
+        if m[15] == 0.0 {
+            return None;
+        }
+        let w = m[15];
+        for value in m.iter_mut() {
+            *value /= w;
+        }
+
+        // the perspective matrix also tests the upper 3x3 for singularity
+        let mut perspective_matrix = m;
+        perspective_matrix[3] = 0.0;
+        perspective_matrix[7] = 0.0;
+        perspective_matrix[11] = 0.0;
+        perspective_matrix[15] = 1.0;
+        let inverse_perspective = M44::col_major(&perspective_matrix).invert()?;
+
+        let perspective = if m[3] != 0.0 || m[7] != 0.0 || m[11] != 0.0 {
+            // the algorithm's row vector times transposed inverse, written for column vectors
+            let p = inverse_perspective
+                .transpose()
+                .map(m[3], m[7], m[11], m[15]);
+            [p.x, p.y, p.z, p.w]
+        } else {
+            [0.0, 0.0, 0.0, 1.0]
+        };
+
+        let translate = [m[12], m[13], m[14]];
+
+        let mut row = [[m[0], m[1], m[2]], [m[4], m[5], m[6]], [m[8], m[9], m[10]]];
+        let mut scale = [0f32; 3];
+        let mut skew = [0f32; 3];
+
+        scale[0] = length(row[0]);
+        row[0] = normalize(row[0]);
+
+        skew[0] = dot(row[0], row[1]);
+        row[1] = combine(row[1], row[0], 1.0, -skew[0]);
+
+        scale[1] = length(row[1]);
+        row[1] = normalize(row[1]);
+        skew[0] /= scale[1];
+
+        skew[1] = dot(row[0], row[2]);
+        row[2] = combine(row[2], row[0], 1.0, -skew[1]);
+        skew[2] = dot(row[1], row[2]);
+        row[2] = combine(row[2], row[1], 1.0, -skew[2]);
+
+        scale[2] = length(row[2]);
+        row[2] = normalize(row[2]);
+        skew[1] /= scale[2];
+        skew[2] /= scale[2];
+
+        // a negative determinant means the coordinate system is flipped
+        if dot(row[0], cross(row[1], row[2])) < 0.0 {
+            for (scale, row) in scale.iter_mut().zip(row.iter_mut()) {
+                *scale = -*scale;
+                *row = combine(*row, *row, -1.0, 0.0);
+            }
+        }
+
+        let mut quaternion = [
+            0.5 * (1.0 + row[0][0] - row[1][1] - row[2][2]).max(0.0).sqrt(),
+            0.5 * (1.0 - row[0][0] + row[1][1] - row[2][2]).max(0.0).sqrt(),
+            0.5 * (1.0 - row[0][0] - row[1][1] + row[2][2]).max(0.0).sqrt(),
+            0.5 * (1.0 + row[0][0] + row[1][1] + row[2][2]).max(0.0).sqrt(),
+        ];
+        if row[2][1] > row[1][2] {
+            quaternion[0] = -quaternion[0];
+        }
+        if row[0][2] > row[2][0] {
+            quaternion[1] = -quaternion[1];
+        }
+        if row[1][0] > row[0][1] {
+            quaternion[2] = -quaternion[2];
+        }
+
+        Some(DecomposedMatrix {
+            translate,
+            scale,
+            skew,
+            perspective,
+            quaternion,
+        })
+    }
+
+    /// Interpolates between the decompositions of `self` and `other` at `t`, 0 giving `self`
+    /// and 1 giving `other`. If either cannot be decomposed the result switches from one to
+    /// the other at 0.5, as CSS animations do.
+    pub fn interpolate(&self, other: &Matrix, t: c_float) -> Matrix {
+        match (self.decompose(), other.decompose()) {
+            (Some(from), Some(to)) => {
+                let mut matrix = from.interpolate(&to, t).recompose();
+                matrix.is_2d = self.is_2d && other.is_2d;
+                matrix
+            }
+            _ => {
+                if t < 0.5 {
+                    self.clone()
+                } else {
+                    other.clone()
+                }
+            }
+        }
+    }
+
+    /// Returns the x, y, z and w of the transformed point.
+    pub fn transform_point(&self, x: c_float, y: c_float, z: c_float, w: c_float) -> [c_float; 4] {
+        let point = self.matrix.map(x, y, z, w);
+        [point.x, point.y, point.z, point.w]
     }
 
     fn member_2d(&self, member: Member2D) -> c_float {
@@ -134,7 +582,8 @@ impl Matrix {
         ];
         self.matrix.get_col_major(&mut m);
         m[member] = value;
-        self.matrix = M44::row_major(&m);
+        self.matrix = M44::col_major(&m);
+        self.is_2d &= holds_2d(&m);
     }
 
     fn member_2d_name(&self, member: Member2DName) -> c_float {
@@ -151,7 +600,8 @@ impl Matrix {
         ];
         self.matrix.get_col_major(&mut m);
         m[member] = value;
-        self.matrix = M44::row_major(&m);
+        self.matrix = M44::col_major(&m);
+        self.is_2d &= holds_2d(&m);
     }
 
     fn member_3d(&self, member: Member3D) -> c_float {
@@ -168,7 +618,8 @@ impl Matrix {
         ];
         self.matrix.get_col_major(&mut m);
         m[member] = value;
-        self.matrix = M44::row_major(&m);
+        self.matrix = M44::col_major(&m);
+        self.is_2d &= holds_2d(&m);
     }
 
     pub fn affine(&self) -> Vec<c_float> {
@@ -186,7 +637,7 @@ impl Matrix {
         m[Member2D::D] = value[3];
         m[Member2D::E] = value[4];
         m[Member2D::F] = value[5];
-        self.matrix = M44::row_major(&m);
+        self.matrix = M44::col_major(&m);
     }
 
     pub fn a(&self) -> c_float {
@@ -381,3 +832,279 @@ impl Matrix {
         self.set_member_3d(Member3D::M44, m44)
     }
 }
+
+/// A matrix split into the components CSS Transforms interpolates.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct DecomposedMatrix {
+    pub translate: [c_float; 3],
+    pub scale: [c_float; 3],
+    /// The xy, xz and yz shears.
+    pub skew: [c_float; 3],
+    pub perspective: [c_float; 4],
+    /// The rotation as an x, y, z, w quaternion.
+    pub quaternion: [c_float; 4],
+}
+
+impl DecomposedMatrix {
+    /// The number of values in `to_array`.
+    pub const LENGTH: usize = 17;
+
+    /// Translate, scale, skew, perspective and quaternion in order.
+    pub fn to_array(&self) -> [c_float; Self::LENGTH] {
+        let mut values = [0f32; Self::LENGTH];
+        values[0..3].copy_from_slice(&self.translate);
+        values[3..6].copy_from_slice(&self.scale);
+        values[6..9].copy_from_slice(&self.skew);
+        values[9..13].copy_from_slice(&self.perspective);
+        values[13..17].copy_from_slice(&self.quaternion);
+        values
+    }
+
+    pub fn from_array(values: &[c_float; Self::LENGTH]) -> Self {
+        let mut decomposed = Self {
+            translate: [0.0; 3],
+            scale: [0.0; 3],
+            skew: [0.0; 3],
+            perspective: [0.0; 4],
+            quaternion: [0.0; 4],
+        };
+        decomposed.translate.copy_from_slice(&values[0..3]);
+        decomposed.scale.copy_from_slice(&values[3..6]);
+        decomposed.skew.copy_from_slice(&values[6..9]);
+        decomposed.perspective.copy_from_slice(&values[9..13]);
+        decomposed.quaternion.copy_from_slice(&values[13..17]);
+        decomposed
+    }
+
+    /// Builds the matrix back, the inverse of `Matrix::decompose`.
+    pub fn recompose(&self) -> Matrix {
+        let mut matrix = M44::new_identity();
+        for (i, value) in self.perspective.iter().enumerate() {
+            matrix.set_rc(3, i, *value);
+        }
+
+        let [tx, ty, tz] = self.translate;
+        matrix.pre_concat(&M44::translate(tx, ty, tz));
+
+        let [x, y, z, w] = self.quaternion;
+        let rotation = M44::row_major(&[
+            1.0 - 2.0 * (y * y + z * z),
+            2.0 * (x * y - z * w),
+            2.0 * (x * z + y * w),
+            0.0,
+            2.0 * (x * y + z * w),
+            1.0 - 2.0 * (x * x + z * z),
+            2.0 * (y * z - x * w),
+            0.0,
+            2.0 * (x * z - y * w),
+            2.0 * (y * z + x * w),
+            1.0 - 2.0 * (x * x + y * y),
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            1.0,
+        ]);
+        matrix.pre_concat(&rotation);
+
+        let [skew_xy, skew_xz, skew_yz] = self.skew;
+        if skew_yz != 0.0 {
+            let mut skew = M44::new_identity();
+            skew.set_rc(1, 2, skew_yz);
+            matrix.pre_concat(&skew);
+        }
+        if skew_xz != 0.0 {
+            let mut skew = M44::new_identity();
+            skew.set_rc(0, 2, skew_xz);
+            matrix.pre_concat(&skew);
+        }
+        if skew_xy != 0.0 {
+            let mut skew = M44::new_identity();
+            skew.set_rc(0, 1, skew_xy);
+            matrix.pre_concat(&skew);
+        }
+
+        let [sx, sy, sz] = self.scale;
+        matrix.pre_concat(&M44::scale(sx, sy, sz));
+
+        Matrix::from_m44(matrix)
+    }
+
+    /// Interpolates every component linearly except the rotation, which is spherically
+    /// interpolated.
+    pub fn interpolate(&self, other: &DecomposedMatrix, t: c_float) -> DecomposedMatrix {
+        let lerp = |from: &[c_float], to: &[c_float], output: &mut [c_float]| {
+            for ((output, from), to) in output.iter_mut().zip(from.iter()).zip(to.iter()) {
+                *output = from + (to - from) * t;
+            }
+        };
+        let mut result = *self;
+        lerp(&self.translate, &other.translate, &mut result.translate);
+        lerp(&self.scale, &other.scale, &mut result.scale);
+        lerp(&self.skew, &other.skew, &mut result.skew);
+        lerp(
+            &self.perspective,
+            &other.perspective,
+            &mut result.perspective,
+        );
+
+        let from = self.quaternion;
+        let to = other.quaternion;
+        let product = (from[0] * to[0] + from[1] * to[1] + from[2] * to[2] + from[3] * to[3])
+            .max(-1.0)
+            .min(1.0);
+        if product.abs() < 1.0 {
+            let theta = product.acos();
+            let w = (t * theta).sin() / (1.0 - product * product).sqrt();
+            let scale_from = (t * theta).cos() - product * w;
+            let quaternions = from.iter().zip(to.iter());
+            for (output, (from, to)) in result.quaternion.iter_mut().zip(quaternions) {
+                *output = from * scale_from + to * w;
+            }
+        }
+        result
+    }
+}
+
+/// Formats a finite number as JS `Number.prototype.toString` does, from the shortest digits that
+/// round trip: exponent notation below 1e-6 and from 1e21, as in `1e-7` and `1.5e+21`, and -0
+/// printed as 0.
+fn js_number_to_string(value: f32) -> String {
+    if value == 0.0 {
+        return "0".into();
+    }
+    let scientific = format!("{:e}", value.abs());
+    let (mantissa, exponent) = match scientific.split_once('e') {
+        Some((mantissa, exponent)) => (mantissa, exponent.parse::<i32>().unwrap_or(0)),
+        None => (scientific.as_str(), 0),
+    };
+    let digits = mantissa.replace('.', "");
+    let k = digits.len() as i32;
+    // the position of the decimal point relative to the start of the digits
+    let n = exponent + 1;
+    let sign = if value < 0.0 { "-" } else { "" };
+    let number = if k <= n && n <= 21 {
+        format!("{}{}", digits, "0".repeat((n - k) as usize))
+    } else if 0 < n && n <= 21 {
+        format!("{}.{}", &digits[..n as usize], &digits[n as usize..])
+    } else if -6 < n && n <= 0 {
+        format!("0.{}{}", "0".repeat(-n as usize), digits)
+    } else {
+        let exponent_sign = if n - 1 < 0 { "-" } else { "+" };
+        format!("{}e{}{}", mantissa, exponent_sign, (n - 1).abs())
+    };
+    format!("{}{}", sign, number)
+}
+
+const TRANSFORM_FUNCTIONS_3D: [&str; 9] = [
+    "matrix3d",
+    "translatez",
+    "translate3d",
+    "scalez",
+    "scale3d",
+    "rotatex",
+    "rotatey",
+    "rotate3d",
+    "perspective",
+];
+
+/// Whether the values only hold a 2d transform, the z and perspective parts being those of the
+/// identity.
+fn holds_2d(m: &[f32; 16]) -> bool {
+    [
+        Member3D::M13,
+        Member3D::M14,
+        Member3D::M23,
+        Member3D::M24,
+        Member3D::M31,
+        Member3D::M32,
+        Member3D::M34,
+        Member3D::M43,
+    ]
+    .iter()
+    .all(|member| m[*member] == 0.0)
+        && m[Member3D::M33] == 1.0
+        && m[Member3D::M44] == 1.0
+}
+
+fn dot(a: [f32; 3], b: [f32; 3]) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn length(a: [f32; 3]) -> f32 {
+    dot(a, a).sqrt()
+}
+
+fn normalize(a: [f32; 3]) -> [f32; 3] {
+    let length = length(a);
+    if length == 0.0 {
+        return a;
+    }
+    combine(a, a, 1.0 / length, 0.0)
+}
+
+/// `a * scale_a + b * scale_b`.
+fn combine(a: [f32; 3], b: [f32; 3], scale_a: f32, scale_b: f32) -> [f32; 3] {
+    [
+        a[0] * scale_a + b[0] * scale_b,
+        a[1] * scale_a + b[1] * scale_b,
+        a[2] * scale_a + b[2] * scale_b,
+    ]
+}
+
+/// The values of comma separated arguments, `None` if the commas are misplaced.
+fn comma_separated<'a>(args: &[Component<'a>]) -> Option<Vec<Component<'a>>> {
+    if args.len() % 2 == 0 && !args.is_empty() {
+        return None;
+    }
+    let mut values = Vec::new();
+    for (i, arg) in args.iter().enumerate() {
+        let is_comma = arg.token == Token::Comma;
+        if is_comma != (i % 2 == 1) {
+            return None;
+        }
+        if !is_comma {
+            values.push(*arg);
+        }
+    }
+    Some(values)
+}
+
+fn parse_number(component: &Component) -> Option<f32> {
+    match component.token {
+        Token::Number(value) => Some(value),
+        _ => None,
+    }
+}
+
+/// A `<number>` or `<percentage>`.
+fn parse_scale(component: &Component) -> Option<f32> {
+    match component.token {
+        Token::Number(value) => Some(value),
+        Token::Percentage(value) => Some(value / 100.0),
+        _ => None,
+    }
+}
+
+fn parse_length(component: &Component, device: Device) -> Option<f32> {
+    match component.token {
+        Token::Dimension(_, unit) => match LengthUnit::from_str(unit)? {
+            LengthUnit::Em | LengthUnit::Rem => None,
+            _ => Some(parse_size(&component.source.to_ascii_lowercase(), device)),
+        },
+        Token::Number(value) if value == 0.0 => Some(0.0),
+        _ => None,
+    }
+}
+
+fn parse_degrees(component: &Component) -> Option<f32> {
+    parse_angle(component).map(|angle| angle.to_degrees())
+}
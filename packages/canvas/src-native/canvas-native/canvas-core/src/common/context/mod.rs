@@ -1,8 +1,13 @@
+use std::collections::HashMap;
 use std::os::raw::c_float;
 
-use skia_safe::{Color, Point, Surface};
+use skia_safe::{Blender, Color, Color4f, ImageFilter, M44, Point, Surface};
 
+use crate::common::context::color_space::PredefinedColorSpace;
 use crate::common::context::filter_quality::FilterQuality;
+use crate::common::context::filters::canvas_filter::CanvasFilter;
+use crate::common::context::masks::LayerMask;
+use crate::common::context::recording::ContextSurface;
 use crate::{
     common::context::compositing::composite_operation_type::CompositeOperationType,
     common::context::drawing_text::typography::Font,
@@ -13,6 +18,7 @@ use crate::{
     common::context::paths::path::Path,
     common::context::text_styles::{
         text_align::TextAlign, text_baseline::TextBaseLine, text_direction::TextDirection,
+        text_rendering::TextRendering,
     },
 };
 
@@ -23,14 +29,20 @@ pub mod paths;
 pub mod pixel_manipulation;
 pub mod text_styles;
 
+pub mod box_shadows;
+pub mod color_space;
 pub mod compositing;
 pub mod drawing_paths;
+pub mod drawing_patches;
 pub mod drawing_rectangles;
 pub mod filters;
 pub mod gradients_and_patterns;
 
 pub mod image_smoothing;
+pub mod layers;
 pub mod line_styles;
+pub mod masks;
+pub mod recording;
 pub mod shadows;
 pub mod state;
 
@@ -51,6 +63,7 @@ pub struct Device {
     pub alpha: bool,
     pub ppi: c_float,
     pub matrix: skia_safe::Matrix,
+    pub color_space: PredefinedColorSpace,
 }
 
 impl Device {
@@ -64,6 +77,7 @@ impl Device {
             alpha: false,
             ppi,
             matrix: skia_safe::Matrix::scale((density, density)),
+            color_space: PredefinedColorSpace::SRGB,
         }
     }
 
@@ -79,7 +93,8 @@ pub(crate) struct State {
     pub(crate) font: Font,
     pub(crate) text_align: TextAlign,
     pub(crate) text_baseline: TextBaseLine,
-    pub(crate) shadow_color: Color,
+    pub(crate) text_rendering: TextRendering,
+    pub(crate) shadow_color: Color4f,
     pub(crate) shadow_offset: Point,
     pub(crate) shadow_blur: f32,
     pub(crate) image_smoothing_enabled: bool,
@@ -91,8 +106,19 @@ pub(crate) struct State {
     pub(crate) line_dash_list: Vec<f32>,
     pub(crate) line_dash_offset: f32,
     pub(crate) filter: String,
+    pub(crate) canvas_filter: Option<CanvasFilter>,
     pub(crate) global_alpha: f32,
     pub(crate) global_composite_operation: CompositeOperationType,
+    pub(crate) custom_composite_operation: Option<String>,
+    /// Set on the states pushed by `begin_layer`, which only `end_layer` pops.
+    pub(crate) is_layer: bool,
+    /// The mask applied when the layer begun with this state ends.
+    pub(crate) layer_mask: Option<LayerMask>,
+    /// The clips made since the last save, in device space, kept so that `reset_clip` can
+    /// rebuild the clip of the saved states.
+    pub(crate) clip_paths: Vec<skia_safe::Path>,
+    /// For saved states, the transform in effect when the save was made.
+    pub(crate) transform: M44,
 }
 
 impl State {
@@ -116,7 +142,8 @@ impl State {
             font,
             text_align: TextAlign::default(),
             text_baseline: TextBaseLine::default(),
-            shadow_color: Color::TRANSPARENT,
+            text_rendering: TextRendering::default(),
+            shadow_color: Color4f::TRANSPARENT,
             shadow_offset: (0.0, 0.0).into(),
             shadow_blur: 0.0,
             image_smoothing_enabled: false,
@@ -128,21 +155,29 @@ impl State {
             line_dash_list: Default::default(),
             line_dash_offset: 0.0,
             filter: "none".into(),
+            canvas_filter: None,
             global_alpha: 1.0,
             global_composite_operation: CompositeOperationType::default(),
+            custom_composite_operation: None,
+            is_layer: false,
+            layer_mask: None,
+            clip_paths: Vec::new(),
+            transform: M44::new_identity(),
         }
     }
 }
 
 #[derive(Clone)]
 pub struct Context {
-    pub(crate) surface: Surface,
+    pub(crate) surface: ContextSurface,
     pub(crate) path: Path,
     pub(crate) state: State,
     pub(crate) state_stack: Vec<State>,
     pub(crate) device: Device,
     pub(crate) font_color: Color,
     pub(crate) enable_scaling: bool,
+    pub(crate) blenders: HashMap<String, Blender>,
+    pub(crate) svg_filters: HashMap<String, ImageFilter>,
 }
 
 impl Context {
@@ -153,13 +188,15 @@ impl Context {
                       device: Device,
                       font_color: Color) -> Self {
         Self {
-            surface,
+            surface: surface.into(),
             path,
             state,
             state_stack,
             device,
             font_color,
             enable_scaling: false,
+            blenders: HashMap::new(),
+            svg_filters: HashMap::new(),
         }
     }
 
@@ -167,6 +204,10 @@ impl Context {
         &self.device
     }
 
+    pub fn color_space(&self) -> PredefinedColorSpace {
+        self.device.color_space
+    }
+
     pub fn reset_state(&mut self) {
         let direction = self.state.direction;
         self.state = State::from_device(self.device, direction);
@@ -64,7 +64,7 @@ impl Context {
             anticlockwise,
         );
 
-        let transform = self.get_transform();
+        let transform = self.get_transform_3x3();
 
         self.path.path.add_path_matrix(path.path(), &transform, skia_safe::path::AddPathMode::Extend);
     }
@@ -96,7 +96,7 @@ impl Context {
             anticlockwise,
         );
 
-        let transform = self.get_transform();
+        let transform = self.get_transform_3x3();
 
         self.path.path.add_path_matrix(path.path(), &transform, skia_safe::path::AddPathMode::Extend);
     }
@@ -104,7 +104,7 @@ impl Context {
     #[inline(always)]
     pub fn rect(&mut self, x: c_float, y: c_float, width: c_float, height: c_float) {
         /*let rect = skia_safe::Rect::from_xywh(x, y, width, height);
-        let quad = self.get_transform().map_rect_to_quad(rect);
+        let quad = self.get_transform_3x3().map_rect_to_quad(rect);
         let mut path = skia_safe::Path::new();
 
         path.move_to(quad[0]);
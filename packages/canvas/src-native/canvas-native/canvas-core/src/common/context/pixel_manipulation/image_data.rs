@@ -1,5 +1,63 @@
 use std::os::raw::c_int;
 
+use skia_safe::ColorType;
+
+use crate::common::context::color_space::PredefinedColorSpace;
+
+/// How each RGBA channel of an `ImageData` is stored. Float16 channels are IEEE half floats
+/// kept as their raw `u16` bits.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ImageDataStorageFormat {
+    Uint8 = 0,
+    Float16 = 1,
+    Float32 = 2,
+}
+
+impl Default for ImageDataStorageFormat {
+    fn default() -> Self {
+        Self::Uint8
+    }
+}
+
+impl From<i32> for ImageDataStorageFormat {
+    fn from(value: i32) -> Self {
+        match value {
+            1 => Self::Float16,
+            2 => Self::Float32,
+            _ => Self::Uint8,
+        }
+    }
+}
+
+impl Into<i32> for ImageDataStorageFormat {
+    fn into(self) -> i32 {
+        match self {
+            ImageDataStorageFormat::Uint8 => 0,
+            ImageDataStorageFormat::Float16 => 1,
+            ImageDataStorageFormat::Float32 => 2,
+        }
+    }
+}
+
+impl ImageDataStorageFormat {
+    pub fn bytes_per_pixel(&self) -> usize {
+        match self {
+            ImageDataStorageFormat::Uint8 => 4,
+            ImageDataStorageFormat::Float16 => 8,
+            ImageDataStorageFormat::Float32 => 16,
+        }
+    }
+
+    pub(crate) fn color_type(&self) -> ColorType {
+        match self {
+            ImageDataStorageFormat::Uint8 => ColorType::RGBA8888,
+            ImageDataStorageFormat::Float16 => ColorType::RGBAF16,
+            ImageDataStorageFormat::Float32 => ColorType::RGBAF32,
+        }
+    }
+}
+
 #[repr(C)]
 #[derive(Debug, Clone)]
 pub struct ImageData {
@@ -8,37 +66,57 @@ pub struct ImageData {
     width: c_int,
     height: c_int,
     pub(crate) scale: f32,
+    color_space: PredefinedColorSpace,
+    storage_format: ImageDataStorageFormat,
 }
 
 impl ImageData {
-    fn to_raw(data: Vec<u8>) -> (*mut u8, usize) {
+    /// Hands out the storage as bytes. Float formats are allocated as their channel type so the
+    /// pointer is aligned for it.
+    fn to_raw<T>(data: Vec<T>) -> (*mut u8, usize) {
         let mut slice = data.into_boxed_slice();
-        let ptr = slice.as_mut_ptr();
-        let len = slice.len();
+        let ptr = slice.as_mut_ptr() as *mut u8;
+        let len = slice.len() * std::mem::size_of::<T>();
         Box::into_raw(slice);
         (ptr, len)
     }
+
     pub fn new(width: c_int, height: c_int) -> Self {
-        let data = vec![255u8; (width * height * 4) as usize];
-        let (data, data_len) = Self::to_raw(data);
+        Self::new_with_color_space(width, height, PredefinedColorSpace::SRGB)
+    }
+
+    pub fn new_with_color_space(
+        width: c_int,
+        height: c_int,
+        color_space: PredefinedColorSpace,
+    ) -> Self {
+        Self::new_with_settings(width, height, color_space, ImageDataStorageFormat::Uint8)
+    }
+
+    pub fn new_with_settings(
+        width: c_int,
+        height: c_int,
+        color_space: PredefinedColorSpace,
+        storage_format: ImageDataStorageFormat,
+    ) -> Self {
+        let len = (width * height) as usize * 4;
+        let (data, data_len) = match storage_format {
+            ImageDataStorageFormat::Uint8 => Self::to_raw(vec![255u8; len]),
+            // all set bits would read back as NaN
+            ImageDataStorageFormat::Float16 => Self::to_raw(vec![0u16; len]),
+            ImageDataStorageFormat::Float32 => Self::to_raw(vec![0f32; len]),
+        };
         Self {
             width,
             height,
             data,
             data_len,
             scale: 1.,
+            color_space,
+            storage_format,
         }
     }
 
-    pub(crate) fn set_data(&mut self, data: Vec<u8>) {
-        unsafe {
-            let _ = Box::from_raw(std::slice::from_raw_parts_mut(self.data, self.data_len));
-        }
-        let (data, data_len) = Self::to_raw(data);
-        self.data = data;
-        self.data_len = data_len;
-    }
-
     pub fn width(&self) -> i32 {
         (self.width as f32 / self.scale) as i32
     }
@@ -47,6 +125,30 @@ impl ImageData {
         (self.height as f32 / self.scale) as i32
     }
 
+    pub fn color_space(&self) -> PredefinedColorSpace {
+        self.color_space
+    }
+
+    pub fn storage_format(&self) -> ImageDataStorageFormat {
+        self.storage_format
+    }
+
+    /// The pixels as half float bits, when stored as `Float16`.
+    pub fn data_f16(&self) -> Option<&[u16]> {
+        if self.storage_format != ImageDataStorageFormat::Float16 {
+            return None;
+        }
+        Some(unsafe { std::slice::from_raw_parts(self.data as *const u16, self.data_len / 2) })
+    }
+
+    /// The pixels as floats, when stored as `Float32`.
+    pub fn data_f32(&self) -> Option<&[f32]> {
+        if self.storage_format != ImageDataStorageFormat::Float32 {
+            return None;
+        }
+        Some(unsafe { std::slice::from_raw_parts(self.data as *const f32, self.data_len / 4) })
+    }
+
     pub fn data(&self) -> &[u8] {
         unsafe { std::slice::from_raw_parts_mut(self.data, self.data_len) }
     }
@@ -58,12 +160,36 @@ impl ImageData {
 
 impl From<&ImageData> for ImageData {
     fn from(data: &ImageData) -> Self {
-        Self::new(data.width, data.height)
+        Self::new_with_settings(
+            data.width,
+            data.height,
+            data.color_space,
+            data.storage_format,
+        )
     }
 }
 
 impl Drop for ImageData {
     fn drop(&mut self) {
-        let _ = unsafe { Box::from_raw(std::slice::from_raw_parts_mut(self.data, self.data_len)) };
+        // freed as the type it was allocated with
+        unsafe {
+            match self.storage_format {
+                ImageDataStorageFormat::Uint8 => {
+                    let _ = Box::from_raw(std::slice::from_raw_parts_mut(self.data, self.data_len));
+                }
+                ImageDataStorageFormat::Float16 => {
+                    let _ = Box::from_raw(std::slice::from_raw_parts_mut(
+                        self.data as *mut u16,
+                        self.data_len / 2,
+                    ));
+                }
+                ImageDataStorageFormat::Float32 => {
+                    let _ = Box::from_raw(std::slice::from_raw_parts_mut(
+                        self.data as *mut f32,
+                        self.data_len / 4,
+                    ));
+                }
+            }
+        }
     }
 }
@@ -1,9 +1,10 @@
 use std::os::raw::{c_float, c_int};
 
-use skia_safe::{AlphaType, ColorType, ImageInfo, IPoint, ISize, IVector, Rect};
+use skia_safe::{AlphaType, ImageInfo, IPoint, ISize, IVector, Rect};
 
+use crate::common::context::color_space::PredefinedColorSpace;
 use crate::common::context::Context;
-use crate::common::context::pixel_manipulation::image_data::ImageData;
+use crate::common::context::pixel_manipulation::image_data::{ImageData, ImageDataStorageFormat};
 
 pub mod image_data;
 
@@ -12,32 +13,91 @@ impl Context {
         ImageData::new(width, height)
     }
 
+    pub fn create_image_data_with_color_space(
+        width: c_int,
+        height: c_int,
+        color_space: PredefinedColorSpace,
+    ) -> ImageData {
+        ImageData::new_with_color_space(width, height, color_space)
+    }
+
+    pub fn create_image_data_with_settings(
+        width: c_int,
+        height: c_int,
+        color_space: PredefinedColorSpace,
+        storage_format: ImageDataStorageFormat,
+    ) -> ImageData {
+        ImageData::new_with_settings(width, height, color_space, storage_format)
+    }
+
     pub fn get_image_data(
         &mut self,
         sx: c_float,
         sy: c_float,
         sw: c_float,
         sh: c_float,
+    ) -> ImageData {
+        self.get_image_data_with_color_space(sx, sy, sw, sh, self.device.color_space)
+    }
+
+    /// Reads back pixels converted from the surface's color space into `color_space`.
+    pub fn get_image_data_with_color_space(
+        &mut self,
+        sx: c_float,
+        sy: c_float,
+        sw: c_float,
+        sh: c_float,
+        color_space: PredefinedColorSpace,
+    ) -> ImageData {
+        self.get_image_data_with_settings(
+            sx,
+            sy,
+            sw,
+            sh,
+            color_space,
+            ImageDataStorageFormat::Uint8,
+        )
+    }
+
+    /// Reads back pixels converted into `color_space` and stored with `storage_format`.
+    pub fn get_image_data_with_settings(
+        &mut self,
+        sx: c_float,
+        sy: c_float,
+        sw: c_float,
+        sh: c_float,
+        color_space: PredefinedColorSpace,
+        storage_format: ImageDataStorageFormat,
     ) -> ImageData {
         let info = ImageInfo::new(
             ISize::new(sw as i32, sh as i32),
-            ColorType::RGBA8888,
+            storage_format.color_type(),
             AlphaType::Unpremul,
-            None,
+            Some(color_space.to_skia()),
         );
-        let row_bytes = info.width() * 4;
-        let mut slice = vec![255u8; (row_bytes * info.height()) as usize];
-        let _ = self.surface.canvas().read_pixels(
+        let row_bytes = info.width() as usize * storage_format.bytes_per_pixel();
+        let image_data =
+            ImageData::new_with_settings(info.width(), info.height(), color_space, storage_format);
+        // pixels outside the surface are left as allocated, a failed read may leave any of them
+        // half written so start over from a fresh buffer
+        if !self.surface.canvas().read_pixels(
             &info,
-            slice.as_mut_slice(),
-            row_bytes as usize,
+            image_data.data_mut(),
+            row_bytes,
             IPoint::new(sx as i32, sy as i32),
-        );
-        let mut image_data = ImageData::new(info.width(), info.height());
-        image_data.set_data(slice);
+        ) {
+            return ImageData::new_with_settings(
+                info.width(),
+                info.height(),
+                color_space,
+                storage_format,
+            );
+        }
         image_data
     }
 
+    /// Writes the pixels directly, so as the canvas spec requires the shadow, global alpha,
+    /// composite operation, filter and clip are ignored.
     pub fn put_image_data(
         &mut self,
         data: &ImageData,
@@ -60,11 +120,11 @@ impl Context {
         if srect.is_empty() {
             info = ImageInfo::new(
                 ISize::new(data.width(), data.height()),
-                ColorType::RGBA8888,
+                data.storage_format().color_type(),
                 AlphaType::Unpremul,
-                None,
+                Some(data.color_space().to_skia()),
             );
-            row_bytes = (data.width() * 4) as usize;
+            row_bytes = data.width() as usize * data.storage_format().bytes_per_pixel();
         } else {
             if sw < 0.0 {
                 sx += sw;
@@ -88,12 +148,12 @@ impl Context {
 
             info = ImageInfo::new(
                 ISize::new(sw as i32, sh as i32),
-                ColorType::RGBA8888,
+                data.storage_format().color_type(),
                 AlphaType::Unpremul,
-                None,
+                Some(data.color_space().to_skia()),
             );
 
-            row_bytes = (sw * 4.0) as usize;
+            row_bytes = sw as usize * data.storage_format().bytes_per_pixel();
         }
         let _ = self.surface.canvas().write_pixels(
             &info,
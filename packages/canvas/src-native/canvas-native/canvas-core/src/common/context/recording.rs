@@ -0,0 +1,112 @@
+use std::ops::{Deref, DerefMut};
+use std::os::raw::c_float;
+
+use skia_safe::{Canvas, Picture, PictureRecorder, Rect, Surface};
+
+use crate::common::context::{Context, State};
+
+/// The drawing state of the context from before `begin_recording`, put back by `end_recording`.
+struct Recording {
+    recorder: PictureRecorder,
+    bounds: Rect,
+    state: State,
+    state_stack: Vec<State>,
+    enable_scaling: bool,
+}
+
+/// The surface a context draws to. While a recording is open `canvas` is the recording canvas,
+/// so every drawing call goes into the picture instead of the surface.
+pub(crate) struct ContextSurface {
+    surface: Surface,
+    recording: Option<Recording>,
+}
+
+impl ContextSurface {
+    pub(crate) fn canvas(&mut self) -> &mut Canvas {
+        match self
+            .recording
+            .as_mut()
+            .and_then(|recording| recording.recorder.recording_canvas())
+        {
+            Some(canvas) => canvas,
+            None => self.surface.canvas(),
+        }
+    }
+
+    pub(crate) fn is_recording(&self) -> bool {
+        self.recording.is_some()
+    }
+}
+
+impl From<Surface> for ContextSurface {
+    fn from(surface: Surface) -> Self {
+        Self {
+            surface,
+            recording: None,
+        }
+    }
+}
+
+/// Clones share the surface; an open recording stays with the original.
+impl Clone for ContextSurface {
+    fn clone(&self) -> Self {
+        Self::from(self.surface.clone())
+    }
+}
+
+impl Deref for ContextSurface {
+    type Target = Surface;
+
+    fn deref(&self) -> &Self::Target {
+        &self.surface
+    }
+}
+
+impl DerefMut for ContextSurface {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.surface
+    }
+}
+
+impl Context {
+    /// Starts recording the drawing calls that follow into a picture with a `width` by `height`
+    /// cull rect, instead of drawing them to the surface. The recording starts from the current
+    /// drawing settings with an identity transform, no clip and no saved states. Returns false,
+    /// doing nothing, if a recording is already open or the size is empty.
+    pub fn begin_recording(&mut self, width: c_float, height: c_float) -> bool {
+        if self.surface.is_recording() || !(width > 0.0 && height > 0.0) {
+            return false;
+        }
+        let bounds = Rect::from_wh(width, height);
+        let mut recorder = PictureRecorder::new();
+        recorder.begin_recording(bounds, None);
+        let mut state = self.state.clone();
+        state.clip_paths.clear();
+        self.surface.recording = Some(Recording {
+            recorder,
+            bounds,
+            state: std::mem::replace(&mut self.state, state),
+            state_stack: std::mem::take(&mut self.state_stack),
+            enable_scaling: self.enable_scaling,
+        });
+        // the picture is recorded in CSS pixels and scaled wherever it is drawn
+        self.enable_scaling = false;
+        true
+    }
+
+    /// Ends the recording begun with `begin_recording`, restores the drawing state from before
+    /// it and returns the picture. Returns `None`, doing nothing, if no recording is open or a
+    /// `save` or layer inside it has not been closed.
+    pub fn end_recording(&mut self) -> Option<Picture> {
+        if !self.state_stack.is_empty() {
+            return None;
+        }
+        let mut recording = self.surface.recording.take()?;
+        self.state = recording.state;
+        self.state_stack = recording.state_stack;
+        self.enable_scaling = recording.enable_scaling;
+        recording
+            .recorder
+            .finish_recording_as_picture(Some(&recording.bounds))
+    }
+}
@@ -1,13 +1,16 @@
 use std::os::raw::c_float;
 
-use skia_safe::Color;
+use skia_safe::{Canvas, Color4f, M44};
 
 use crate::common::context::Context;
+use crate::common::context::fill_and_stroke_styles::paint::Paint;
 
 impl Context {
+    /// Negative and non finite values are ignored.
     pub fn set_shadow_blur(&mut self, blur: c_float) {
-        // TODO ?
-        self.state.shadow_blur = blur;
+        if blur.is_finite() && blur >= 0.0 {
+            self.state.shadow_blur = blur;
+        }
     }
 
     pub fn shadow_blur(&self) -> c_float {
@@ -15,7 +18,9 @@ impl Context {
     }
 
     pub fn set_shadow_offset_x(&mut self, x: c_float) {
-        self.state.shadow_offset.x = x;
+        if x.is_finite() {
+            self.state.shadow_offset.x = x;
+        }
     }
 
     pub fn shadow_offset_x(&self) -> c_float {
@@ -23,18 +28,68 @@ impl Context {
     }
 
     pub fn set_shadow_offset_y(&mut self, y: c_float) {
-        self.state.shadow_offset.y = y;
+        if y.is_finite() {
+            self.state.shadow_offset.y = y;
+        }
     }
 
     pub fn shadow_offset_y(&self) -> c_float {
         self.state.shadow_offset.y
     }
 
-    pub fn set_shadow_color(&mut self, color: Color) {
+    pub fn set_shadow_color(&mut self, color: Color4f) {
         self.state.shadow_color = color;
     }
 
-    pub fn shadow_color(&self) -> Color {
+    pub fn shadow_color(&self) -> Color4f {
         self.state.shadow_color
     }
+
+    /// Shadows are drawn when the color is not fully transparent and either the blur or one of
+    /// the offsets is not 0.
+    pub(crate) fn shadow_visible(&self) -> bool {
+        self.state.shadow_color.a > 0.0
+            && (self.state.shadow_blur > 0.0
+                || self.state.shadow_offset.x != 0.0
+                || self.state.shadow_offset.y != 0.0)
+    }
+
+    /// Draws the shadow of whatever `draw` renders with `paint`, before the primitive itself is
+    /// drawn. As in the canvas spec, the offsets and the blur, with a sigma of half the shadow
+    /// blur, are in canvas pixels and unaffected by the current transform.
+    pub(crate) fn draw_shadow<F: FnOnce(&mut Canvas, &skia_safe::Paint)>(
+        &mut self,
+        paint: &skia_safe::Paint,
+        draw: F,
+    ) {
+        if !self.shadow_visible() {
+            return;
+        }
+        let density = if self.enable_scaling {
+            self.device.density
+        } else {
+            1.0
+        };
+        let offset = self.state.shadow_offset;
+        let sigma = self.state.shadow_blur / 2.0 * density;
+
+        let canvas = self.surface.canvas();
+        let matrix = canvas.local_to_device();
+        // the blur runs in the scaled space of the current transform, so undo its scale
+        let sigma = match matrix.to_m33().decompose_scale(None) {
+            Some(scale) if scale.width > 0.0 && scale.height > 0.0 => {
+                (sigma / scale.width, sigma / scale.height)
+            }
+            _ => (sigma, sigma),
+        };
+        let shadow_paint = Paint::shadow_paint(paint, self.state.shadow_color, sigma);
+
+        canvas.save();
+        canvas.set_matrix(&M44::concat(
+            &M44::translate(offset.x * density, offset.y * density, 0.0),
+            &matrix,
+        ));
+        draw(canvas, &shadow_paint);
+        canvas.restore();
+    }
 }
@@ -1,16 +1,41 @@
+use skia_safe::Color;
+
 use crate::common::context::Context;
+use crate::common::context::paths::path::Path;
 
 impl Context {
     pub fn save(&mut self) {
+        let mut stack = self.state.clone();
+        stack.transform = self.surface.canvas().local_to_device();
         self.surface.canvas().save();
-        let stack = self.state.clone();
         self.state_stack.push(stack);
+        self.state.clip_paths.clear();
     }
 
+    /// Does nothing when the last save was made by `begin_layer`, which is closed with
+    /// `end_layer` instead.
     pub fn restore(&mut self) {
+        match self.state_stack.last() {
+            Some(state) if !state.is_layer => {}
+            _ => return,
+        }
         if let Some(state) = self.state_stack.pop() {
             self.surface.canvas().restore();
             self.state = state;
         }
     }
+
+    /// Returns the context to its initial state as in the canvas spec: the bitmap is cleared to
+    /// transparent black, and the path, the saved states, the transform, the clip and every
+    /// other drawing setting are reset. Open layers are discarded without being drawn.
+    pub fn reset(&mut self) {
+        let canvas = self.surface.canvas();
+        canvas.restore_to_count(1);
+        canvas.reset_matrix();
+        canvas.clear(Color::TRANSPARENT);
+        self.state_stack.clear();
+        self.path = Path::default();
+        self.reset_state();
+        self.flush();
+    }
 }
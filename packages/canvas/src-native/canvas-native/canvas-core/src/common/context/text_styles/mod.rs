@@ -2,12 +2,14 @@ use crate::{
     common::context::Context,
     common::context::text_styles::text_align::TextAlign,
     common::context::text_styles::text_baseline::TextBaseLine, common::context::text_styles::text_direction::TextDirection,
+    common::context::text_styles::text_rendering::{TextEdging, TextHinting, TextRendering},
 };
 
 
 pub mod text_align;
 pub mod text_baseline;
 pub mod text_direction;
+pub mod text_rendering;
 
 impl Context {
     pub fn set_direction(&mut self, direction: TextDirection) {
@@ -26,6 +28,22 @@ impl Context {
         self.state.font.get_font_details()
     }
 
+    pub fn set_font_variation_settings(&mut self, settings: &str) {
+        self.state.font.set_variation_settings(settings);
+    }
+
+    pub fn font_variation_settings(&self) -> &str {
+        self.state.font.get_variation_settings()
+    }
+
+    pub fn set_font_feature_settings(&mut self, settings: &str) {
+        self.state.font.set_feature_settings(settings);
+    }
+
+    pub fn font_feature_settings(&self) -> &str {
+        self.state.font.get_feature_settings()
+    }
+
     pub fn set_text_align(&mut self, align: TextAlign) {
         self.state.text_align = align;
     }
@@ -41,4 +59,55 @@ impl Context {
     pub fn text_baseline(&self) -> TextBaseLine {
         self.state.text_baseline
     }
+
+    /// Applies the rendering preset for a `textRendering` value, replacing any
+    /// individually set edging, hinting or positioning options.
+    pub fn set_text_rendering(&mut self, rendering: TextRendering) {
+        self.state.text_rendering = rendering;
+        *self.state.font.rendering_mut() = rendering.into();
+    }
+
+    pub fn text_rendering(&self) -> TextRendering {
+        self.state.text_rendering
+    }
+
+    pub fn set_text_edging(&mut self, edging: TextEdging) {
+        self.state.font.rendering_mut().edging = edging;
+    }
+
+    pub fn text_edging(&self) -> TextEdging {
+        self.state.font.rendering().edging
+    }
+
+    pub fn set_text_hinting(&mut self, hinting: TextHinting) {
+        self.state.font.rendering_mut().hinting = hinting;
+    }
+
+    pub fn text_hinting(&self) -> TextHinting {
+        self.state.font.rendering().hinting
+    }
+
+    pub fn set_text_subpixel_positioning(&mut self, value: bool) {
+        self.state.font.rendering_mut().subpixel = value;
+    }
+
+    pub fn text_subpixel_positioning(&self) -> bool {
+        self.state.font.rendering().subpixel
+    }
+
+    pub fn set_text_embedded_bitmaps(&mut self, value: bool) {
+        self.state.font.rendering_mut().embedded_bitmaps = value;
+    }
+
+    pub fn text_embedded_bitmaps(&self) -> bool {
+        self.state.font.rendering().embedded_bitmaps
+    }
+
+    pub fn set_text_linear_metrics(&mut self, value: bool) {
+        self.state.font.rendering_mut().linear_metrics = value;
+    }
+
+    pub fn text_linear_metrics(&self) -> bool {
+        self.state.font.rendering().linear_metrics
+    }
 }
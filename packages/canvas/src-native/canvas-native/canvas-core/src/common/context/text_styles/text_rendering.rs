@@ -0,0 +1,185 @@
+use skia_safe::font::Edging;
+use skia_safe::FontHinting;
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum TextRendering {
+    Auto = 0,
+    OptimizeSpeed = 1,
+    OptimizeLegibility = 2,
+    GeometricPrecision = 3,
+}
+
+impl Default for TextRendering {
+    fn default() -> Self {
+        Self::Auto
+    }
+}
+
+impl From<i32> for TextRendering {
+    fn from(value: i32) -> Self {
+        match value {
+            0 => Self::Auto,
+            1 => Self::OptimizeSpeed,
+            2 => Self::OptimizeLegibility,
+            3 => Self::GeometricPrecision,
+            _ => Self::Auto,
+        }
+    }
+}
+
+impl Into<i32> for TextRendering {
+    fn into(self) -> i32 {
+        match self {
+            TextRendering::Auto => 0,
+            TextRendering::OptimizeSpeed => 1,
+            TextRendering::OptimizeLegibility => 2,
+            TextRendering::GeometricPrecision => 3,
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum TextEdging {
+    Alias = 0,
+    AntiAlias = 1,
+    SubpixelAntiAlias = 2,
+}
+
+impl From<i32> for TextEdging {
+    fn from(value: i32) -> Self {
+        match value {
+            0 => Self::Alias,
+            1 => Self::AntiAlias,
+            2 => Self::SubpixelAntiAlias,
+            _ => Self::AntiAlias,
+        }
+    }
+}
+
+impl Into<i32> for TextEdging {
+    fn into(self) -> i32 {
+        match self {
+            TextEdging::Alias => 0,
+            TextEdging::AntiAlias => 1,
+            TextEdging::SubpixelAntiAlias => 2,
+        }
+    }
+}
+
+impl Into<Edging> for TextEdging {
+    fn into(self) -> Edging {
+        match self {
+            TextEdging::Alias => Edging::Alias,
+            TextEdging::AntiAlias => Edging::AntiAlias,
+            TextEdging::SubpixelAntiAlias => Edging::SubpixelAntiAlias,
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum TextHinting {
+    None = 0,
+    Slight = 1,
+    Normal = 2,
+    Full = 3,
+}
+
+impl From<i32> for TextHinting {
+    fn from(value: i32) -> Self {
+        match value {
+            0 => Self::None,
+            1 => Self::Slight,
+            2 => Self::Normal,
+            3 => Self::Full,
+            _ => Self::Normal,
+        }
+    }
+}
+
+impl Into<i32> for TextHinting {
+    fn into(self) -> i32 {
+        match self {
+            TextHinting::None => 0,
+            TextHinting::Slight => 1,
+            TextHinting::Normal => 2,
+            TextHinting::Full => 3,
+        }
+    }
+}
+
+impl Into<FontHinting> for TextHinting {
+    fn into(self) -> FontHinting {
+        match self {
+            TextHinting::None => FontHinting::None,
+            TextHinting::Slight => FontHinting::Slight,
+            TextHinting::Normal => FontHinting::Normal,
+            TextHinting::Full => FontHinting::Full,
+        }
+    }
+}
+
+/// Glyph rasterization settings applied to the Skia font used for drawing and measuring text.
+#[derive(Copy, Clone, Debug)]
+pub struct TextRenderingOptions {
+    pub edging: TextEdging,
+    pub hinting: TextHinting,
+    pub subpixel: bool,
+    pub embedded_bitmaps: bool,
+    pub linear_metrics: bool,
+}
+
+impl Default for TextRenderingOptions {
+    fn default() -> Self {
+        TextRendering::Auto.into()
+    }
+}
+
+impl From<TextRendering> for TextRenderingOptions {
+    fn from(value: TextRendering) -> Self {
+        match value {
+            // Skia's own font defaults
+            TextRendering::Auto => Self {
+                edging: TextEdging::AntiAlias,
+                hinting: TextHinting::Normal,
+                subpixel: false,
+                embedded_bitmaps: false,
+                linear_metrics: false,
+            },
+            TextRendering::OptimizeSpeed => Self {
+                edging: TextEdging::AntiAlias,
+                hinting: TextHinting::Full,
+                subpixel: false,
+                embedded_bitmaps: true,
+                linear_metrics: false,
+            },
+            TextRendering::OptimizeLegibility => Self {
+                edging: TextEdging::SubpixelAntiAlias,
+                hinting: TextHinting::Slight,
+                subpixel: true,
+                embedded_bitmaps: false,
+                linear_metrics: false,
+            },
+            // unhinted outlines with fractional positions scale and animate smoothly
+            TextRendering::GeometricPrecision => Self {
+                edging: TextEdging::AntiAlias,
+                hinting: TextHinting::None,
+                subpixel: true,
+                embedded_bitmaps: false,
+                linear_metrics: true,
+            },
+        }
+    }
+}
+
+impl TextRenderingOptions {
+    pub(crate) fn apply(&self, font: &mut skia_safe::Font) {
+        font.set_edging(self.edging.into())
+            .set_hinting(self.hinting.into())
+            .set_subpixel(self.subpixel)
+            .set_embedded_bitmaps(self.embedded_bitmaps)
+            .set_linear_metrics(self.linear_metrics);
+    }
+}
@@ -4,9 +4,22 @@ use std::os::raw::c_float;
 use skia_safe::{M44, Matrix, Point};
 
 use crate::common::context::Context;
+use crate::common::context::matrix::Matrix as DOMMatrix;
+
+fn is_finite(matrix: &M44) -> bool {
+    let mut values = [0f32; 16];
+    matrix.get_col_major(&mut values);
+    values.iter().all(|value| value.is_finite())
+}
 
 impl Context {
-    pub fn get_transform(&mut self) -> Matrix {
+    /// The full 4x4 transform, including any perspective and z components.
+    pub fn get_transform(&mut self) -> DOMMatrix {
+        DOMMatrix::from_m44(self.surface.canvas().local_to_device())
+    }
+
+    /// The transform flattened to 3x3, dropping the z row and column.
+    pub(crate) fn get_transform_3x3(&mut self) -> Matrix {
         self.surface.canvas().local_to_device_as_3x3()
     }
 
@@ -36,11 +49,13 @@ impl Context {
         self.surface.canvas().concat(&transform);
     }
 
-    pub fn transform_with_matrix(&mut self, matrix: &Matrix) {
-        let mut current = self.surface.canvas().local_to_device_as_3x3();
-        current.pre_concat(matrix);
-        let m = M44::from(&current);
-        self.surface.canvas().set_matrix(&m);
+    /// Multiplies the current transform by a 4x4 matrix. Matrices with non finite values are
+    /// ignored.
+    pub fn transform_with_matrix(&mut self, matrix: &DOMMatrix) {
+        if !is_finite(&matrix.matrix) {
+            return;
+        }
+        self.surface.canvas().concat_44(&matrix.matrix);
     }
 
     pub fn set_transform(
@@ -58,11 +73,13 @@ impl Context {
         self.surface.canvas().set_matrix(&m44);
     }
 
-    pub fn set_transform_matrix(&mut self, matrix: &Matrix) {
-        self.surface.canvas().reset_matrix();
-        let matrix = matrix.clone();
-        let m44 = M44::from(matrix);
-        self.surface.canvas().set_matrix(&m44);
+    /// Replaces the transform with a 4x4 matrix, keeping its perspective and z components.
+    /// Matrices with non finite values are ignored.
+    pub fn set_transform_matrix(&mut self, matrix: &DOMMatrix) {
+        if !is_finite(&matrix.matrix) {
+            return;
+        }
+        self.surface.canvas().set_matrix(&matrix.matrix);
     }
 
     pub fn reset_transform(&mut self) {
@@ -7,6 +7,7 @@ pub enum PaintStyleValueType {
     PaintStyleValueTypeColor = 0,
     PaintStyleValueTypeGradient = 1,
     PaintStyleValueTypePattern = 2,
+    PaintStyleValueTypeRuntimeEffect = 3,
 }
 
 impl Into<i32> for PaintStyleValueType {
@@ -15,6 +16,7 @@ impl Into<i32> for PaintStyleValueType {
             PaintStyleValueType::PaintStyleValueTypeColor => 0,
             PaintStyleValueType::PaintStyleValueTypeGradient => 1,
             PaintStyleValueType::PaintStyleValueTypePattern => 2,
+            PaintStyleValueType::PaintStyleValueTypeRuntimeEffect => 3,
         }
     }
 }
@@ -25,6 +27,7 @@ impl PaintStyleValueType {
             0 => Some(PaintStyleValueType::PaintStyleValueTypeColor),
             1 => Some(PaintStyleValueType::PaintStyleValueTypeGradient),
             2 => Some(PaintStyleValueType::PaintStyleValueTypePattern),
+            3 => Some(PaintStyleValueType::PaintStyleValueTypeRuntimeEffect),
             _ => None,
         }
     }
@@ -1,15 +1,46 @@
-use skia_safe::Color;
+use std::f64::consts::PI;
 
+use skia_safe::{Color, Color4f};
+
+/// Parses a CSS Color Level 4 value into an 8-bit sRGB color.
+/// `currentcolor` resolves to opaque black, as it does for gradient stops.
 pub(crate) fn parse_color(value: &str) -> Option<Color> {
-    match value.parse::<css_color_parser::Color>() {
-        Ok(color) => Some(Color::from_argb(
-            (color.a * 255.0) as u8,
-            color.r,
-            color.g,
-            color.b,
-        )),
-        _ => None,
+    parse_color_with_current_color(value, Color::BLACK)
+}
+
+/// Parses a CSS Color Level 4 value into an 8-bit sRGB color, resolving `currentcolor`
+/// against `current_color`. Colors outside of sRGB are clipped to the sRGB gamut.
+pub(crate) fn parse_color_with_current_color(value: &str, current_color: Color) -> Option<Color> {
+    parse_color_4f(value, current_color).map(|color| color.to_color())
+}
+
+/// Parses a CSS Color Level 4 value into extended sRGB, keeping components outside of
+/// the sRGB gamut so wide gamut destinations can use them.
+pub(crate) fn parse_color_4f(value: &str, current_color: Color) -> Option<Color4f> {
+    let value = value.trim().to_ascii_lowercase();
+    if value.is_empty() {
+        return None;
+    }
+
+    if value == "currentcolor" {
+        return Some(Color4f::from(current_color));
+    }
+
+    if value == "transparent" {
+        return Some(Color4f::new(0.0, 0.0, 0.0, 0.0));
+    }
+
+    if let Some(hex) = value.strip_prefix('#') {
+        return parse_hex(hex).map(Color4f::from);
+    }
+
+    if let Some(open) = value.find('(') {
+        let body = value[open + 1..].strip_suffix(')')?;
+        let name = value[..open].trim_end();
+        return parse_function(name, body);
     }
+
+    named_color(&value).map(Color4f::from)
 }
 
 fn to_hex(value: u8) -> String {
@@ -20,6 +51,22 @@ fn to_hex(value: u8) -> String {
     hex
 }
 
+/// Serializes an alpha byte as the shortest decimal that maps back to the same byte.
+fn to_alpha_string(alpha: u8) -> String {
+    for precision in 1..=6 {
+        let value = format!("{:.*}", precision, alpha as f32 / 255.0);
+        let value = value.trim_end_matches('0').trim_end_matches('.');
+        if let Ok(parsed) = value.parse::<f32>() {
+            if (parsed * 255.0).round() as u8 == alpha {
+                return value.to_string();
+            }
+        }
+    }
+    (alpha as f32 / 255.0).to_string()
+}
+
+/// Serializes a color the way the canvas spec requires: `#rrggbb` when opaque, otherwise
+/// `rgba(r, g, b, a)`.
 pub(crate) fn to_parsed_color(color: Color) -> String {
     if color.a() == 255 {
         format!(
@@ -30,11 +77,675 @@ pub(crate) fn to_parsed_color(color: Color) -> String {
         )
     } else {
         format!(
-            "rgba({},{},{},{})",
+            "rgba({}, {}, {}, {})",
             color.r(),
             color.g(),
             color.b(),
-            (color.a() as f32 / 255.0)
+            to_alpha_string(color.a())
         )
     }
 }
+
+#[derive(Copy, Clone, Debug)]
+enum Component {
+    Number(f64),
+    Percentage(f64),
+    Angle(f64),
+    None,
+}
+
+impl Component {
+    fn parse(value: &str) -> Option<Self> {
+        if value == "none" {
+            return Some(Component::None);
+        }
+        if let Some(value) = value.strip_suffix('%') {
+            return parse_number(value).map(Component::Percentage);
+        }
+        for (unit, to_degrees) in [
+            ("deg", 1.0),
+            ("grad", 0.9),
+            ("rad", 180.0 / PI),
+            ("turn", 360.0),
+        ] {
+            if let Some(value) = value.strip_suffix(unit) {
+                return parse_number(value).map(|v| Component::Angle(v * to_degrees));
+            }
+        }
+        parse_number(value).map(Component::Number)
+    }
+
+    /// Resolves a number or percentage, where `percent_reference` is the value of 100%.
+    fn number(self, percent_reference: f64) -> Option<f64> {
+        match self {
+            Component::Number(value) => Some(value),
+            Component::Percentage(value) => Some(value / 100.0 * percent_reference),
+            Component::None => Some(0.0),
+            Component::Angle(_) => None,
+        }
+    }
+
+    fn hue(self) -> Option<f64> {
+        match self {
+            Component::Number(value) | Component::Angle(value) => Some(value),
+            Component::None => Some(0.0),
+            Component::Percentage(_) => None,
+        }
+    }
+
+    fn alpha(self) -> Option<f64> {
+        self.number(1.0).map(|value| value.max(0.0).min(1.0))
+    }
+}
+
+fn parse_number(value: &str) -> Option<f64> {
+    if value.is_empty() {
+        return None;
+    }
+    value.parse::<f64>().ok().filter(|value| value.is_finite())
+}
+
+/// The components of a color function, along with whether the legacy comma syntax was used.
+struct Arguments {
+    components: Vec<Component>,
+    alpha: Option<Component>,
+    legacy: bool,
+}
+
+fn parse_arguments(body: &str) -> Option<Arguments> {
+    let body = body.trim();
+    if body.contains(',') {
+        if body.contains('/') {
+            return None;
+        }
+        let mut components = body
+            .split(',')
+            .map(|value| {
+                let value = value.trim();
+                if value == "none" {
+                    // `none` is only valid in the modern syntax
+                    None
+                } else {
+                    Component::parse(value)
+                }
+            })
+            .collect::<Option<Vec<_>>>()?;
+        let alpha = match components.len() {
+            3 => None,
+            4 => components.pop(),
+            _ => return None,
+        };
+        return Some(Arguments {
+            components,
+            alpha,
+            legacy: true,
+        });
+    }
+
+    let mut parts = body.splitn(2, '/');
+    let components = parts
+        .next()?
+        .split_whitespace()
+        .map(Component::parse)
+        .collect::<Option<Vec<_>>>()?;
+    let alpha = match parts.next() {
+        Some(alpha) => {
+            let mut alpha = alpha.split_whitespace();
+            let value = Component::parse(alpha.next()?)?;
+            if alpha.next().is_some() {
+                return None;
+            }
+            Some(value)
+        }
+        None => None,
+    };
+    Some(Arguments {
+        components,
+        alpha,
+        legacy: false,
+    })
+}
+
+fn parse_function(name: &str, body: &str) -> Option<Color4f> {
+    if name == "color" {
+        return parse_color_function(body);
+    }
+
+    let arguments = parse_arguments(body)?;
+    if arguments.components.len() != 3 {
+        return None;
+    }
+    let alpha = match arguments.alpha {
+        Some(alpha) => alpha.alpha()? as f32,
+        None => 1.0,
+    };
+    let [c0, c1, c2] = [
+        arguments.components[0],
+        arguments.components[1],
+        arguments.components[2],
+    ];
+
+    let (r, g, b) = match name {
+        "rgb" | "rgba" => {
+            if arguments.legacy {
+                // legacy syntax requires all numbers or all percentages
+                let percentages = arguments
+                    .components
+                    .iter()
+                    .all(|c| matches!(c, Component::Percentage(_)));
+                let numbers = arguments
+                    .components
+                    .iter()
+                    .all(|c| matches!(c, Component::Number(_)));
+                if !percentages && !numbers {
+                    return None;
+                }
+            }
+            (
+                c0.number(255.0)? / 255.0,
+                c1.number(255.0)? / 255.0,
+                c2.number(255.0)? / 255.0,
+            )
+        }
+        "hsl" | "hsla" => {
+            if arguments.legacy
+                && (!matches!(c1, Component::Percentage(_))
+                    || !matches!(c2, Component::Percentage(_)))
+            {
+                return None;
+            }
+            hsl_to_srgb(c0.hue()?, c1.number(100.0)? / 100.0, c2.number(100.0)? / 100.0)
+        }
+        "hwb" if !arguments.legacy => {
+            hwb_to_srgb(c0.hue()?, c1.number(100.0)? / 100.0, c2.number(100.0)? / 100.0)
+        }
+        "lab" if !arguments.legacy => {
+            let xyz = lab_to_xyz_d50(c0.number(100.0)?.max(0.0), c1.number(125.0)?, c2.number(125.0)?);
+            gamma_encode_srgb(xyz_d65_to_linear_srgb(d50_to_d65(xyz)))
+        }
+        "lch" if !arguments.legacy => {
+            let (a, b) = polar_to_rectangular(c1.number(150.0)?.max(0.0), c2.hue()?);
+            let xyz = lab_to_xyz_d50(c0.number(100.0)?.max(0.0), a, b);
+            gamma_encode_srgb(xyz_d65_to_linear_srgb(d50_to_d65(xyz)))
+        }
+        "oklab" if !arguments.legacy => gamma_encode_srgb(oklab_to_linear_srgb((
+            c0.number(1.0)?.max(0.0),
+            c1.number(0.4)?,
+            c2.number(0.4)?,
+        ))),
+        "oklch" if !arguments.legacy => {
+            let (a, b) = polar_to_rectangular(c1.number(0.4)?.max(0.0), c2.hue()?);
+            gamma_encode_srgb(oklab_to_linear_srgb((c0.number(1.0)?.max(0.0), a, b)))
+        }
+        _ => return None,
+    };
+
+    Some(Color4f::new(r as f32, g as f32, b as f32, alpha))
+}
+
+fn parse_color_function(body: &str) -> Option<Color4f> {
+    let body = body.trim();
+    let space_end = body.find(char::is_whitespace)?;
+    let space = &body[..space_end];
+    let arguments = parse_arguments(&body[space_end..])?;
+    if arguments.legacy || arguments.components.len() != 3 {
+        return None;
+    }
+    let alpha = match arguments.alpha {
+        Some(alpha) => alpha.alpha()? as f32,
+        None => 1.0,
+    };
+    let mut values = [0f64; 3];
+    for (value, component) in values.iter_mut().zip(arguments.components.iter()) {
+        if let Component::Angle(_) = component {
+            return None;
+        }
+        *value = component.number(1.0)?;
+    }
+    let rgb = color_space_to_srgb(space, (values[0], values[1], values[2]))?;
+    Some(Color4f::new(rgb.0 as f32, rgb.1 as f32, rgb.2 as f32, alpha))
+}
+
+/// Converts components of a predefined `color()` space into gamma encoded extended sRGB.
+pub(crate) fn color_space_to_srgb(space: &str, value: (f64, f64, f64)) -> Option<(f64, f64, f64)> {
+    let linear = match space {
+        "srgb" => return Some(value),
+        "srgb-linear" => value,
+        "display-p3" => multiply(&P3_TO_SRGB, map(value, srgb_to_linear)),
+        "a98-rgb" => xyz_d65_to_linear_srgb(multiply(
+            &A98_TO_XYZ_D65,
+            map(value, |c| c.signum() * c.abs().powf(563.0 / 256.0)),
+        )),
+        "prophoto-rgb" => xyz_d65_to_linear_srgb(d50_to_d65(multiply(
+            &PROPHOTO_TO_XYZ_D50,
+            map(value, |c| {
+                if c.abs() <= 16.0 / 512.0 {
+                    c / 16.0
+                } else {
+                    c.signum() * c.abs().powf(1.8)
+                }
+            }),
+        ))),
+        "rec2020" => xyz_d65_to_linear_srgb(multiply(
+            &REC2020_TO_XYZ_D65,
+            map(value, |c| {
+                const ALPHA: f64 = 1.09929682680944;
+                const BETA: f64 = 0.018053968510807;
+                if c.abs() < BETA * 4.5 {
+                    c / 4.5
+                } else {
+                    c.signum() * ((c.abs() + ALPHA - 1.0) / ALPHA).powf(1.0 / 0.45)
+                }
+            }),
+        )),
+        "xyz" | "xyz-d65" => xyz_d65_to_linear_srgb(value),
+        "xyz-d50" => xyz_d65_to_linear_srgb(d50_to_d65(value)),
+        _ => return None,
+    };
+    Some(gamma_encode_srgb(linear))
+}
+
+fn parse_hex(hex: &str) -> Option<Color> {
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let digit = |i: usize| u8::from_str_radix(&hex[i..i + 1], 16).ok().map(|v| v * 17);
+    let byte = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    match hex.len() {
+        3 => Some(Color::from_argb(255, digit(0)?, digit(1)?, digit(2)?)),
+        4 => Some(Color::from_argb(digit(3)?, digit(0)?, digit(1)?, digit(2)?)),
+        6 => Some(Color::from_argb(255, byte(0)?, byte(2)?, byte(4)?)),
+        8 => Some(Color::from_argb(byte(6)?, byte(0)?, byte(2)?, byte(4)?)),
+        _ => None,
+    }
+}
+
+fn map(value: (f64, f64, f64), f: impl Fn(f64) -> f64) -> (f64, f64, f64) {
+    (f(value.0), f(value.1), f(value.2))
+}
+
+fn multiply(matrix: &[[f64; 3]; 3], value: (f64, f64, f64)) -> (f64, f64, f64) {
+    let v = [value.0, value.1, value.2];
+    let row = |r: &[f64; 3]| r[0] * v[0] + r[1] * v[1] + r[2] * v[2];
+    (row(&matrix[0]), row(&matrix[1]), row(&matrix[2]))
+}
+
+pub(crate) fn srgb_to_linear(c: f64) -> f64 {
+    if c.abs() <= 0.04045 {
+        c / 12.92
+    } else {
+        c.signum() * ((c.abs() + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+pub(crate) fn linear_to_srgb(c: f64) -> f64 {
+    if c.abs() <= 0.0031308 {
+        c * 12.92
+    } else {
+        c.signum() * (1.055 * c.abs().powf(1.0 / 2.4) - 0.055)
+    }
+}
+
+pub(crate) fn gamma_encode_srgb(value: (f64, f64, f64)) -> (f64, f64, f64) {
+    map(value, linear_to_srgb)
+}
+
+pub(crate) fn gamma_decode_srgb(value: (f64, f64, f64)) -> (f64, f64, f64) {
+    map(value, srgb_to_linear)
+}
+
+fn polar_to_rectangular(chroma: f64, hue: f64) -> (f64, f64) {
+    let hue = hue.to_radians();
+    (chroma * hue.cos(), chroma * hue.sin())
+}
+
+fn rectangular_to_polar(a: f64, b: f64) -> (f64, f64) {
+    let mut hue = b.atan2(a).to_degrees();
+    if hue < 0.0 {
+        hue += 360.0;
+    }
+    ((a * a + b * b).sqrt(), hue)
+}
+
+fn hsl_to_srgb(hue: f64, saturation: f64, lightness: f64) -> (f64, f64, f64) {
+    let saturation = saturation.max(0.0).min(1.0);
+    let lightness = lightness.max(0.0).min(1.0);
+    let hue = hue.rem_euclid(360.0);
+    let f = |n: f64| {
+        let k = (n + hue / 30.0) % 12.0;
+        let a = saturation * lightness.min(1.0 - lightness);
+        lightness - a * (k - 3.0).min(9.0 - k).min(1.0).max(-1.0)
+    };
+    (f(0.0), f(8.0), f(4.0))
+}
+
+fn hwb_to_srgb(hue: f64, whiteness: f64, blackness: f64) -> (f64, f64, f64) {
+    let whiteness = whiteness.max(0.0).min(1.0);
+    let blackness = blackness.max(0.0).min(1.0);
+    if whiteness + blackness >= 1.0 {
+        let gray = whiteness / (whiteness + blackness);
+        return (gray, gray, gray);
+    }
+    let rgb = hsl_to_srgb(hue, 1.0, 0.5);
+    let scale = 1.0 - whiteness - blackness;
+    map(rgb, |c| c * scale + whiteness)
+}
+
+const D50_WHITE: (f64, f64, f64) = (0.3457 / 0.3585, 1.0, (1.0 - 0.3457 - 0.3585) / 0.3585);
+
+pub(crate) fn lab_to_xyz_d50(l: f64, a: f64, b: f64) -> (f64, f64, f64) {
+    const KAPPA: f64 = 24389.0 / 27.0;
+    const EPSILON: f64 = 216.0 / 24389.0;
+    let fy = (l + 16.0) / 116.0;
+    let fx = a / 500.0 + fy;
+    let fz = fy - b / 200.0;
+    let x = if fx.powi(3) > EPSILON {
+        fx.powi(3)
+    } else {
+        (116.0 * fx - 16.0) / KAPPA
+    };
+    let y = if l > KAPPA * EPSILON {
+        fy.powi(3)
+    } else {
+        l / KAPPA
+    };
+    let z = if fz.powi(3) > EPSILON {
+        fz.powi(3)
+    } else {
+        (116.0 * fz - 16.0) / KAPPA
+    };
+    (x * D50_WHITE.0, y * D50_WHITE.1, z * D50_WHITE.2)
+}
+
+pub(crate) fn xyz_d50_to_lab(xyz: (f64, f64, f64)) -> (f64, f64, f64) {
+    const KAPPA: f64 = 24389.0 / 27.0;
+    const EPSILON: f64 = 216.0 / 24389.0;
+    let f = |v: f64| {
+        if v > EPSILON {
+            v.cbrt()
+        } else {
+            (KAPPA * v + 16.0) / 116.0
+        }
+    };
+    let fx = f(xyz.0 / D50_WHITE.0);
+    let fy = f(xyz.1 / D50_WHITE.1);
+    let fz = f(xyz.2 / D50_WHITE.2);
+    (116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz))
+}
+
+pub(crate) fn lab_to_lch(lab: (f64, f64, f64)) -> (f64, f64, f64) {
+    let (c, h) = rectangular_to_polar(lab.1, lab.2);
+    (lab.0, c, h)
+}
+
+pub(crate) fn lch_to_lab(lch: (f64, f64, f64)) -> (f64, f64, f64) {
+    let (a, b) = polar_to_rectangular(lch.1, lch.2);
+    (lch.0, a, b)
+}
+
+pub(crate) fn d50_to_d65(xyz: (f64, f64, f64)) -> (f64, f64, f64) {
+    multiply(&D50_TO_D65, xyz)
+}
+
+pub(crate) fn d65_to_d50(xyz: (f64, f64, f64)) -> (f64, f64, f64) {
+    multiply(&D65_TO_D50, xyz)
+}
+
+pub(crate) fn xyz_d65_to_linear_srgb(xyz: (f64, f64, f64)) -> (f64, f64, f64) {
+    multiply(&XYZ_D65_TO_SRGB, xyz)
+}
+
+pub(crate) fn linear_srgb_to_xyz_d65(rgb: (f64, f64, f64)) -> (f64, f64, f64) {
+    multiply(&SRGB_TO_XYZ_D65, rgb)
+}
+
+pub(crate) fn oklab_to_linear_srgb(lab: (f64, f64, f64)) -> (f64, f64, f64) {
+    let lms = multiply(&OKLAB_TO_LMS, lab);
+    multiply(&LMS_TO_SRGB, map(lms, |c| c * c * c))
+}
+
+pub(crate) fn linear_srgb_to_oklab(rgb: (f64, f64, f64)) -> (f64, f64, f64) {
+    let lms = multiply(&SRGB_TO_LMS, rgb);
+    multiply(&LMS_TO_OKLAB, map(lms, f64::cbrt))
+}
+
+pub(crate) fn linear_srgb_to_linear_p3(rgb: (f64, f64, f64)) -> (f64, f64, f64) {
+    multiply(&SRGB_TO_P3, rgb)
+}
+
+pub(crate) fn linear_p3_to_linear_srgb(rgb: (f64, f64, f64)) -> (f64, f64, f64) {
+    multiply(&P3_TO_SRGB, rgb)
+}
+
+const SRGB_TO_XYZ_D65: [[f64; 3]; 3] = [
+    [0.41239079926595934, 0.357584339383878, 0.1804807884018343],
+    [0.21263900587151027, 0.715168678767756, 0.07219231536073371],
+    [0.01933081871559182, 0.11919477979462598, 0.9505321522496607],
+];
+
+const XYZ_D65_TO_SRGB: [[f64; 3]; 3] = [
+    [3.2409699419045226, -1.537383177570094, -0.4986107602930034],
+    [-0.9692436362808796, 1.8759675015077202, 0.04155505740717559],
+    [0.05563007969699366, -0.20397695888897652, 1.0569715142428786],
+];
+
+const D50_TO_D65: [[f64; 3]; 3] = [
+    [0.955473421488075, -0.02309845494876471, 0.06325924320057072],
+    [-0.0283697093338637, 1.0099953980813041, 0.021041441191917323],
+    [0.012314014864481998, -0.020507649298898964, 1.330365926242124],
+];
+
+const D65_TO_D50: [[f64; 3]; 3] = [
+    [1.0479297925449969, 0.022946870601609652, -0.05019226628920524],
+    [0.02962780877005599, 0.9904344267538799, -0.017073799063418826],
+    [-0.009243040646204504, 0.015055191490298152, 0.7518742814281371],
+];
+
+const P3_TO_SRGB: [[f64; 3]; 3] = [
+    [1.2249401762805598, -0.22494017628055996, 0.0],
+    [-0.042056954709688163, 1.042056954709688, 0.0],
+    [-0.019637554590334432, -0.07863604555063188, 1.0982736001409663],
+];
+
+const SRGB_TO_P3: [[f64; 3]; 3] = [
+    [0.8224621724963226, 0.17753782750367746, 0.0],
+    [0.033194199167244055, 0.9668058008327559, 0.0],
+    [0.01708263072112004, 0.07239744066396345, 0.9105199286149165],
+];
+
+const A98_TO_XYZ_D65: [[f64; 3]; 3] = [
+    [0.5766690429101305, 0.1855582379065463, 0.1882286462349947],
+    [0.29734497525053605, 0.6273635662554661, 0.07529145849399788],
+    [0.02703136138641234, 0.07068885253582723, 0.9913375368376388],
+];
+
+const PROPHOTO_TO_XYZ_D50: [[f64; 3]; 3] = [
+    [0.7977666449006423, 0.13518129740053308, 0.0313477341283922],
+    [0.2880748288194013, 0.711835234241873, 0.00008993693872564],
+    [0.0, 0.0, 0.8251046025104602],
+];
+
+const REC2020_TO_XYZ_D65: [[f64; 3]; 3] = [
+    [0.6369580483012914, 0.14461690358620832, 0.1688809751641721],
+    [0.2627002120112671, 0.6779980715188708, 0.05930171646986196],
+    [0.0, 0.028072693049087428, 1.060985057710791],
+];
+
+const OKLAB_TO_LMS: [[f64; 3]; 3] = [
+    [1.0, 0.3963377773761749, 0.2158037573099136],
+    [1.0, -0.1055613458156586, -0.0638541728258133],
+    [1.0, -0.0894841775298119, -1.2914855480194092],
+];
+
+const LMS_TO_SRGB: [[f64; 3]; 3] = [
+    [4.0767416621, -3.3077115913, 0.2309699292],
+    [-1.2684380046, 2.6097574011, -0.3413193965],
+    [-0.0041960863, -0.7034186147, 1.7076147010],
+];
+
+const SRGB_TO_LMS: [[f64; 3]; 3] = [
+    [0.4122214708, 0.5363325363, 0.0514459929],
+    [0.2119034982, 0.6806995451, 0.1073969566],
+    [0.0883024619, 0.2817188376, 0.6299787005],
+];
+
+const LMS_TO_OKLAB: [[f64; 3]; 3] = [
+    [0.2104542553, 0.7936177850, -0.0040720468],
+    [1.9779984951, -2.4285922050, 0.4505937099],
+    [0.0259040371, 0.7827717662, -0.8086757660],
+];
+
+fn named_color(name: &str) -> Option<Color> {
+    let rgb: u32 = match name {
+        "aliceblue" => 0xf0f8ff,
+        "antiquewhite" => 0xfaebd7,
+        "aqua" => 0x00ffff,
+        "aquamarine" => 0x7fffd4,
+        "azure" => 0xf0ffff,
+        "beige" => 0xf5f5dc,
+        "bisque" => 0xffe4c4,
+        "black" => 0x000000,
+        "blanchedalmond" => 0xffebcd,
+        "blue" => 0x0000ff,
+        "blueviolet" => 0x8a2be2,
+        "brown" => 0xa52a2a,
+        "burlywood" => 0xdeb887,
+        "cadetblue" => 0x5f9ea0,
+        "chartreuse" => 0x7fff00,
+        "chocolate" => 0xd2691e,
+        "coral" => 0xff7f50,
+        "cornflowerblue" => 0x6495ed,
+        "cornsilk" => 0xfff8dc,
+        "crimson" => 0xdc143c,
+        "cyan" => 0x00ffff,
+        "darkblue" => 0x00008b,
+        "darkcyan" => 0x008b8b,
+        "darkgoldenrod" => 0xb8860b,
+        "darkgray" => 0xa9a9a9,
+        "darkgreen" => 0x006400,
+        "darkgrey" => 0xa9a9a9,
+        "darkkhaki" => 0xbdb76b,
+        "darkmagenta" => 0x8b008b,
+        "darkolivegreen" => 0x556b2f,
+        "darkorange" => 0xff8c00,
+        "darkorchid" => 0x9932cc,
+        "darkred" => 0x8b0000,
+        "darksalmon" => 0xe9967a,
+        "darkseagreen" => 0x8fbc8f,
+        "darkslateblue" => 0x483d8b,
+        "darkslategray" => 0x2f4f4f,
+        "darkslategrey" => 0x2f4f4f,
+        "darkturquoise" => 0x00ced1,
+        "darkviolet" => 0x9400d3,
+        "deeppink" => 0xff1493,
+        "deepskyblue" => 0x00bfff,
+        "dimgray" => 0x696969,
+        "dimgrey" => 0x696969,
+        "dodgerblue" => 0x1e90ff,
+        "firebrick" => 0xb22222,
+        "floralwhite" => 0xfffaf0,
+        "forestgreen" => 0x228b22,
+        "fuchsia" => 0xff00ff,
+        "gainsboro" => 0xdcdcdc,
+        "ghostwhite" => 0xf8f8ff,
+        "gold" => 0xffd700,
+        "goldenrod" => 0xdaa520,
+        "gray" => 0x808080,
+        "green" => 0x008000,
+        "greenyellow" => 0xadff2f,
+        "grey" => 0x808080,
+        "honeydew" => 0xf0fff0,
+        "hotpink" => 0xff69b4,
+        "indianred" => 0xcd5c5c,
+        "indigo" => 0x4b0082,
+        "ivory" => 0xfffff0,
+        "khaki" => 0xf0e68c,
+        "lavender" => 0xe6e6fa,
+        "lavenderblush" => 0xfff0f5,
+        "lawngreen" => 0x7cfc00,
+        "lemonchiffon" => 0xfffacd,
+        "lightblue" => 0xadd8e6,
+        "lightcoral" => 0xf08080,
+        "lightcyan" => 0xe0ffff,
+        "lightgoldenrodyellow" => 0xfafad2,
+        "lightgray" => 0xd3d3d3,
+        "lightgreen" => 0x90ee90,
+        "lightgrey" => 0xd3d3d3,
+        "lightpink" => 0xffb6c1,
+        "lightsalmon" => 0xffa07a,
+        "lightseagreen" => 0x20b2aa,
+        "lightskyblue" => 0x87cefa,
+        "lightslategray" => 0x778899,
+        "lightslategrey" => 0x778899,
+        "lightsteelblue" => 0xb0c4de,
+        "lightyellow" => 0xffffe0,
+        "lime" => 0x00ff00,
+        "limegreen" => 0x32cd32,
+        "linen" => 0xfaf0e6,
+        "magenta" => 0xff00ff,
+        "maroon" => 0x800000,
+        "mediumaquamarine" => 0x66cdaa,
+        "mediumblue" => 0x0000cd,
+        "mediumorchid" => 0xba55d3,
+        "mediumpurple" => 0x9370db,
+        "mediumseagreen" => 0x3cb371,
+        "mediumslateblue" => 0x7b68ee,
+        "mediumspringgreen" => 0x00fa9a,
+        "mediumturquoise" => 0x48d1cc,
+        "mediumvioletred" => 0xc71585,
+        "midnightblue" => 0x191970,
+        "mintcream" => 0xf5fffa,
+        "mistyrose" => 0xffe4e1,
+        "moccasin" => 0xffe4b5,
+        "navajowhite" => 0xffdead,
+        "navy" => 0x000080,
+        "oldlace" => 0xfdf5e6,
+        "olive" => 0x808000,
+        "olivedrab" => 0x6b8e23,
+        "orange" => 0xffa500,
+        "orangered" => 0xff4500,
+        "orchid" => 0xda70d6,
+        "palegoldenrod" => 0xeee8aa,
+        "palegreen" => 0x98fb98,
+        "paleturquoise" => 0xafeeee,
+        "palevioletred" => 0xdb7093,
+        "papayawhip" => 0xffefd5,
+        "peachpuff" => 0xffdab9,
+        "peru" => 0xcd853f,
+        "pink" => 0xffc0cb,
+        "plum" => 0xdda0dd,
+        "powderblue" => 0xb0e0e6,
+        "purple" => 0x800080,
+        "rebeccapurple" => 0x663399,
+        "red" => 0xff0000,
+        "rosybrown" => 0xbc8f8f,
+        "royalblue" => 0x4169e1,
+        "saddlebrown" => 0x8b4513,
+        "salmon" => 0xfa8072,
+        "sandybrown" => 0xf4a460,
+        "seagreen" => 0x2e8b57,
+        "seashell" => 0xfff5ee,
+        "sienna" => 0xa0522d,
+        "silver" => 0xc0c0c0,
+        "skyblue" => 0x87ceeb,
+        "slateblue" => 0x6a5acd,
+        "slategray" => 0x708090,
+        "slategrey" => 0x708090,
+        "snow" => 0xfffafa,
+        "springgreen" => 0x00ff7f,
+        "steelblue" => 0x4682b4,
+        "tan" => 0xd2b48c,
+        "teal" => 0x008080,
+        "thistle" => 0xd8bfd8,
+        "tomato" => 0xff6347,
+        "turquoise" => 0x40e0d0,
+        "violet" => 0xee82ee,
+        "wheat" => 0xf5deb3,
+        "white" => 0xffffff,
+        "whitesmoke" => 0xf5f5f5,
+        "yellow" => 0xffff00,
+        "yellowgreen" => 0x9acd32,
+        _ => return None,
+    };
+    Some(Color::new(0xff000000 | rgb))
+}
@@ -1,4 +1,5 @@
 use crate::common::context::Device;
+use crate::common::context::filters::css_filter::LengthUnit;
 
 const SIZE_CM: f32 = 1.0;
 const SIZE_MILLIMETERS: f32 = SIZE_CM * 10.0;
@@ -7,27 +8,31 @@ const SIZE_PX: f32 = 1.0 / 96.0 * SIZE_INCH;
 const SIZE_POINT: f32 = 1.0 / 72.0 * SIZE_INCH;
 const SIZE_PICAS: f32 = SIZE_POINT * 12.0;
 
+/// Font size `em` and `rem` resolve against here, the initial value of `font-size`.
+const ROOT_FONT_SIZE: f32 = 16.0;
+
 pub(crate) fn parse_size(value: &str, device: Device) -> f32 {
-    if value.contains("px") {
-        return value.replace("px", "").parse::<f32>().unwrap_or(0.0);
-    } else if value.contains("cm") {
-        let cm = device.ppi / 2.54;
-        return value.replace("cm", "").parse::<f32>().unwrap_or(0.0) * cm;
-    } else if value.contains("in") {
-        let inch = device.ppi;
-        return value.replace("in", "").parse::<f32>().unwrap_or(0.0) * inch;
-    } else if value.contains("pt") {
-        let pt = device.ppi * (1.0 / 72.0);
-        return value.replace("pt", "").parse::<f32>().unwrap_or(0.0) * pt;
-    } else if value.contains("pc") {
-        let pc = (device.ppi * (1.0 / 72.0)) * 12.0;
-        return value.replace("pt", "").parse::<f32>().unwrap_or(0.0) * pc;
-    } else if value.contains("vh") {
-        let vh = value.replace("vh", "").parse::<f32>().unwrap_or(0.0) / 100.0;
-        return device.height * vh;
-    } else if value.contains("vw") {
-        let vw = value.replace("vw", "").parse::<f32>().unwrap_or(0.0) / 100.0;
-        return device.width * vw;
+    let value = value.trim();
+    let number = value.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+    let unit = &value[number.len()..];
+    match (number.parse::<f32>(), LengthUnit::from_str(unit)) {
+        (Ok(number), Some(unit)) => length_to_px(number, unit, device),
+        _ => 0.0,
+    }
+}
+
+/// Resolves a length to CSS pixels, physical units against the device ppi and viewport units
+/// against its size.
+pub(crate) fn length_to_px(value: f32, unit: LengthUnit, device: Device) -> f32 {
+    match unit {
+        LengthUnit::Px => value,
+        LengthUnit::Em | LengthUnit::Rem => value * ROOT_FONT_SIZE,
+        LengthUnit::Cm => value * device.ppi / 2.54,
+        LengthUnit::Mm => value * device.ppi / 25.4,
+        LengthUnit::In => value * device.ppi,
+        LengthUnit::Pt => value * device.ppi / 72.0,
+        LengthUnit::Pc => value * device.ppi / 72.0 * 12.0,
+        LengthUnit::Vw => value * device.width / 100.0,
+        LengthUnit::Vh => value * device.height / 100.0,
     }
-    return 0.0;
 }
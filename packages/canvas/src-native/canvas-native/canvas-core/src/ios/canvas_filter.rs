@@ -0,0 +1,279 @@
+use std::ffi::CStr;
+use std::os::raw::{c_char, c_float, c_longlong};
+
+use skia_safe::{Color, Picture, Point3};
+
+use crate::common::context::compositing::composite_operation_type::CompositeOperationType;
+use crate::common::context::Context;
+use crate::common::context::fill_and_stroke_styles::paint::PaintStyle;
+use crate::common::context::filters::canvas_filter::{
+    CanvasFilter, ColorChannelSelector, LightSource, Lighting, MorphologyOperator,
+};
+use crate::common::context::image_asset::ImageAsset;
+use crate::common::utils::color::parse_color;
+
+// Filters are passed around as handles, 0 standing for the source graphic when used as an
+// input. Every function returns 0 if the filter could not be created.
+
+fn filter_ref<'a>(filter: c_longlong) -> Option<&'a CanvasFilter> {
+    if filter == 0 {
+        return None;
+    }
+    unsafe {
+        let filter: *const CanvasFilter = filter as _;
+        Some(&*filter)
+    }
+}
+
+fn to_handle(filter: Option<CanvasFilter>) -> c_longlong {
+    match filter {
+        Some(filter) => Box::into_raw(Box::new(filter)) as c_longlong,
+        None => 0,
+    }
+}
+
+fn to_lighting(specular: bool, constant: c_float, exponent: c_float) -> Lighting {
+    if specular {
+        Lighting::Specular { constant, exponent }
+    } else {
+        Lighting::Diffuse { constant }
+    }
+}
+
+fn to_light_color(color: *const c_char) -> Color {
+    if color.is_null() {
+        return Color::WHITE;
+    }
+    let color = unsafe { CStr::from_ptr(color) }.to_string_lossy();
+    parse_color(color.as_ref()).unwrap_or(Color::WHITE)
+}
+
+#[no_mangle]
+pub extern "C" fn canvas_filter_source() -> c_longlong {
+    to_handle(CanvasFilter::source())
+}
+
+#[no_mangle]
+pub extern "C" fn canvas_filter_blur(
+    sigma_x: c_float,
+    sigma_y: c_float,
+    input: c_longlong,
+) -> c_longlong {
+    to_handle(CanvasFilter::blur(sigma_x, sigma_y, filter_ref(input)))
+}
+
+/// `matrix` must hold 20 values, a row major 4x5 matrix.
+#[no_mangle]
+pub extern "C" fn canvas_filter_color_matrix(
+    matrix: *const c_float,
+    matrix_length: usize,
+    input: c_longlong,
+) -> c_longlong {
+    if matrix.is_null() || matrix_length != 20 {
+        return 0;
+    }
+    let mut values = [0f32; 20];
+    values.copy_from_slice(unsafe { std::slice::from_raw_parts(matrix, matrix_length) });
+    to_handle(CanvasFilter::color_matrix(&values, filter_ref(input)))
+}
+
+#[no_mangle]
+pub extern "C" fn canvas_filter_offset(dx: c_float, dy: c_float, input: c_longlong) -> c_longlong {
+    to_handle(CanvasFilter::offset(dx, dy, filter_ref(input)))
+}
+
+#[no_mangle]
+pub extern "C" fn canvas_filter_morphology(
+    operator: MorphologyOperator,
+    radius_x: c_float,
+    radius_y: c_float,
+    input: c_longlong,
+) -> c_longlong {
+    to_handle(CanvasFilter::morphology(
+        operator,
+        radius_x,
+        radius_y,
+        filter_ref(input),
+    ))
+}
+
+#[no_mangle]
+pub extern "C" fn canvas_filter_displacement_map(
+    x_channel: ColorChannelSelector,
+    y_channel: ColorChannelSelector,
+    scale: c_float,
+    displacement: c_longlong,
+    color: c_longlong,
+) -> c_longlong {
+    to_handle(CanvasFilter::displacement_map(
+        x_channel,
+        y_channel,
+        scale,
+        filter_ref(displacement),
+        filter_ref(color),
+    ))
+}
+
+#[no_mangle]
+pub extern "C" fn canvas_filter_distant_lighting(
+    azimuth: c_float,
+    elevation: c_float,
+    specular: bool,
+    constant: c_float,
+    exponent: c_float,
+    color: *const c_char,
+    surface_scale: c_float,
+    input: c_longlong,
+) -> c_longlong {
+    to_handle(CanvasFilter::lighting(
+        LightSource::Distant { azimuth, elevation },
+        to_lighting(specular, constant, exponent),
+        to_light_color(color),
+        surface_scale,
+        filter_ref(input),
+    ))
+}
+
+#[no_mangle]
+pub extern "C" fn canvas_filter_point_lighting(
+    x: c_float,
+    y: c_float,
+    z: c_float,
+    specular: bool,
+    constant: c_float,
+    exponent: c_float,
+    color: *const c_char,
+    surface_scale: c_float,
+    input: c_longlong,
+) -> c_longlong {
+    to_handle(CanvasFilter::lighting(
+        LightSource::Point {
+            location: Point3::new(x, y, z),
+        },
+        to_lighting(specular, constant, exponent),
+        to_light_color(color),
+        surface_scale,
+        filter_ref(input),
+    ))
+}
+
+#[no_mangle]
+pub extern "C" fn canvas_filter_spot_lighting(
+    x: c_float,
+    y: c_float,
+    z: c_float,
+    target_x: c_float,
+    target_y: c_float,
+    target_z: c_float,
+    specular_exponent: c_float,
+    limiting_cone_angle: c_float,
+    specular: bool,
+    constant: c_float,
+    exponent: c_float,
+    color: *const c_char,
+    surface_scale: c_float,
+    input: c_longlong,
+) -> c_longlong {
+    to_handle(CanvasFilter::lighting(
+        LightSource::Spot {
+            location: Point3::new(x, y, z),
+            target: Point3::new(target_x, target_y, target_z),
+            specular_exponent,
+            limiting_cone_angle,
+        },
+        to_lighting(specular, constant, exponent),
+        to_light_color(color),
+        surface_scale,
+        filter_ref(input),
+    ))
+}
+
+#[no_mangle]
+pub extern "C" fn canvas_filter_blend(
+    mode: CompositeOperationType,
+    background: c_longlong,
+    foreground: c_longlong,
+) -> c_longlong {
+    to_handle(CanvasFilter::blend(
+        mode,
+        filter_ref(background),
+        filter_ref(foreground),
+    ))
+}
+
+#[no_mangle]
+pub extern "C" fn canvas_filter_merge(inputs: *const c_longlong, inputs_length: usize) -> c_longlong {
+    if inputs.is_null() {
+        return 0;
+    }
+    let inputs: Vec<Option<&CanvasFilter>> = unsafe { std::slice::from_raw_parts(inputs, inputs_length) }
+        .iter()
+        .map(|input| filter_ref(*input))
+        .collect();
+    to_handle(CanvasFilter::merge(inputs.as_slice()))
+}
+
+/// Patterns in `style` are sampled with the image smoothing settings of `context`.
+#[no_mangle]
+pub extern "C" fn canvas_filter_shader(context: c_longlong, style: c_longlong) -> c_longlong {
+    if context == 0 || style == 0 {
+        return 0;
+    }
+    unsafe {
+        let context: *const Context = context as _;
+        let context = &*context;
+        let style: *const PaintStyle = style as _;
+        let style = &*style;
+        to_handle(CanvasFilter::shader(style, context.state.image_filter_quality()))
+    }
+}
+
+/// `picture` is a handle from `context_end_recording`.
+#[no_mangle]
+pub extern "C" fn canvas_filter_picture(picture: c_longlong) -> c_longlong {
+    if picture == 0 {
+        return 0;
+    }
+    let picture: *const Picture = picture as _;
+    let picture = unsafe { &*picture };
+    to_handle(CanvasFilter::picture(picture.clone()))
+}
+
+#[no_mangle]
+pub extern "C" fn canvas_filter_compose(outer: c_longlong, inner: c_longlong) -> c_longlong {
+    match (filter_ref(outer), filter_ref(inner)) {
+        (Some(outer), Some(inner)) => to_handle(CanvasFilter::compose(outer, inner)),
+        _ => 0,
+    }
+}
+
+/// Returns a new image asset holding the filtered pixels, or 0 on failure.
+#[no_mangle]
+pub extern "C" fn canvas_filter_apply_to_asset(filter: c_longlong, asset: c_longlong) -> c_longlong {
+    if asset == 0 {
+        return 0;
+    }
+    let filter = match filter_ref(filter) {
+        Some(filter) => filter,
+        None => return 0,
+    };
+    unsafe {
+        let asset: *const ImageAsset = asset as _;
+        let asset = &*asset;
+        match filter.apply_to_asset(asset) {
+            Some(output) => Box::into_raw(Box::new(output)) as c_longlong,
+            None => 0,
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn destroy_canvas_filter(filter: c_longlong) {
+    if filter == 0 {
+        return;
+    }
+    unsafe {
+        let filter: *mut CanvasFilter = filter as _;
+        let _ = Box::from_raw(filter);
+    }
+}
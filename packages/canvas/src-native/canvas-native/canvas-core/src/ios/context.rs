@@ -1,34 +1,41 @@
+use std::collections::HashMap;
 use std::ffi::{CStr, CString};
 use std::os::raw::{c_char, c_float, c_int, c_longlong, c_uint};
-use std::str::FromStr;
 
 use skia_safe::{
-    AlphaType, Color, ColorType, EncodedImageFormat, ImageInfo, IPoint, ISize, M44, PixelGeometry,
-    Rect, Surface,
+    AlphaType, Color, ColorType, EncodedImageFormat, ImageInfo, IPoint, ISize, PixelGeometry,
+    Picture, Rect, Surface,
 };
 use skia_safe::gpu::gl::Interface;
 use skia_safe::image::CachingHint;
 
 use crate::common::context::{Context, Device, State};
+use crate::common::context::box_shadows::BoxShadow;
+use crate::common::context::color_space::PredefinedColorSpace;
 use crate::common::context::compositing::composite_operation_type::CompositeOperationType;
 use crate::common::context::drawing_paths::fill_rule::FillRule;
+use crate::common::context::fill_and_stroke_styles::gradient::Gradient;
 use crate::common::context::fill_and_stroke_styles::paint::PaintStyle;
 use crate::common::context::fill_and_stroke_styles::pattern::Repetition;
+use crate::common::context::filters::canvas_filter::CanvasFilter;
 use crate::common::context::image_asset::ImageAsset;
 use crate::common::context::image_smoothing::ImageSmoothingQuality;
+use crate::common::context::layers::LayerOptions;
 use crate::common::context::line_styles::line_cap::LineCap;
 use crate::common::context::line_styles::line_join::LineJoin;
+use crate::common::context::masks::MaskType;
 use crate::common::context::matrix::Matrix;
 use crate::common::context::paths::path::Path;
-use crate::common::context::pixel_manipulation::image_data::ImageData;
+use crate::common::context::pixel_manipulation::image_data::{ImageData, ImageDataStorageFormat};
 use crate::common::context::text_styles::text_align::TextAlign;
 use crate::common::context::text_styles::text_baseline::TextBaseLine;
 use crate::common::context::text_styles::text_direction::TextDirection;
+use crate::common::context::text_styles::text_rendering::{TextEdging, TextHinting, TextRendering};
 use crate::common::ffi::f32_array::F32Array;
 use crate::common::ffi::paint_style_value::{PaintStyleValue, PaintStyleValueType};
 use crate::common::ffi::u8_array::U8Array;
 use crate::common::to_data_url;
-use crate::common::utils::color::to_parsed_color;
+use crate::common::utils::color::{parse_color_4f, to_parsed_color};
 use crate::common::utils::image::{from_image_slice, to_image, to_image_encoded};
 
 /*
@@ -60,8 +67,7 @@ pub extern "C" fn context_init_context_mtl(
 const GR_GL_RGB565: c_uint = 0x8D62;
 const GR_GL_RGBA8: c_uint = 0x8058;
 
-#[no_mangle]
-pub extern "C" fn context_init_context(
+fn init_context(
     width: c_float,
     height: c_float,
     density: c_float,
@@ -71,6 +77,7 @@ pub extern "C" fn context_init_context(
     font_color: c_uint,
     ppi: c_float,
     direction: TextDirection,
+    color_space: PredefinedColorSpace,
 ) -> c_longlong {
     let device = Device {
         width,
@@ -81,6 +88,7 @@ pub extern "C" fn context_init_context(
         alpha,
         ppi,
         matrix: skia_safe::Matrix::scale((density, density)),
+        color_space,
     };
     let interface = Interface::new_native();
     let mut ctx = skia_safe::gpu::DirectContext::new_gl(interface, None).unwrap();
@@ -110,23 +118,24 @@ pub extern "C" fn context_init_context(
         &target,
         skia_safe::gpu::SurfaceOrigin::BottomLeft,
         color_type,
-        None,
+        Some(color_space.to_skia()),
         Some(&surface_props),
     );
 
     Box::into_raw(Box::new(Context {
-        surface: surface_holder.unwrap(),
+        surface: surface_holder.unwrap().into(),
         path: Path::default(),
         state: State::from_device(device, direction),
         state_stack: vec![],
         font_color: Color::new(font_color),
         device,
         enable_scaling: false,
+        blenders: HashMap::new(),
+        svg_filters: HashMap::new(),
     })) as c_longlong
 }
 
-#[no_mangle]
-pub extern "C" fn context_init_context_with_custom_surface(
+fn init_context_with_custom_surface(
     width: c_float,
     height: c_float,
     density: c_float,
@@ -134,6 +143,7 @@ pub extern "C" fn context_init_context_with_custom_surface(
     font_color: c_int,
     ppi: c_float,
     direction: TextDirection,
+    color_space: PredefinedColorSpace,
 ) -> c_longlong {
     let device = Device {
         width,
@@ -144,25 +154,126 @@ pub extern "C" fn context_init_context_with_custom_surface(
         alpha,
         ppi,
         matrix: skia_safe::Matrix::scale((density, density)),
+        color_space,
     };
     let info = ImageInfo::new(
         ISize::new(width as i32, height as i32),
         ColorType::RGBA8888,
         AlphaType::Premul,
-        None,
+        Some(color_space.to_skia()),
     );
 
     Box::into_raw(Box::new(Context {
-        surface: Surface::new_raster(&info, None, None).unwrap(),
+        surface: Surface::new_raster(&info, None, None).unwrap().into(),
         path: Path::default(),
         state: State::from_device(device, direction),
         state_stack: vec![],
         font_color: Color::new(font_color as u32),
         device,
         enable_scaling: false,
+        blenders: HashMap::new(),
+        svg_filters: HashMap::new(),
     })) as c_longlong
 }
 
+#[no_mangle]
+pub extern "C" fn context_init_context(
+    width: c_float,
+    height: c_float,
+    density: c_float,
+    buffer_id: c_int,
+    samples: usize,
+    alpha: bool,
+    font_color: c_uint,
+    ppi: c_float,
+    direction: TextDirection,
+) -> c_longlong {
+    init_context(
+        width,
+        height,
+        density,
+        buffer_id,
+        samples,
+        alpha,
+        font_color,
+        ppi,
+        direction,
+        PredefinedColorSpace::SRGB,
+    )
+}
+
+#[no_mangle]
+pub extern "C" fn context_init_context_with_color_space(
+    width: c_float,
+    height: c_float,
+    density: c_float,
+    buffer_id: c_int,
+    samples: usize,
+    alpha: bool,
+    font_color: c_uint,
+    ppi: c_float,
+    direction: TextDirection,
+    color_space: PredefinedColorSpace,
+) -> c_longlong {
+    init_context(
+        width,
+        height,
+        density,
+        buffer_id,
+        samples,
+        alpha,
+        font_color,
+        ppi,
+        direction,
+        color_space,
+    )
+}
+
+#[no_mangle]
+pub extern "C" fn context_init_context_with_custom_surface(
+    width: c_float,
+    height: c_float,
+    density: c_float,
+    alpha: bool,
+    font_color: c_int,
+    ppi: c_float,
+    direction: TextDirection,
+) -> c_longlong {
+    init_context_with_custom_surface(
+        width,
+        height,
+        density,
+        alpha,
+        font_color,
+        ppi,
+        direction,
+        PredefinedColorSpace::SRGB,
+    )
+}
+
+#[no_mangle]
+pub extern "C" fn context_init_context_with_custom_surface_and_color_space(
+    width: c_float,
+    height: c_float,
+    density: c_float,
+    alpha: bool,
+    font_color: c_int,
+    ppi: c_float,
+    direction: TextDirection,
+    color_space: PredefinedColorSpace,
+) -> c_longlong {
+    init_context_with_custom_surface(
+        width,
+        height,
+        density,
+        alpha,
+        font_color,
+        ppi,
+        direction,
+        color_space,
+    )
+}
+
 #[no_mangle]
 pub extern "C" fn context_set_scaling(
     context: c_longlong,
@@ -202,17 +313,18 @@ pub extern "C" fn context_resize_custom_surface(
             alpha,
             ppi,
             matrix: skia_safe::Matrix::scale((density, density)),
+            color_space: context.device.color_space,
         };
 
         let info = ImageInfo::new(
             ISize::new(width as i32, height as i32),
             ColorType::RGBA8888,
             AlphaType::Premul,
-            None,
+            Some(device.color_space.to_skia()),
         );
 
         if let Some(surface) = Surface::new_raster(&info, None, None) {
-            context.surface = surface;
+            context.surface = surface.into();
             context.device = device;
             context.path = Path::default();
             context.reset_state();
@@ -253,6 +365,7 @@ pub extern "C" fn context_resize_surface(
             alpha: false,
             ppi,
             matrix: skia_safe::Matrix::scale((density, density)),
+            color_space: context.device.color_space,
         };
         let mut frame_buffer = skia_safe::gpu::gl::FramebufferInfo::from_fboid(buffer_id as u32);
 
@@ -282,10 +395,10 @@ pub extern "C" fn context_resize_surface(
             &target,
             skia_safe::gpu::SurfaceOrigin::BottomLeft,
             color_type,
-            None,
+            Some(device.color_space.to_skia()),
             Some(&surface_props),
         ) {
-            context.surface = surface;
+            context.surface = surface.into();
             context.device = device;
             context.path = Path::default();
             context.reset_state();
@@ -522,6 +635,10 @@ pub extern "C" fn context_get_fill_style(context: c_longlong) -> *mut PaintStyle
             PaintStyle::Pattern(_) => {
                 PaintStyleValue::new(fill_style, PaintStyleValueType::PaintStyleValueTypePattern)
             }
+            PaintStyle::RuntimeEffect(_) => PaintStyleValue::new(
+                fill_style,
+                PaintStyleValueType::PaintStyleValueTypeRuntimeEffect,
+            ),
         };
         Box::into_raw(Box::new(result))
     }
@@ -550,6 +667,112 @@ pub extern "C" fn context_get_filter(context: c_longlong) -> *const c_char {
     }
 }
 
+/// Sets the filter graph as the context filter, 0 clearing it.
+#[no_mangle]
+pub extern "C" fn context_set_canvas_filter(context: c_longlong, filter: c_longlong) {
+    unsafe {
+        if context == 0 {
+            return;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        if filter == 0 {
+            context.set_canvas_filter(None)
+        } else {
+            let filter: *const CanvasFilter = filter as _;
+            context.set_canvas_filter(Some(&*filter))
+        }
+    }
+}
+
+/// Filters what is already drawn inside `path`, 0 using the current path. Returns false if
+/// `filter` does not parse.
+#[no_mangle]
+pub extern "C" fn context_apply_backdrop_filter(
+    context: c_longlong,
+    filter: *const c_char,
+    path: c_longlong,
+    rule: FillRule,
+) -> bool {
+    unsafe {
+        if context == 0 || filter.is_null() {
+            return false;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        let filter = CStr::from_ptr(filter).to_string_lossy();
+        if path == 0 {
+            context.apply_backdrop_filter(filter.as_ref(), None, rule)
+        } else {
+            let path: *mut Path = path as _;
+            context.apply_backdrop_filter(filter.as_ref(), Some(&mut *path), rule)
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn context_apply_backdrop_canvas_filter(
+    context: c_longlong,
+    filter: c_longlong,
+    path: c_longlong,
+    rule: FillRule,
+) {
+    unsafe {
+        if context == 0 || filter == 0 {
+            return;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        let filter: *const CanvasFilter = filter as _;
+        if path == 0 {
+            context.apply_backdrop_canvas_filter(&*filter, None, rule)
+        } else {
+            let path: *mut Path = path as _;
+            context.apply_backdrop_canvas_filter(&*filter, Some(&mut *path), rule)
+        }
+    }
+}
+
+/// Registers the `<filter>` elements of an SVG document for `url(#id)` filters and returns
+/// how many were found, or -1 if the document is invalid. On failure the reason is written to
+/// `error` if it is not null and must be released with `destroy_string`.
+#[no_mangle]
+pub extern "C" fn context_register_svg_filters(
+    context: c_longlong,
+    svg: *const c_char,
+    error: *mut *const c_char,
+) -> c_int {
+    unsafe {
+        if context == 0 || svg.is_null() {
+            return -1;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        let svg = CStr::from_ptr(svg).to_string_lossy();
+        match context.register_svg_filters(svg.as_ref()) {
+            Ok(count) => count as c_int,
+            Err(message) => {
+                if !error.is_null() {
+                    *error = CString::new(message).unwrap_or_default().into_raw();
+                }
+                -1
+            }
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn context_clear_svg_filters(context: c_longlong) {
+    unsafe {
+        if context == 0 {
+            return;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        context.clear_svg_filters()
+    }
+}
+
 #[no_mangle]
 pub extern "C" fn context_set_font(context: c_longlong, filter: *const c_char) {
     unsafe {
@@ -573,6 +796,52 @@ pub extern "C" fn context_get_font(context: c_longlong) -> *const c_char {
     }
 }
 
+#[no_mangle]
+pub extern "C" fn context_set_font_variation_settings(context: c_longlong, settings: *const c_char) {
+    unsafe {
+        if context == 0 || settings.is_null() {
+            return;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        let settings = CStr::from_ptr(settings).to_string_lossy();
+        context.set_font_variation_settings(settings.as_ref());
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn context_get_font_variation_settings(context: c_longlong) -> *const c_char {
+    unsafe {
+        let context: *const Context = context as _;
+        let context = &*context;
+        let settings = context.font_variation_settings();
+        CString::new(settings).unwrap().into_raw()
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn context_set_font_feature_settings(context: c_longlong, settings: *const c_char) {
+    unsafe {
+        if context == 0 || settings.is_null() {
+            return;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        let settings = CStr::from_ptr(settings).to_string_lossy();
+        context.set_font_feature_settings(settings.as_ref());
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn context_get_font_feature_settings(context: c_longlong) -> *const c_char {
+    unsafe {
+        let context: *const Context = context as _;
+        let context = &*context;
+        let settings = context.font_feature_settings();
+        CString::new(settings).unwrap().into_raw()
+    }
+}
+
 #[no_mangle]
 pub extern "C" fn context_set_global_alpha(context: c_longlong, alpha: c_float) {
     unsafe {
@@ -620,6 +889,67 @@ pub extern "C" fn context_get_global_composite_operation(
     }
 }
 
+#[no_mangle]
+pub extern "C" fn context_set_global_composite_operation_name(
+    context: c_longlong,
+    operation: *const c_char,
+) -> bool {
+    unsafe {
+        if context == 0 || operation.is_null() {
+            return false;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        let operation = CStr::from_ptr(operation).to_string_lossy();
+        context.set_global_composite_operation_name(operation.as_ref())
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn context_get_global_composite_operation_name(
+    context: c_longlong,
+) -> *const c_char {
+    unsafe {
+        if context == 0 {
+            return std::ptr::null();
+        }
+        let context: *const Context = context as _;
+        let context = &*context;
+        CString::new(context.global_composite_operation_name())
+            .unwrap()
+            .into_raw()
+    }
+}
+
+/// On failure the compiler output is written to `error` if it is not null and must be
+/// released with `destroy_string`.
+#[no_mangle]
+pub extern "C" fn context_register_blender(
+    context: c_longlong,
+    name: *const c_char,
+    sksl: *const c_char,
+    error: *mut *const c_char,
+) -> bool {
+    unsafe {
+        if context == 0 || name.is_null() || sksl.is_null() {
+            return false;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        let name = CStr::from_ptr(name).to_string_lossy();
+        let sksl = CStr::from_ptr(sksl).to_string_lossy();
+        match context.register_blender(name.as_ref(), sksl.as_ref()) {
+            Ok(_) => true,
+            Err(message) => {
+                if !error.is_null() {
+                    *error = CString::new(message).unwrap_or_default().into_raw();
+                }
+                false
+            }
+        }
+    }
+}
+
 #[no_mangle]
 pub extern "C" fn context_set_image_smoothing_enabled(context: c_longlong, enabled: bool) {
     unsafe {
@@ -801,7 +1131,7 @@ pub extern "C" fn context_set_shadow_color(context: c_longlong, r: u8, g: u8, b:
         }
         let context: *mut Context = context as _;
         let context = &mut *context;
-        context.set_shadow_color(Color::from_argb(a, r, g, b))
+        context.set_shadow_color(Color::from_argb(a, r, g, b).into())
     }
 }
 
@@ -814,13 +1144,8 @@ pub extern "C" fn context_set_shadow_color_string(context: c_longlong, color: *c
         let context: *mut Context = context as _;
         let context = &mut *context;
         let color = CStr::from_ptr(color).to_string_lossy();
-        if let Ok(color) = css_color_parser::Color::from_str(color.as_ref()) {
-            context.set_shadow_color(Color::from_argb(
-                (color.a * 255.0) as u8,
-                color.r,
-                color.g,
-                color.b,
-            ))
+        if let Some(color) = parse_color_4f(color.as_ref(), context.font_color) {
+            context.set_shadow_color(color)
         }
     }
 }
@@ -830,7 +1155,7 @@ pub extern "C" fn context_get_shadow_color(context: c_longlong) -> *const c_char
     unsafe {
         let context: *const Context = context as _;
         let context = &*context;
-        CString::new(to_parsed_color(context.shadow_color()))
+        CString::new(to_parsed_color(context.shadow_color().to_color()))
             .unwrap()
             .into_raw()
     }
@@ -910,6 +1235,10 @@ pub extern "C" fn context_get_stroke_style(context: c_longlong) -> *mut PaintSty
                 stroke_style,
                 PaintStyleValueType::PaintStyleValueTypePattern,
             ),
+            PaintStyle::RuntimeEffect(_) => PaintStyleValue::new(
+                stroke_style,
+                PaintStyleValueType::PaintStyleValueTypeRuntimeEffect,
+            ),
         }))
     }
 }
@@ -957,58 +1286,184 @@ pub extern "C" fn context_get_text_baseline(context: c_longlong) -> TextBaseLine
 }
 
 #[no_mangle]
-pub extern "C" fn context_arc(
-    context: c_longlong,
-    x: c_float,
-    y: c_float,
-    radius: c_float,
-    start_angle: c_float,
-    end_angle: c_float,
-    anti_clockwise: bool,
-) {
+pub extern "C" fn context_set_text_rendering(context: c_longlong, value: TextRendering) {
     unsafe {
         if context == 0 {
             return;
         }
         let context: *mut Context = context as _;
         let context = &mut *context;
-        context.arc(x, y, radius, start_angle, end_angle, anti_clockwise)
+        context.set_text_rendering(value);
     }
 }
 
 #[no_mangle]
-pub extern "C" fn context_arc_to(
-    context: c_longlong,
-    x1: c_float,
-    y1: c_float,
-    x2: c_float,
-    y2: c_float,
-    radius: c_float,
-) {
+pub extern "C" fn context_get_text_rendering(context: c_longlong) -> TextRendering {
     unsafe {
-        if context == 0 {
-            return;
-        }
-        let context: *mut Context = context as _;
-        let context = &mut *context;
-        context.arc_to(x1, y1, x2, y2, radius)
+        let context: *const Context = context as _;
+        let context = &*context;
+        context.text_rendering()
     }
 }
 
 #[no_mangle]
-pub extern "C" fn context_begin_path(context: c_longlong) {
+pub extern "C" fn context_set_text_edging(context: c_longlong, value: TextEdging) {
     unsafe {
         if context == 0 {
             return;
         }
         let context: *mut Context = context as _;
         let context = &mut *context;
-        context.begin_path()
+        context.set_text_edging(value);
     }
 }
 
 #[no_mangle]
-pub extern "C" fn context_bezier_curve_to(
+pub extern "C" fn context_get_text_edging(context: c_longlong) -> TextEdging {
+    unsafe {
+        let context: *const Context = context as _;
+        let context = &*context;
+        context.text_edging()
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn context_set_text_hinting(context: c_longlong, value: TextHinting) {
+    unsafe {
+        if context == 0 {
+            return;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        context.set_text_hinting(value);
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn context_get_text_hinting(context: c_longlong) -> TextHinting {
+    unsafe {
+        let context: *const Context = context as _;
+        let context = &*context;
+        context.text_hinting()
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn context_set_text_subpixel_positioning(context: c_longlong, value: bool) {
+    unsafe {
+        if context == 0 {
+            return;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        context.set_text_subpixel_positioning(value);
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn context_get_text_subpixel_positioning(context: c_longlong) -> bool {
+    unsafe {
+        let context: *const Context = context as _;
+        let context = &*context;
+        context.text_subpixel_positioning()
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn context_set_text_embedded_bitmaps(context: c_longlong, value: bool) {
+    unsafe {
+        if context == 0 {
+            return;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        context.set_text_embedded_bitmaps(value);
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn context_get_text_embedded_bitmaps(context: c_longlong) -> bool {
+    unsafe {
+        let context: *const Context = context as _;
+        let context = &*context;
+        context.text_embedded_bitmaps()
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn context_set_text_linear_metrics(context: c_longlong, value: bool) {
+    unsafe {
+        if context == 0 {
+            return;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        context.set_text_linear_metrics(value);
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn context_get_text_linear_metrics(context: c_longlong) -> bool {
+    unsafe {
+        let context: *const Context = context as _;
+        let context = &*context;
+        context.text_linear_metrics()
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn context_arc(
+    context: c_longlong,
+    x: c_float,
+    y: c_float,
+    radius: c_float,
+    start_angle: c_float,
+    end_angle: c_float,
+    anti_clockwise: bool,
+) {
+    unsafe {
+        if context == 0 {
+            return;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        context.arc(x, y, radius, start_angle, end_angle, anti_clockwise)
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn context_arc_to(
+    context: c_longlong,
+    x1: c_float,
+    y1: c_float,
+    x2: c_float,
+    y2: c_float,
+    radius: c_float,
+) {
+    unsafe {
+        if context == 0 {
+            return;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        context.arc_to(x1, y1, x2, y2, radius)
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn context_begin_path(context: c_longlong) {
+    unsafe {
+        if context == 0 {
+            return;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        context.begin_path()
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn context_bezier_curve_to(
     context: c_longlong,
     cp1x: c_float,
     cp1y: c_float,
@@ -1087,6 +1542,34 @@ pub extern "C" fn context_create_image_data(width: c_int, height: c_int) -> c_lo
     Box::into_raw(Box::new(Context::create_image_data(width, height))) as c_longlong
 }
 
+#[no_mangle]
+pub extern "C" fn context_create_image_data_with_color_space(
+    width: c_int,
+    height: c_int,
+    color_space: PredefinedColorSpace,
+) -> c_longlong {
+    Box::into_raw(Box::new(Context::create_image_data_with_color_space(
+        width,
+        height,
+        color_space,
+    ))) as c_longlong
+}
+
+#[no_mangle]
+pub extern "C" fn context_create_image_data_with_settings(
+    width: c_int,
+    height: c_int,
+    color_space: PredefinedColorSpace,
+    storage_format: ImageDataStorageFormat,
+) -> c_longlong {
+    Box::into_raw(Box::new(Context::create_image_data_with_settings(
+        width,
+        height,
+        color_space,
+        storage_format,
+    ))) as c_longlong
+}
+
 #[no_mangle]
 pub extern "C" fn context_create_linear_gradient(
     context: c_longlong,
@@ -1161,6 +1644,132 @@ pub extern "C" fn context_create_pattern_asset(
     }
 }
 
+/// Returns 0 when the SkSL fails to compile; the compiler output is written to `error` if it
+/// is not null and must be released with `destroy_string`.
+#[no_mangle]
+pub extern "C" fn context_create_runtime_effect(
+    context: c_longlong,
+    sksl: *const c_char,
+    error: *mut *const c_char,
+) -> c_longlong {
+    unsafe {
+        if context == 0 || sksl.is_null() {
+            return 0;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        let sksl = CStr::from_ptr(sksl).to_string_lossy();
+        match context.create_runtime_effect(sksl.as_ref()) {
+            Ok(effect) => Box::into_raw(Box::new(PaintStyle::RuntimeEffect(effect))) as c_longlong,
+            Err(message) => {
+                if !error.is_null() {
+                    *error = CString::new(message).unwrap_or_default().into_raw();
+                }
+                0
+            }
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn context_begin_recording(
+    context: c_longlong,
+    width: c_float,
+    height: c_float,
+) -> bool {
+    unsafe {
+        if context == 0 {
+            return false;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        context.begin_recording(width, height)
+    }
+}
+
+/// Returns a picture handle, released with `destroy_picture`, or 0 if the recording could not
+/// be ended.
+#[no_mangle]
+pub extern "C" fn context_end_recording(context: c_longlong) -> c_longlong {
+    unsafe {
+        if context == 0 {
+            return 0;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        match context.end_recording() {
+            Some(picture) => Box::into_raw(Box::new(picture)) as c_longlong,
+            None => 0,
+        }
+    }
+}
+
+/// `picture` is a handle from `context_end_recording`; its cull rect becomes the pattern tile.
+#[no_mangle]
+pub extern "C" fn context_create_pattern_picture(
+    context: c_longlong,
+    picture: c_longlong,
+    repetition: Repetition,
+) -> c_longlong {
+    unsafe {
+        if context == 0 || picture == 0 {
+            return 0;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        let picture: *const Picture = picture as _;
+        let picture = &*picture;
+        let pattern = context.create_pattern_from_picture(picture.clone(), repetition);
+        Box::into_raw(Box::new(PaintStyle::Pattern(pattern))) as c_longlong
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn context_create_pattern_svg(
+    context: c_longlong,
+    svg: *const c_char,
+    width: c_float,
+    height: c_float,
+    repetition: Repetition,
+) -> c_longlong {
+    unsafe {
+        if context == 0 || svg.is_null() {
+            return 0;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        let svg = CStr::from_ptr(svg).to_string_lossy();
+        match context.create_pattern_from_svg(svg.as_ref(), width, height, repetition) {
+            Some(pattern) => Box::into_raw(Box::new(PaintStyle::Pattern(pattern))) as c_longlong,
+            None => 0,
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn context_create_pattern_path(
+    context: c_longlong,
+    path: c_longlong,
+    is_fill: bool,
+    width: c_float,
+    height: c_float,
+    repetition: Repetition,
+) -> c_longlong {
+    unsafe {
+        if context == 0 || path == 0 {
+            return 0;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        let path: *const Path = path as _;
+        let path = &*path;
+        match context.create_pattern_from_path(path, is_fill, width, height, repetition) {
+            Some(pattern) => Box::into_raw(Box::new(PaintStyle::Pattern(pattern))) as c_longlong,
+            None => 0,
+        }
+    }
+}
+
 #[no_mangle]
 pub extern "C" fn context_create_pattern_encoded(
     context: c_longlong,
@@ -1225,6 +1834,77 @@ pub extern "C" fn context_create_conic_gradient(
     }
 }
 
+/// `points` holds the twelve control points as 24 interleaved x, y values and `colors` the
+/// four ARGB corner colors. Returns 0 if either length is wrong.
+#[no_mangle]
+pub extern "C" fn context_create_mesh_gradient(
+    context: c_longlong,
+    points: *const c_float,
+    points_length: usize,
+    colors: *const c_uint,
+    colors_length: usize,
+) -> c_longlong {
+    unsafe {
+        if context == 0 || points.is_null() || colors.is_null() {
+            return 0;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        let cubics = Gradient::cubics_from_slice(std::slice::from_raw_parts(points, points_length));
+        let colors =
+            Gradient::corner_colors_from_slice(std::slice::from_raw_parts(colors, colors_length));
+        match (cubics, colors) {
+            (Some(cubics), Some(colors)) => Box::into_raw(Box::new(PaintStyle::Gradient(
+                context.create_mesh_gradient(cubics, colors),
+            ))) as c_longlong,
+            _ => 0,
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn context_create_four_corner_gradient(
+    context: c_longlong,
+    x: c_float,
+    y: c_float,
+    width: c_float,
+    height: c_float,
+) -> c_longlong {
+    unsafe {
+        if context == 0 {
+            return 0;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        Box::into_raw(Box::new(PaintStyle::Gradient(
+            context.create_four_corner_gradient(x, y, width, height),
+        ))) as c_longlong
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn context_draw_patch(
+    context: c_longlong,
+    points: *const c_float,
+    points_length: usize,
+    colors: *const c_uint,
+    colors_length: usize,
+) {
+    unsafe {
+        if context == 0 || points.is_null() || colors.is_null() {
+            return;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        let cubics = Gradient::cubics_from_slice(std::slice::from_raw_parts(points, points_length));
+        let colors =
+            Gradient::corner_colors_from_slice(std::slice::from_raw_parts(colors, colors_length));
+        if let (Some(cubics), Some(colors)) = (cubics, colors) {
+            context.draw_patch(&cubics, &colors)
+        }
+    }
+}
+
 #[no_mangle]
 pub extern "C" fn context_draw_image_dx_dy(
     context: c_longlong,
@@ -1555,6 +2235,25 @@ pub extern "C" fn context_fill_text(
     }
 }
 
+#[no_mangle]
+pub extern "C" fn context_text_to_path(
+    context: c_longlong,
+    text: *const c_char,
+    x: c_float,
+    y: c_float,
+    width: c_float,
+) -> c_longlong {
+    unsafe {
+        if context == 0 || text.is_null() {
+            return Box::into_raw(Box::new(Path::new())) as c_longlong;
+        }
+        let context: *mut Context = context as _;
+        let context = &*context;
+        let txt = CStr::from_ptr(text).to_string_lossy();
+        Box::into_raw(Box::new(context.text_to_path(txt.as_ref(), x, y, width))) as c_longlong
+    }
+}
+
 #[no_mangle]
 pub extern "C" fn context_get_image_data(
     context: c_longlong,
@@ -1573,6 +2272,66 @@ pub extern "C" fn context_get_image_data(
     }
 }
 
+#[no_mangle]
+pub extern "C" fn context_get_image_data_with_color_space(
+    context: c_longlong,
+    sx: c_float,
+    sy: c_float,
+    sw: c_float,
+    sh: c_float,
+    color_space: PredefinedColorSpace,
+) -> c_longlong {
+    unsafe {
+        if context == 0 {
+            return 0;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        Box::into_raw(Box::new(
+            context.get_image_data_with_color_space(sx, sy, sw, sh, color_space),
+        )) as c_longlong
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn context_get_image_data_with_settings(
+    context: c_longlong,
+    sx: c_float,
+    sy: c_float,
+    sw: c_float,
+    sh: c_float,
+    color_space: PredefinedColorSpace,
+    storage_format: ImageDataStorageFormat,
+) -> c_longlong {
+    unsafe {
+        if context == 0 {
+            return 0;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        Box::into_raw(Box::new(context.get_image_data_with_settings(
+            sx,
+            sy,
+            sw,
+            sh,
+            color_space,
+            storage_format,
+        ))) as c_longlong
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn context_get_color_space(context: c_longlong) -> PredefinedColorSpace {
+    unsafe {
+        if context == 0 {
+            return PredefinedColorSpace::default();
+        }
+        let context: *const Context = context as _;
+        let context = &*context;
+        context.color_space()
+    }
+}
+
 #[no_mangle]
 pub extern "C" fn context_get_line_dash(context: c_longlong) -> *mut F32Array {
     unsafe {
@@ -1596,9 +2355,7 @@ pub extern "C" fn context_get_transform(context: c_longlong) -> c_longlong {
         }
         let context: *mut Context = context as _;
         let context = &mut *context;
-        let matrix = Matrix {
-            matrix: M44::from(context.get_transform().clone()),
-        };
+        let matrix = context.get_transform();
         Box::into_raw(Box::new(matrix)) as c_longlong
     }
 }
@@ -1771,6 +2528,67 @@ pub extern "C" fn context_round_rect(
     }
 }
 
+#[no_mangle]
+pub extern "C" fn context_reset(context: c_longlong) {
+    unsafe {
+        if context == 0 {
+            return;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        context.reset()
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn context_reset_clip(context: c_longlong) {
+    unsafe {
+        if context == 0 {
+            return;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        context.reset_clip()
+    }
+}
+
+/// Returns the x, y, width and height of the clip in user space, or null if the clip is empty.
+#[no_mangle]
+pub extern "C" fn context_get_clip_bounds(context: c_longlong) -> *mut F32Array {
+    unsafe {
+        if context == 0 {
+            return std::ptr::null_mut();
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        match context.clip_bounds() {
+            Some(bounds) => {
+                let mut values = vec![bounds.x(), bounds.y(), bounds.width(), bounds.height()]
+                    .into_boxed_slice();
+                let array = F32Array {
+                    data: values.as_mut_ptr(),
+                    data_len: values.len(),
+                };
+                let _ = Box::into_raw(values);
+                Box::into_raw(Box::new(array))
+            }
+            None => std::ptr::null_mut(),
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn context_is_clip_empty(context: c_longlong) -> bool {
+    unsafe {
+        if context == 0 {
+            return true;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        context.is_clip_empty()
+    }
+}
+
 #[no_mangle]
 pub extern "C" fn context_reset_transform(context: c_longlong) {
     unsafe {
@@ -1795,6 +2613,215 @@ pub extern "C" fn context_restore(context: c_longlong) {
     }
 }
 
+/// Starts a layer composited with `alpha`, the CSS `filter`, null keeping the current filter,
+/// and `composite_operation`, a negative value keeping the current one.
+#[no_mangle]
+pub extern "C" fn context_begin_layer(
+    context: c_longlong,
+    alpha: c_float,
+    filter: *const c_char,
+    composite_operation: c_int,
+) {
+    unsafe {
+        if context == 0 {
+            return;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        let mut options = LayerOptions::default();
+        options.alpha = alpha;
+        if !filter.is_null() {
+            options.filter = Some(CStr::from_ptr(filter).to_string_lossy().to_string());
+        }
+        if composite_operation >= 0 {
+            options.composite_operation = Some(CompositeOperationType::from(composite_operation));
+        }
+        context.begin_layer(&options)
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn context_end_layer(context: c_longlong) -> bool {
+    unsafe {
+        if context == 0 {
+            return false;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        context.end_layer()
+    }
+}
+
+/// Starts a layer masked by `asset` drawn into the rectangle, closed by `context_end_layer`.
+#[no_mangle]
+pub extern "C" fn context_begin_image_mask(
+    context: c_longlong,
+    asset: c_longlong,
+    x: c_float,
+    y: c_float,
+    width: c_float,
+    height: c_float,
+    mask_type: MaskType,
+) -> bool {
+    unsafe {
+        if context == 0 || asset == 0 {
+            return false;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        let asset: *const ImageAsset = asset as _;
+        context.begin_image_mask(&*asset, x, y, width, height, mask_type)
+    }
+}
+
+/// Starts a layer masked by a color, gradient or pattern, closed by `context_end_layer`.
+#[no_mangle]
+pub extern "C" fn context_begin_style_mask(
+    context: c_longlong,
+    style: c_longlong,
+    mask_type: MaskType,
+) -> bool {
+    unsafe {
+        if context == 0 || style == 0 {
+            return false;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        let style: *const PaintStyle = style as _;
+        context.begin_style_mask(&*style, mask_type)
+    }
+}
+
+/// Starts a layer clipped to `path`, 0 using the current path, with edges faded over
+/// `feather` pixels, closed by `context_end_layer`.
+#[no_mangle]
+pub extern "C" fn context_begin_path_mask(
+    context: c_longlong,
+    path: c_longlong,
+    rule: FillRule,
+    feather: c_float,
+) {
+    unsafe {
+        if context == 0 {
+            return;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        if path == 0 {
+            context.begin_path_mask(None, rule, feather)
+        } else {
+            let path: *mut Path = path as _;
+            context.begin_path_mask(Some(&mut *path), rule, feather)
+        }
+    }
+}
+
+fn box_shadows_from_raw(
+    values: *const c_float,
+    values_length: usize,
+    colors: *const c_uint,
+    colors_length: usize,
+) -> Option<Vec<BoxShadow>> {
+    if values.is_null() || colors.is_null() {
+        return None;
+    }
+    let values = unsafe { std::slice::from_raw_parts(values, values_length) };
+    let colors = unsafe { std::slice::from_raw_parts(colors, colors_length) };
+    BoxShadow::list_from_slices(values, colors)
+}
+
+/// Fills `path`, 0 using the current path, with box shadows given as five values each, offset
+/// x, offset y, blur, spread and inset, and one ARGB color per shadow.
+#[no_mangle]
+pub extern "C" fn context_fill_with_box_shadows(
+    context: c_longlong,
+    path: c_longlong,
+    rule: FillRule,
+    values: *const c_float,
+    values_length: usize,
+    colors: *const c_uint,
+    colors_length: usize,
+) {
+    unsafe {
+        if context == 0 {
+            return;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        if let Some(shadows) = box_shadows_from_raw(values, values_length, colors, colors_length) {
+            if path == 0 {
+                context.fill_with_box_shadows(None, rule, shadows.as_slice())
+            } else {
+                let path: *mut Path = path as _;
+                context.fill_with_box_shadows(Some(&mut *path), rule, shadows.as_slice())
+            }
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn context_fill_rect_with_box_shadows(
+    context: c_longlong,
+    x: c_float,
+    y: c_float,
+    width: c_float,
+    height: c_float,
+    values: *const c_float,
+    values_length: usize,
+    colors: *const c_uint,
+    colors_length: usize,
+) {
+    unsafe {
+        if context == 0 {
+            return;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        if let Some(shadows) = box_shadows_from_raw(values, values_length, colors, colors_length) {
+            let rect = Rect::from_xywh(x, y, width, height);
+            context.fill_rect_with_box_shadows(&rect, shadows.as_slice())
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn context_fill_round_rect_with_box_shadows(
+    context: c_longlong,
+    x: c_float,
+    y: c_float,
+    width: c_float,
+    height: c_float,
+    top_left: c_float,
+    top_right: c_float,
+    bottom_right: c_float,
+    bottom_left: c_float,
+    values: *const c_float,
+    values_length: usize,
+    colors: *const c_uint,
+    colors_length: usize,
+) {
+    unsafe {
+        if context == 0 {
+            return;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        if let Some(shadows) = box_shadows_from_raw(values, values_length, colors, colors_length) {
+            context.fill_round_rect_with_box_shadows(
+                x,
+                y,
+                width,
+                height,
+                [
+                    top_left, top_left, top_right, top_right, bottom_right, bottom_right,
+                    bottom_left, bottom_left,
+                ],
+                shadows.as_slice(),
+            )
+        }
+    }
+}
+
 #[no_mangle]
 pub extern "C" fn context_rotate(context: c_longlong, angle: c_float) {
     unsafe {
@@ -1877,7 +2904,21 @@ pub extern "C" fn context_set_transform_matrix(context: c_longlong, matrix: c_lo
         let context = &mut *context;
         let matrix: *const Matrix = matrix as _;
         let matrix = &*matrix;
-        context.set_transform_matrix(&matrix.matrix.to_m33())
+        context.set_transform_matrix(matrix)
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn context_transform_matrix(context: c_longlong, matrix: c_longlong) {
+    unsafe {
+        if context == 0 || matrix == 0 {
+            return;
+        }
+        let context: *mut Context = context as _;
+        let context = &mut *context;
+        let matrix: *const Matrix = matrix as _;
+        let matrix = &*matrix;
+        context.transform_with_matrix(matrix)
     }
 }
 
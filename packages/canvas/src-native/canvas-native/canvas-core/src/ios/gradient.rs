@@ -1,7 +1,11 @@
 use std::ffi::CStr;
-use std::os::raw::{c_char, c_float, c_longlong};
+use std::os::raw::{c_char, c_float, c_longlong, c_uint};
 
+use crate::common::context::fill_and_stroke_styles::gradient_interpolation::{
+    HueInterpolationMethod, InterpolationColorSpace,
+};
 use crate::common::context::fill_and_stroke_styles::paint::PaintStyle;
+use crate::common::utils::color::parse_color;
 
 #[no_mangle]
 pub extern "C" fn gradient_add_color_stop(style: c_longlong, stop: c_float, color: *const c_char) {
@@ -14,19 +18,122 @@ pub extern "C" fn gradient_add_color_stop(style: c_longlong, stop: c_float, colo
         match style {
             PaintStyle::Gradient(gradient) => {
                 let color = CStr::from_ptr(color).to_string_lossy();
-                if let Ok(color) = color.as_ref().parse::<css_color_parser::Color>() {
-                    gradient.add_color_stop(
-                        stop,
-                        skia_safe::Color::from_argb(
-                            (color.a * 255.0) as u8,
-                            color.r,
-                            color.g,
-                            color.b,
-                        ),
-                    )
+                if let Some(color) = parse_color(color.as_ref()) {
+                    gradient.add_color_stop(stop, color)
                 }
             }
             _ => {}
         }
     }
 }
+
+/// Sets a corner of a mesh or four-corner gradient, 0 being the top left and going clockwise.
+#[no_mangle]
+pub extern "C" fn gradient_set_corner_color(style: c_longlong, corner: c_uint, color: *const c_char) {
+    if style == 0 || color.is_null() {
+        return;
+    }
+    unsafe {
+        let style: *mut PaintStyle = style as _;
+        let style = &mut *style;
+        match style {
+            PaintStyle::Gradient(gradient) => {
+                let color = CStr::from_ptr(color).to_string_lossy();
+                if let Some(color) = parse_color(color.as_ref()) {
+                    gradient.set_corner_color(corner as usize, color)
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn gradient_set_interpolation_color_space(style: c_longlong, color_space: InterpolationColorSpace) {
+    if style == 0 {
+        return;
+    }
+    unsafe {
+        let style: *mut PaintStyle = style as _;
+        let style = &mut *style;
+        match style {
+            PaintStyle::Gradient(gradient) => gradient.set_interpolation_color_space(color_space),
+            _ => {}
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn gradient_get_interpolation_color_space(style: c_longlong) -> InterpolationColorSpace {
+    if style == 0 {
+        return InterpolationColorSpace::default();
+    }
+    unsafe {
+        let style: *const PaintStyle = style as _;
+        let style = &*style;
+        match style {
+            PaintStyle::Gradient(gradient) => gradient.interpolation().color_space,
+            _ => InterpolationColorSpace::default(),
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn gradient_set_hue_interpolation_method(style: c_longlong, method: HueInterpolationMethod) {
+    if style == 0 {
+        return;
+    }
+    unsafe {
+        let style: *mut PaintStyle = style as _;
+        let style = &mut *style;
+        match style {
+            PaintStyle::Gradient(gradient) => gradient.set_hue_interpolation_method(method),
+            _ => {}
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn gradient_get_hue_interpolation_method(style: c_longlong) -> HueInterpolationMethod {
+    if style == 0 {
+        return HueInterpolationMethod::default();
+    }
+    unsafe {
+        let style: *const PaintStyle = style as _;
+        let style = &*style;
+        match style {
+            PaintStyle::Gradient(gradient) => gradient.interpolation().hue_method,
+            _ => HueInterpolationMethod::default(),
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn gradient_set_dither(style: c_longlong, dither: bool) {
+    if style == 0 {
+        return;
+    }
+    unsafe {
+        let style: *mut PaintStyle = style as _;
+        let style = &mut *style;
+        match style {
+            PaintStyle::Gradient(gradient) => gradient.set_dither(dither),
+            _ => {}
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn gradient_get_dither(style: c_longlong) -> bool {
+    if style == 0 {
+        return false;
+    }
+    unsafe {
+        let style: *const PaintStyle = style as _;
+        let style = &*style;
+        match style {
+            PaintStyle::Gradient(gradient) => gradient.interpolation().dither,
+            _ => false,
+        }
+    }
+}
@@ -1,12 +1,41 @@
 use std::os::raw::{c_int, c_longlong};
 
-use crate::common::context::pixel_manipulation::image_data::ImageData;
+use crate::common::context::color_space::PredefinedColorSpace;
+use crate::common::context::pixel_manipulation::image_data::{ImageData, ImageDataStorageFormat};
 
 #[no_mangle]
 pub extern "C" fn image_data_create(width: c_int, height: c_int) -> c_longlong {
     Box::into_raw(Box::new(ImageData::new(width, height))) as c_longlong
 }
 
+#[no_mangle]
+pub extern "C" fn image_data_create_with_color_space(
+    width: c_int,
+    height: c_int,
+    color_space: PredefinedColorSpace,
+) -> c_longlong {
+    Box::into_raw(Box::new(ImageData::new_with_color_space(
+        width,
+        height,
+        color_space,
+    ))) as c_longlong
+}
+
+#[no_mangle]
+pub extern "C" fn image_data_create_with_settings(
+    width: c_int,
+    height: c_int,
+    color_space: PredefinedColorSpace,
+    storage_format: ImageDataStorageFormat,
+) -> c_longlong {
+    Box::into_raw(Box::new(ImageData::new_with_settings(
+        width,
+        height,
+        color_space,
+        storage_format,
+    ))) as c_longlong
+}
+
 #[no_mangle]
 pub extern "C" fn image_data_width(image_data: c_longlong) -> c_int {
     if image_data == 0 {
@@ -55,6 +84,30 @@ pub extern "C" fn image_data_data_length(image_data: c_longlong) -> usize {
     }
 }
 
+#[no_mangle]
+pub extern "C" fn image_data_color_space(image_data: c_longlong) -> PredefinedColorSpace {
+    if image_data == 0 {
+        return PredefinedColorSpace::default();
+    }
+    unsafe {
+        let image_data: *mut ImageData = image_data as _;
+        let image_data = &mut *image_data;
+        image_data.color_space()
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn image_data_storage_format(image_data: c_longlong) -> ImageDataStorageFormat {
+    if image_data == 0 {
+        return ImageDataStorageFormat::default();
+    }
+    unsafe {
+        let image_data: *mut ImageData = image_data as _;
+        let image_data = &mut *image_data;
+        image_data.storage_format()
+    }
+}
+
 #[no_mangle]
 pub extern "C" fn destroy_image_data(image_data: c_longlong) {
     if image_data == 0 {
@@ -1,6 +1,46 @@
-use std::os::raw::{c_float, c_longlong};
+use std::ffi::{CStr, CString};
+use std::os::raw::{c_char, c_float, c_longlong};
 
-use crate::common::context::matrix::Matrix;
+use crate::common::context::Device;
+use crate::common::context::matrix::{DecomposedMatrix, Matrix};
+use crate::common::ffi::f32_array::F32Array;
+use crate::common::ffi::f64_array::F64Array;
+
+fn matrix_ref<'a>(matrix: c_longlong) -> Option<&'a Matrix> {
+    if matrix == 0 {
+        return None;
+    }
+    unsafe {
+        let matrix: *const Matrix = matrix as _;
+        Some(&*matrix)
+    }
+}
+
+fn matrix_mut<'a>(matrix: c_longlong) -> Option<&'a mut Matrix> {
+    if matrix == 0 {
+        return None;
+    }
+    unsafe {
+        let matrix: *mut Matrix = matrix as _;
+        Some(&mut *matrix)
+    }
+}
+
+fn to_handle(matrix: Matrix) -> c_longlong {
+    Box::into_raw(Box::new(matrix)) as c_longlong
+}
+
+/// Runs `op` on a copy of `matrix` and returns the copy, or 0 if `matrix` is 0.
+fn with_copy<F: FnOnce(&mut Matrix)>(matrix: c_longlong, op: F) -> c_longlong {
+    match matrix_ref(matrix) {
+        Some(matrix) => {
+            let mut matrix = matrix.clone();
+            op(&mut matrix);
+            to_handle(matrix)
+        }
+        None => 0,
+    }
+}
 
 #[no_mangle]
 pub extern "C" fn matrix_create() -> c_longlong {
@@ -550,6 +590,343 @@ pub extern "C" fn matrix_set_m44(matrix: c_longlong, m44: c_float) {
     }
 }
 
+/// `data` must hold 16 values, m11 to m44 in order.
+#[no_mangle]
+pub extern "C" fn matrix_create_with_values(data: *const c_float, data_len: usize) -> c_longlong {
+    if data.is_null() || data_len != 16 {
+        return 0;
+    }
+    let mut values = [0f32; 16];
+    values.copy_from_slice(unsafe { std::slice::from_raw_parts(data, data_len) });
+    to_handle(Matrix::from_float32_array(&values))
+}
+
+/// Parses a CSS transform list, lengths resolving against a viewport of `width` by `height`
+/// with `ppi` pixels per inch. Returns 0 if the value is invalid.
+#[no_mangle]
+pub extern "C" fn matrix_create_from_css(
+    value: *const c_char,
+    width: c_float,
+    height: c_float,
+    density: c_float,
+    ppi: c_float,
+) -> c_longlong {
+    if value.is_null() {
+        return 0;
+    }
+    let value = unsafe { CStr::from_ptr(value) }.to_string_lossy();
+    let device = Device::new_non_gpu(width, height, density, ppi);
+    match Matrix::from_css(value.as_ref(), device) {
+        Some(matrix) => to_handle(matrix),
+        None => 0,
+    }
+}
+
+/// Returns null if `matrix` is 0 or holds values that are not finite.
+#[no_mangle]
+pub extern "C" fn matrix_to_string(matrix: c_longlong) -> *const c_char {
+    match matrix_ref(matrix).and_then(|matrix| matrix.to_string()) {
+        Some(value) => CString::new(value).unwrap().into_raw(),
+        None => std::ptr::null(),
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn matrix_is_2d(matrix: c_longlong) -> bool {
+    matrix_ref(matrix)
+        .map(|matrix| matrix.is_2d())
+        .unwrap_or(false)
+}
+
+#[no_mangle]
+pub extern "C" fn matrix_is_identity(matrix: c_longlong) -> bool {
+    matrix_ref(matrix)
+        .map(|matrix| matrix.is_identity())
+        .unwrap_or(false)
+}
+
+#[no_mangle]
+pub extern "C" fn matrix_multiply(matrix: c_longlong, other: c_longlong) -> c_longlong {
+    match (matrix_ref(matrix), matrix_ref(other)) {
+        (Some(matrix), Some(other)) => to_handle(matrix.multiply(other)),
+        _ => 0,
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn matrix_multiply_self(matrix: c_longlong, other: c_longlong) {
+    // `other` may be the same handle as `matrix`, so copy it before borrowing `matrix` mutably
+    if let Some(other) = matrix_ref(other).cloned() {
+        if let Some(matrix) = matrix_mut(matrix) {
+            matrix.multiply_self(&other)
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn matrix_pre_multiply_self(matrix: c_longlong, other: c_longlong) {
+    // `other` may be the same handle as `matrix`, so copy it before borrowing `matrix` mutably
+    if let Some(other) = matrix_ref(other).cloned() {
+        if let Some(matrix) = matrix_mut(matrix) {
+            matrix.pre_multiply_self(&other)
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn matrix_inverse(matrix: c_longlong) -> c_longlong {
+    match matrix_ref(matrix) {
+        Some(matrix) => to_handle(matrix.inverse()),
+        None => 0,
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn matrix_invert_self(matrix: c_longlong) {
+    if let Some(matrix) = matrix_mut(matrix) {
+        matrix.invert_self()
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn matrix_translate(
+    matrix: c_longlong,
+    tx: c_float,
+    ty: c_float,
+    tz: c_float,
+) -> c_longlong {
+    with_copy(matrix, |matrix| matrix.translate_self(tx, ty, tz))
+}
+
+#[no_mangle]
+pub extern "C" fn matrix_translate_self(matrix: c_longlong, tx: c_float, ty: c_float, tz: c_float) {
+    if let Some(matrix) = matrix_mut(matrix) {
+        matrix.translate_self(tx, ty, tz)
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn matrix_scale(
+    matrix: c_longlong,
+    sx: c_float,
+    sy: c_float,
+    sz: c_float,
+    origin_x: c_float,
+    origin_y: c_float,
+    origin_z: c_float,
+) -> c_longlong {
+    with_copy(matrix, |matrix| {
+        matrix.scale_self(sx, sy, sz, origin_x, origin_y, origin_z)
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn matrix_scale_self(
+    matrix: c_longlong,
+    sx: c_float,
+    sy: c_float,
+    sz: c_float,
+    origin_x: c_float,
+    origin_y: c_float,
+    origin_z: c_float,
+) {
+    if let Some(matrix) = matrix_mut(matrix) {
+        matrix.scale_self(sx, sy, sz, origin_x, origin_y, origin_z)
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn matrix_scale3d(
+    matrix: c_longlong,
+    scale: c_float,
+    origin_x: c_float,
+    origin_y: c_float,
+    origin_z: c_float,
+) -> c_longlong {
+    with_copy(matrix, |matrix| {
+        matrix.scale3d_self(scale, origin_x, origin_y, origin_z)
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn matrix_scale3d_self(
+    matrix: c_longlong,
+    scale: c_float,
+    origin_x: c_float,
+    origin_y: c_float,
+    origin_z: c_float,
+) {
+    if let Some(matrix) = matrix_mut(matrix) {
+        matrix.scale3d_self(scale, origin_x, origin_y, origin_z)
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn matrix_rotate(
+    matrix: c_longlong,
+    rot_x: c_float,
+    rot_y: c_float,
+    rot_z: c_float,
+) -> c_longlong {
+    with_copy(matrix, |matrix| matrix.rotate_self(rot_x, rot_y, rot_z))
+}
+
+#[no_mangle]
+pub extern "C" fn matrix_rotate_self(
+    matrix: c_longlong,
+    rot_x: c_float,
+    rot_y: c_float,
+    rot_z: c_float,
+) {
+    if let Some(matrix) = matrix_mut(matrix) {
+        matrix.rotate_self(rot_x, rot_y, rot_z)
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn matrix_rotate_from_vector(
+    matrix: c_longlong,
+    x: c_float,
+    y: c_float,
+) -> c_longlong {
+    with_copy(matrix, |matrix| matrix.rotate_from_vector_self(x, y))
+}
+
+#[no_mangle]
+pub extern "C" fn matrix_rotate_from_vector_self(matrix: c_longlong, x: c_float, y: c_float) {
+    if let Some(matrix) = matrix_mut(matrix) {
+        matrix.rotate_from_vector_self(x, y)
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn matrix_rotate_axis_angle(
+    matrix: c_longlong,
+    x: c_float,
+    y: c_float,
+    z: c_float,
+    angle: c_float,
+) -> c_longlong {
+    with_copy(matrix, |matrix| {
+        matrix.rotate_axis_angle_self(x, y, z, angle)
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn matrix_rotate_axis_angle_self(
+    matrix: c_longlong,
+    x: c_float,
+    y: c_float,
+    z: c_float,
+    angle: c_float,
+) {
+    if let Some(matrix) = matrix_mut(matrix) {
+        matrix.rotate_axis_angle_self(x, y, z, angle)
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn matrix_skew_x(matrix: c_longlong, angle: c_float) -> c_longlong {
+    with_copy(matrix, |matrix| matrix.skew_x_self(angle))
+}
+
+#[no_mangle]
+pub extern "C" fn matrix_skew_x_self(matrix: c_longlong, angle: c_float) {
+    if let Some(matrix) = matrix_mut(matrix) {
+        matrix.skew_x_self(angle)
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn matrix_skew_y(matrix: c_longlong, angle: c_float) -> c_longlong {
+    with_copy(matrix, |matrix| matrix.skew_y_self(angle))
+}
+
+#[no_mangle]
+pub extern "C" fn matrix_skew_y_self(matrix: c_longlong, angle: c_float) {
+    if let Some(matrix) = matrix_mut(matrix) {
+        matrix.skew_y_self(angle)
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn matrix_flip_x(matrix: c_longlong) -> c_longlong {
+    match matrix_ref(matrix) {
+        Some(matrix) => to_handle(matrix.flip_x()),
+        None => 0,
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn matrix_flip_y(matrix: c_longlong) -> c_longlong {
+    match matrix_ref(matrix) {
+        Some(matrix) => to_handle(matrix.flip_y()),
+        None => 0,
+    }
+}
+
+/// Returns the x, y, z and w of the transformed point, or null if `matrix` is 0.
+#[no_mangle]
+pub extern "C" fn matrix_transform_point(
+    matrix: c_longlong,
+    x: c_float,
+    y: c_float,
+    z: c_float,
+    w: c_float,
+) -> *mut F32Array {
+    match matrix_ref(matrix) {
+        Some(matrix) => Box::into_raw(Box::new(F32Array::from(
+            matrix.transform_point(x, y, z, w).to_vec(),
+        ))),
+        None => std::ptr::null_mut(),
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn matrix_to_float32_array(matrix: c_longlong) -> *mut F32Array {
+    match matrix_ref(matrix) {
+        Some(matrix) => Box::into_raw(Box::new(F32Array::from(matrix.to_float32_array().to_vec()))),
+        None => std::ptr::null_mut(),
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn matrix_to_float64_array(matrix: c_longlong) -> *mut F64Array {
+    match matrix_ref(matrix) {
+        Some(matrix) => Box::into_raw(Box::new(F64Array::from(matrix.to_float64_array().to_vec()))),
+        None => std::ptr::null_mut(),
+    }
+}
+
+/// Returns translate, scale, skew, perspective and quaternion in order, 17 values, or null if
+/// the matrix cannot be decomposed.
+#[no_mangle]
+pub extern "C" fn matrix_decompose(matrix: c_longlong) -> *mut F32Array {
+    match matrix_ref(matrix).and_then(|matrix| matrix.decompose()) {
+        Some(decomposed) => Box::into_raw(Box::new(F32Array::from(decomposed.to_array().to_vec()))),
+        None => std::ptr::null_mut(),
+    }
+}
+
+/// `data` must hold the 17 values returned by `matrix_decompose`.
+#[no_mangle]
+pub extern "C" fn matrix_recompose(data: *const c_float, data_len: usize) -> c_longlong {
+    if data.is_null() || data_len != DecomposedMatrix::LENGTH {
+        return 0;
+    }
+    let mut values = [0f32; DecomposedMatrix::LENGTH];
+    values.copy_from_slice(unsafe { std::slice::from_raw_parts(data, data_len) });
+    to_handle(DecomposedMatrix::from_array(&values).recompose())
+}
+
+#[no_mangle]
+pub extern "C" fn matrix_interpolate(from: c_longlong, to: c_longlong, t: c_float) -> c_longlong {
+    match (matrix_ref(from), matrix_ref(to)) {
+        (Some(from), Some(to)) => to_handle(from.interpolate(to, t)),
+        _ => 0,
+    }
+}
+
 #[no_mangle]
 pub extern "C" fn destroy_matrix(matrix: c_longlong) {
     if matrix == 0 {
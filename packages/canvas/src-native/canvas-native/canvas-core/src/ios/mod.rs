@@ -1,10 +1,13 @@
 use std::ffi::{c_float, CString};
 use std::os::raw::{c_char, c_longlong, c_void};
 
+use skia_safe::Picture;
+
 use crate::common::context::Context;
 use crate::common::context::drawing_text::text_metrics::TextMetrics;
 use crate::common::context::fill_and_stroke_styles::paint::PaintStyle;
 
+pub mod canvas_filter;
 pub mod context;
 pub mod gl;
 pub mod gradient;
@@ -15,6 +18,7 @@ pub mod matrix;
 pub mod paint;
 pub mod path;
 pub mod pattern;
+pub mod runtime_effect;
 pub mod svg;
 pub mod text_decoder;
 pub mod text_encoder;
@@ -53,6 +57,17 @@ pub extern "C" fn destroy_paint_style(style: c_longlong) {
     }
 }
 
+#[no_mangle]
+pub extern "C" fn destroy_picture(picture: c_longlong) {
+    if picture == 0 {
+        return;
+    }
+    unsafe {
+        let picture: *mut Picture = picture as _;
+        let _ = Box::from_raw(picture);
+    }
+}
+
 #[no_mangle]
 pub extern "C" fn destroy_text_metrics(metrics: c_longlong) {
     if metrics == 0 {
@@ -0,0 +1,119 @@
+use std::ffi::CStr;
+use std::os::raw::{c_char, c_float, c_int, c_longlong};
+
+use crate::common::context::fill_and_stroke_styles::paint::PaintStyle;
+use crate::common::context::image_asset::ImageAsset;
+use crate::common::context::matrix::Matrix;
+
+#[no_mangle]
+pub extern "C" fn runtime_effect_set_uniform(
+    style: c_longlong,
+    name: *const c_char,
+    data: *const c_float,
+    data_len: usize,
+) -> bool {
+    if style == 0 || name.is_null() || data.is_null() {
+        return false;
+    }
+    unsafe {
+        let style: *mut PaintStyle = style as _;
+        let style = &mut *style;
+        match style {
+            PaintStyle::RuntimeEffect(effect) => {
+                let name = CStr::from_ptr(name).to_string_lossy();
+                let data = std::slice::from_raw_parts(data, data_len);
+                effect.set_uniform(name.as_ref(), data)
+            }
+            _ => false,
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn runtime_effect_set_uniform_int(
+    style: c_longlong,
+    name: *const c_char,
+    data: *const c_int,
+    data_len: usize,
+) -> bool {
+    if style == 0 || name.is_null() || data.is_null() {
+        return false;
+    }
+    unsafe {
+        let style: *mut PaintStyle = style as _;
+        let style = &mut *style;
+        match style {
+            PaintStyle::RuntimeEffect(effect) => {
+                let name = CStr::from_ptr(name).to_string_lossy();
+                let data = std::slice::from_raw_parts(data, data_len);
+                effect.set_uniform_int(name.as_ref(), data)
+            }
+            _ => false,
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn runtime_effect_set_child(
+    style: c_longlong,
+    name: *const c_char,
+    child: c_longlong,
+) -> bool {
+    if style == 0 || name.is_null() || child == 0 {
+        return false;
+    }
+    unsafe {
+        let child: *const PaintStyle = child as _;
+        let child = (&*child).clone();
+        let style: *mut PaintStyle = style as _;
+        let style = &mut *style;
+        match style {
+            PaintStyle::RuntimeEffect(effect) => {
+                let name = CStr::from_ptr(name).to_string_lossy();
+                effect.set_child(name.as_ref(), child)
+            }
+            _ => false,
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn runtime_effect_set_child_asset(
+    style: c_longlong,
+    name: *const c_char,
+    asset: c_longlong,
+) -> bool {
+    if style == 0 || name.is_null() || asset == 0 {
+        return false;
+    }
+    unsafe {
+        let asset: *const ImageAsset = asset as _;
+        let asset = &*asset;
+        let style: *mut PaintStyle = style as _;
+        let style = &mut *style;
+        match (style, asset.skia_image()) {
+            (PaintStyle::RuntimeEffect(effect), Some(image)) => {
+                let name = CStr::from_ptr(name).to_string_lossy();
+                effect.set_child_image(name.as_ref(), image)
+            }
+            _ => false,
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn runtime_effect_set_transform(style: c_longlong, matrix: c_longlong) {
+    if style == 0 || matrix == 0 {
+        return;
+    }
+    unsafe {
+        let style: *mut PaintStyle = style as _;
+        let style = &mut *style;
+        let matrix: *mut Matrix = matrix as _;
+        let matrix = &mut *matrix;
+        match style {
+            PaintStyle::RuntimeEffect(effect) => effect.set_transform(&matrix),
+            _ => {}
+        }
+    }
+}